@@ -225,14 +225,17 @@ impl Component<Msg, NoUserEvent> for LetterCounter {
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
                 modifiers: KeyModifiers::NONE,
+                ..
             }) if ch.is_alphabetic() => Cmd::Submit,
             Event::Keyboard(KeyEvent {
                 code: Key::Tab,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(Msg::LetterCounterBlur), // Return focus lost
             Event::Keyboard(KeyEvent {
                 code: Key::Esc,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(Msg::AppClose),
             _ => Cmd::None,
         };
@@ -277,14 +280,17 @@ impl Component<Msg, NoUserEvent> for DigitCounter {
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
                 modifiers: KeyModifiers::NONE,
+                ..
             }) if ch.is_digit(10) => Cmd::Submit,
             Event::Keyboard(KeyEvent {
                 code: Key::Tab,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(Msg::DigitCounterBlur), // Return focus lost
             Event::Keyboard(KeyEvent {
                 code: Key::Esc,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(Msg::AppClose),
             _ => Cmd::None,
         };
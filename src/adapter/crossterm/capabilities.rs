@@ -0,0 +1,188 @@
+//! ## Capabilities
+//!
+//! terminal capabilities detection for crossterm
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::env;
+
+/// ## TerminalCapabilities
+///
+/// Describes the features supported by the current terminal.
+/// Use it to decide whether to enable mouse capture, bracketed paste and so on, in order to degrade gracefully
+/// on terminals which don't support them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// Terminal can report mouse events
+    pub mouse: bool,
+    /// Terminal can report focus gained/lost events
+    pub focus: bool,
+    /// Terminal supports bracketed paste
+    pub paste: bool,
+    /// Terminal supports the kitty keyboard protocol (progressive enhancement)
+    pub enhanced_keyboard: bool,
+    /// Terminal supports 24-bit colors
+    pub truecolor: bool,
+}
+
+/// ## PasteMode
+///
+/// Describes how `Event::Paste` is produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteMode {
+    /// Pasted text is reported by the terminal through bracketed paste
+    Bracketed,
+    /// Pasted text is received as keys and detected by the paste heuristic (see `EventListenerCfg::paste_heuristic()`)
+    Heuristic,
+}
+
+/// ### capabilities
+///
+/// Detect the capabilities of the current terminal.
+///
+/// The crossterm version in use doesn't provide a way to query the terminal for its features,
+/// so capabilities are guessed from the `TERM`, `TERM_PROGRAM`, `COLORTERM` and `KITTY_WINDOW_ID`
+/// environment variables. The result is best-effort: a terminal may support more than what's reported here.
+pub fn capabilities() -> TerminalCapabilities {
+    TerminalCapabilities::detect(|name| env::var(name).ok())
+}
+
+impl TerminalCapabilities {
+    /// ### paste_mode
+    ///
+    /// Returns how paste events are produced when requested with `EventListenerCfg::paste_events()`.
+    ///
+    /// Even if the terminal supports bracketed paste (`paste`), the crossterm version in use can't parse
+    /// bracketed paste sequences, so pasted text is always received as keys and the paste heuristic is used.
+    pub fn paste_mode(&self) -> PasteMode {
+        PasteMode::Heuristic
+    }
+
+    /// ### detect
+    ///
+    /// Guess capabilities using `var` to read environment variables
+    fn detect<F>(var: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let term = var("TERM").unwrap_or_default().to_lowercase();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+        // Windows console doesn't set TERM, but supports mouse events through the console API
+        let xterm_like = match term.as_str() {
+            "" => cfg!(target_family = "windows"),
+            term => !matches!(term, "dumb" | "linux"),
+        };
+        let kitty = term == "xterm-kitty" || var("KITTY_WINDOW_ID").is_some();
+        let enhanced_keyboard = kitty
+            || term.starts_with("foot")
+            || matches!(term_program.as_str(), "wezterm" | "ghostty");
+        let truecolor = matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || term.contains("truecolor")
+            || term.contains("24bit")
+            || term.ends_with("-direct")
+            || kitty;
+        Self {
+            mouse: xterm_like,
+            focus: xterm_like,
+            paste: xterm_like,
+            enhanced_keyboard,
+            truecolor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)]) -> TerminalCapabilities {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        TerminalCapabilities::detect(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn should_detect_dumb_terminal() {
+        assert_eq!(detect(&[("TERM", "dumb")]), TerminalCapabilities::default());
+        assert_eq!(
+            detect(&[("TERM", "linux")]),
+            TerminalCapabilities::default()
+        );
+    }
+
+    #[test]
+    fn should_detect_xterm() {
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color")]),
+            TerminalCapabilities {
+                mouse: true,
+                focus: true,
+                paste: true,
+                enhanced_keyboard: false,
+                truecolor: false,
+            }
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]).truecolor,
+            true
+        );
+    }
+
+    #[test]
+    fn should_fall_back_to_paste_heuristic() {
+        assert_eq!(
+            detect(&[("TERM", "dumb")]).paste_mode(),
+            PasteMode::Heuristic
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color")]).paste_mode(),
+            PasteMode::Heuristic
+        );
+    }
+
+    #[test]
+    fn should_detect_kitty() {
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            TerminalCapabilities {
+                mouse: true,
+                focus: true,
+                paste: true,
+                enhanced_keyboard: true,
+                truecolor: true,
+            }
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]).enhanced_keyboard,
+            true
+        );
+    }
+}
@@ -25,21 +25,22 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{Event, Key, KeyEvent, KeyModifiers};
+use super::{Event, Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 use crossterm::event::{
     Event as XtermEvent, KeyCode as XtermKeyCode, KeyEvent as XtermKeyEvent,
-    KeyModifiers as XtermKeyModifiers,
+    KeyModifiers as XtermKeyModifiers, MouseButton as XtermMouseButton,
+    MouseEvent as XtermMouseEvent, MouseEventKind as XtermMouseEventKind,
 };
 
 impl<U> From<XtermEvent> for Event<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
     fn from(e: XtermEvent) -> Self {
         match e {
             XtermEvent::Key(key) => Self::Keyboard(key.into()),
-            XtermEvent::Mouse(_) => Self::None,
+            XtermEvent::Mouse(mouse) => Self::Mouse(mouse.into()),
             XtermEvent::Resize(w, h) => Self::WindowResize(w, h),
         }
     }
@@ -47,10 +48,7 @@ where
 
 impl From<XtermKeyEvent> for KeyEvent {
     fn from(e: XtermKeyEvent) -> Self {
-        Self {
-            code: e.code.into(),
-            modifiers: e.modifiers.into(),
-        }
+        Self::new(e.code.into(), e.modifiers.into())
     }
 }
 
@@ -79,6 +77,35 @@ impl From<XtermKeyCode> for Key {
     }
 }
 
+impl From<XtermMouseEvent> for MouseEvent {
+    fn from(e: XtermMouseEvent) -> Self {
+        Self::new(e.kind.into(), e.column, e.row, e.modifiers.into())
+    }
+}
+
+impl From<XtermMouseEventKind> for MouseEventKind {
+    fn from(k: XtermMouseEventKind) -> Self {
+        match k {
+            XtermMouseEventKind::Down(b) => Self::Down(b.into()),
+            XtermMouseEventKind::Up(b) => Self::Up(b.into()),
+            XtermMouseEventKind::Drag(b) => Self::Drag(b.into()),
+            XtermMouseEventKind::Moved => Self::Moved,
+            XtermMouseEventKind::ScrollDown => Self::ScrollDown,
+            XtermMouseEventKind::ScrollUp => Self::ScrollUp,
+        }
+    }
+}
+
+impl From<XtermMouseButton> for MouseButton {
+    fn from(b: XtermMouseButton) -> Self {
+        match b {
+            XtermMouseButton::Left => Self::Left,
+            XtermMouseButton::Right => Self::Right,
+            XtermMouseButton::Middle => Self::Middle,
+        }
+    }
+}
+
 impl From<XtermKeyModifiers> for KeyModifiers {
     fn from(k: XtermKeyModifiers) -> Self {
         let mut km = KeyModifiers::empty();
@@ -103,8 +130,6 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
-    use crossterm::event::{MouseEvent as XtermMouseEvent, MouseEventKind as XtermMouseEventKind};
-
     #[test]
     fn adapt_crossterm_keycode() {
         assert_eq!(Key::from(XtermKeyCode::BackTab), Key::BackTab);
@@ -133,7 +158,7 @@ mod test {
             KeyModifiers::from(
                 XtermKeyModifiers::CONTROL | XtermKeyModifiers::SHIFT | XtermKeyModifiers::ALT
             ),
-            KeyModifiers::all()
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT | KeyModifiers::ALT
         );
         assert_eq!(
             KeyModifiers::from(XtermKeyModifiers::ALT),
@@ -152,6 +177,46 @@ mod test {
         );
     }
 
+    #[test]
+    fn adapt_crossterm_mouse_event() {
+        assert_eq!(
+            MouseEvent::from(XtermMouseEvent {
+                kind: XtermMouseEventKind::Down(XtermMouseButton::Left),
+                column: 8,
+                row: 3,
+                modifiers: XtermKeyModifiers::CONTROL,
+            }),
+            MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                modifiers: KeyModifiers::CONTROL,
+                column: 8,
+                row: 3,
+                click_count: 1,
+                delta: 0,
+            }
+        );
+        assert_eq!(
+            MouseEventKind::from(XtermMouseEventKind::Up(XtermMouseButton::Right)),
+            MouseEventKind::Up(MouseButton::Right)
+        );
+        assert_eq!(
+            MouseEventKind::from(XtermMouseEventKind::Drag(XtermMouseButton::Middle)),
+            MouseEventKind::Drag(MouseButton::Middle)
+        );
+        assert_eq!(
+            MouseEventKind::from(XtermMouseEventKind::Moved),
+            MouseEventKind::Moved
+        );
+        assert_eq!(
+            MouseEventKind::from(XtermMouseEventKind::ScrollDown),
+            MouseEventKind::ScrollDown
+        );
+        assert_eq!(
+            MouseEventKind::from(XtermMouseEventKind::ScrollUp),
+            MouseEventKind::ScrollUp
+        );
+    }
+
     #[test]
     fn adapt_crossterm_event() {
         type AppEvent = Event<MockEvent>;
@@ -172,7 +237,30 @@ mod test {
                 row: 0,
                 modifiers: XtermKeyModifiers::empty(),
             })),
-            Event::None
+            Event::Mouse(MouseEvent::new(
+                MouseEventKind::Moved,
+                0,
+                0,
+                KeyModifiers::NONE
+            ))
         );
     }
+
+    #[test]
+    fn adapt_crossterm_scroll_modifiers() {
+        let scroll = |kind, modifiers| {
+            MouseEvent::from(XtermMouseEvent {
+                kind,
+                column: 4,
+                row: 2,
+                modifiers,
+            })
+        };
+        let m = scroll(XtermMouseEventKind::ScrollUp, XtermKeyModifiers::CONTROL);
+        assert_eq!(m.modifiers, KeyModifiers::CONTROL);
+        assert_eq!(m.is_zoom(), true);
+        let m = scroll(XtermMouseEventKind::ScrollDown, XtermKeyModifiers::empty());
+        assert_eq!(m.modifiers, KeyModifiers::NONE);
+        assert_eq!(m.is_zoom(), false);
+    }
 }
@@ -25,36 +25,184 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::Event;
+use super::{coalesce_scroll, ClickCounter, Event, EventMapFn, MouseEvent};
 
 use crate::listener::{ListenerError, ListenerResult, Poll};
-use crossterm::event as xterm;
+use bitflags::bitflags;
+use crossterm::event::{self as xterm, DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use std::io::stdout;
 use std::marker::PhantomData;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+bitflags! {
+    /// ## CaptureFlags
+    ///
+    /// Defines which terminal features the crossterm input listener should enable and report.
+    /// Only mouse capture is available: the crossterm version in use supports neither bracketed paste,
+    /// nor focus events, nor the kitty keyboard protocol.
+    pub struct CaptureFlags: u8 {
+        const NONE = 0b0000_0000;
+        /// Capture and report mouse events
+        const MOUSE = 0b0000_0001;
+    }
+}
 
 /// ## CrosstermInputListener
 ///
 /// The input listener for crossterm.
 /// If crossterm is enabled, this will already be exported as `InputEventListener` in the `adapter` module
-/// or you can use it directly in the event listener, calling `default_input_listener()` in the `EventListenerCfg`
+/// or you can use it directly in the event listener, calling `default_input_listener()` in the `EventListenerCfg`.
+/// The user event doesn't need to be `Send`, so the listener can be used by the `LocalEventListener` too
+/// (see `LocalEventListener::input_listener()`).
 pub struct CrosstermInputListener<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
-    ghost: PhantomData<U>,
+    ghost: PhantomData<fn() -> U>,
     interval: Duration,
+    clicks: ClickCounter,
+    scroll_window: Option<Duration>,
+    pending: Option<Event<U>>,
+    /// Features to capture; if `None`, the terminal is left untouched
+    capture: Option<CaptureFlags>,
+    /// Whether capture has already been enabled
+    captured: bool,
+    /// If set, is applied to the events read before reporting them
+    mapper: Option<EventMapFn<U>>,
 }
 
 impl<U> CrosstermInputListener<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
     pub fn new(interval: Duration) -> Self {
         Self {
-            ghost: PhantomData::default(),
+            ghost: PhantomData,
             interval: interval / 2,
+            clicks: ClickCounter::default(),
+            scroll_window: None,
+            pending: None,
+            capture: None,
+            captured: false,
+            mapper: None,
         }
     }
+
+    /// ### capture
+    ///
+    /// Set the terminal features to capture. Capture is enabled on the first `poll()` and disabled once the
+    /// listener is dropped. Mouse events are reported only if `CaptureFlags::MOUSE` is set.
+    /// If capture flags are not set, the listener won't change the terminal mode and will report
+    /// any event it reads.
+    pub fn capture(mut self, flags: CaptureFlags) -> Self {
+        self.capture = Some(flags);
+        self
+    }
+
+    /// ### multi_click_threshold
+    ///
+    /// Set the max time between two clicks on the same cell to report them as a double (or triple) click.
+    /// Default: 300ms
+    pub fn multi_click_threshold(mut self, threshold: Duration) -> Self {
+        self.clicks.set_threshold(threshold);
+        self
+    }
+
+    /// ### event_mapper
+    ///
+    /// Set a function applied to each event after it has been converted from the crossterm event:
+    /// the event returned is reported instead, while if `None` is returned, the event is dropped
+    pub fn event_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: FnMut(Event<U>) -> Option<Event<U>> + Send + 'static,
+    {
+        self.mapper = Some(Box::new(mapper));
+        self
+    }
+
+    /// ### map
+    ///
+    /// Apply the event mapper, if any, to the event
+    fn map(&mut self, ev: Option<Event<U>>) -> Option<Event<U>> {
+        match (ev, self.mapper.as_mut()) {
+            (Some(ev), Some(mapper)) => mapper(ev),
+            (ev, _) => ev,
+        }
+    }
+
+    /// ### coalesce_scroll
+    ///
+    /// Enable scroll coalescing: once a scroll event is read, the listener keeps reading scroll events
+    /// in the same direction for `window` and reports them as a single `MouseEvent`, whose `delta` is the
+    /// sum of all the scrolled lines.
+    /// By default coalescing is disabled and each wheel tick is reported as a distinct event.
+    pub fn coalesce_scroll(mut self, window: Duration) -> Self {
+        self.scroll_window = Some(window);
+        self
+    }
+
+    /// ### enable_capture
+    ///
+    /// Enable capture for the configured features, if not enabled yet
+    fn enable_capture(&mut self) -> ListenerResult<()> {
+        match self.capture {
+            Some(flags) if !self.captured => {
+                if flags.contains(CaptureFlags::MOUSE) {
+                    execute!(stdout(), EnableMouseCapture)
+                        .map_err(|err| ListenerError::PollFailed(err.to_string()))?;
+                }
+                self.captured = true;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// ### mouse_enabled
+    ///
+    /// Returns whether mouse events should be reported
+    fn mouse_enabled(&self) -> bool {
+        self.capture
+            .map(|x| x.contains(CaptureFlags::MOUSE))
+            .unwrap_or(true)
+    }
+
+    /// ### read
+    ///
+    /// Read the next event from crossterm, waiting up to `timeout`
+    fn read(&mut self, timeout: Duration) -> ListenerResult<Option<Event<U>>> {
+        match xterm::poll(timeout) {
+            Ok(true) => xterm::read()
+                .map(|x| match Event::from(x) {
+                    Event::Mouse(_) if !self.mouse_enabled() => None,
+                    Event::Mouse(ev) => Some(Event::Mouse(self.clicks.count(ev))),
+                    ev => Some(ev),
+                })
+                .map_err(|err| ListenerError::PollFailed(err.to_string())),
+            Ok(false) => Ok(None),
+            Err(err) => Err(ListenerError::PollFailed(err.to_string())),
+        }
+    }
+
+    /// ### read_scroll
+    ///
+    /// Keep reading scroll events for `window`, merging them into `ev`.
+    /// The first event which can't be merged is kept for the next poll.
+    fn read_scroll(&mut self, mut ev: MouseEvent, window: Duration) -> ListenerResult<MouseEvent> {
+        let deadline = Instant::now() + window;
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            match self.read(timeout)? {
+                None => break,
+                Some(Event::Mouse(next)) if coalesce_scroll(&mut ev, &next) => continue,
+                Some(other) => {
+                    self.pending = Some(other);
+                    break;
+                }
+            }
+        }
+        Ok(ev)
+    }
 }
 
 impl<U> Poll<U> for CrosstermInputListener<U>
@@ -62,12 +210,83 @@ where
     U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
 {
     fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
-        match xterm::poll(self.interval) {
-            Ok(true) => xterm::read()
-                .map(|x| Some(Event::from(x)))
-                .map_err(|_| ListenerError::PollFailed),
-            Ok(false) => Ok(None),
-            Err(_) => Err(ListenerError::PollFailed),
+        self.read_event()
+    }
+}
+
+impl<U> CrosstermInputListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    /// ### read_event
+    ///
+    /// Read the next event, as `Poll::poll()`, without requiring the user event to be `Send`
+    pub(crate) fn read_event(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.enable_capture()?;
+        let ev = match (self.pending.take(), self.scroll_window) {
+            (Some(ev), _) => Some(ev),
+            (None, scroll_window) => match (self.read(self.interval)?, scroll_window) {
+                (Some(Event::Mouse(ev)), Some(window)) if ev.is_scroll() => {
+                    Some(Event::Mouse(self.read_scroll(ev, window)?))
+                }
+                (ev, _) => ev,
+            },
+        };
+        Ok(self.map(ev))
+    }
+}
+
+impl<U> Drop for CrosstermInputListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    fn drop(&mut self) {
+        if self.captured && self.capture.map(|x| x.contains(CaptureFlags::MOUSE)) == Some(true) {
+            let _ = execute!(stdout(), DisableMouseCapture);
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_report_mouse_events_only_if_captured() {
+        let listener = CrosstermInputListener::<MockEvent>::new(Duration::from_millis(10));
+        assert_eq!(listener.mouse_enabled(), true);
+        let listener = listener.capture(CaptureFlags::MOUSE);
+        assert_eq!(listener.mouse_enabled(), true);
+        let listener = CrosstermInputListener::<MockEvent>::new(Duration::from_millis(10))
+            .capture(CaptureFlags::NONE);
+        assert_eq!(listener.mouse_enabled(), false);
+        assert_eq!(listener.captured, false);
+    }
+
+    #[test]
+    fn should_apply_event_mapper() {
+        use crate::event::{Key, KeyEvent};
+
+        let mut listener = CrosstermInputListener::<MockEvent>::new(Duration::from_millis(10));
+        assert_eq!(listener.map(Some(Event::Tick)), Some(Event::Tick));
+        let mut listener = listener.event_mapper(|ev| match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('q'),
+                ..
+            }) => Some(Event::Keyboard(KeyEvent::from(Key::Esc))),
+            Event::WindowResize(_, _) => None,
+            ev => Some(ev),
+        });
+        assert_eq!(
+            listener.map(Some(Event::Keyboard(KeyEvent::from(Key::Char('q'))))),
+            Some(Event::Keyboard(KeyEvent::from(Key::Esc)))
+        );
+        assert_eq!(listener.map(Some(Event::WindowResize(80, 24))), None);
+        assert_eq!(listener.map(Some(Event::Tick)), Some(Event::Tick));
+        assert_eq!(listener.map(None), None);
+    }
+}
@@ -27,14 +27,19 @@
  */
 extern crate crossterm;
 
+mod capabilities;
 mod event;
 mod listener;
 mod terminal;
 
 // -- export
-pub use listener::CrosstermInputListener;
+pub use capabilities::{capabilities, PasteMode, TerminalCapabilities};
+pub use listener::{CaptureFlags, CrosstermInputListener};
 
-use super::{Event, Key, KeyEvent, KeyModifiers};
+use super::{
+    coalesce_scroll, ClickCounter, Event, EventMapFn, Key, KeyEvent, KeyModifiers, MouseButton,
+    MouseEvent, MouseEventKind,
+};
 use crate::tui::{backend::CrosstermBackend, Frame as TuiFrame, Terminal as TuiTerminal};
 use std::io::Stdout;
 
@@ -28,6 +28,7 @@
 use crate::terminal::{TerminalBridge, TerminalError, TerminalResult};
 use crate::tui::backend::CrosstermBackend;
 use crate::Terminal;
+use crossterm::cursor::position;
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 #[cfg(target_family = "unix")]
 use crossterm::{
@@ -79,6 +80,10 @@ impl TerminalBridge {
             .map_err(|_| TerminalError::CannotClear)
     }
 
+    pub(crate) fn adapt_cursor_position(&mut self) -> TerminalResult<(u16, u16)> {
+        position().map_err(|_| TerminalError::CannotGetCursorPosition)
+    }
+
     pub(crate) fn adapt_enable_raw_mode(&mut self) -> TerminalResult<()> {
         enable_raw_mode().map_err(|_| TerminalError::CannotToggleRawMode)
     }
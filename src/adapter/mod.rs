@@ -25,7 +25,33 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::core::event::{Event, Key, KeyEvent, KeyModifiers};
+use crate::core::event::{
+    Event, Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+mod mouse;
+#[cfg(feature = "with-crossterm")]
+pub(crate) use mouse::coalesce_scroll;
+pub(crate) use mouse::ClickCounter;
+
+/// Function applied by the input listeners to the events they read (see `event_mapper()`)
+pub(crate) type EventMapFn<U> = Box<dyn FnMut(Event<U>) -> Option<Event<U>> + Send>;
+
+/// ### terminal_size
+///
+/// Query the current size of the terminal as (columns, rows) with the primary backend; `None` if it can't be read
+#[cfg(feature = "with-crossterm")]
+pub(crate) fn terminal_size() -> Option<(u16, u16)> {
+    ::crossterm::terminal::size().ok()
+}
+
+/// ### terminal_size
+///
+/// Query the current size of the terminal as (columns, rows) with the primary backend; `None` if it can't be read
+#[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+pub(crate) fn terminal_size() -> Option<(u16, u16)> {
+    ::termion::terminal_size().ok()
+}
 
 // -- crossterm
 #[cfg(feature = "with-crossterm")]
@@ -36,9 +62,11 @@ pub use self::crossterm::CrosstermInputListener as InputEventListener;
 pub use self::crossterm::{Frame, Terminal};
 
 // -- termion
+// If both backends are enabled, crossterm is the primary backend (`Frame`, `Terminal`, `InputEventListener`),
+// while the termion event conversions and input listener are still available from `adapter::termion`
 #[cfg(feature = "with-termion")]
 pub mod termion;
-#[cfg(feature = "with-termion")]
+#[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
 pub use self::termion::TermionInputListener as InputEventListener;
-#[cfg(feature = "with-termion")]
+#[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
 pub use self::termion::{Frame, Terminal};
@@ -0,0 +1,235 @@
+//! ## Mouse
+//!
+//! This module exposes the mouse helpers shared by the backend adapters
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{MouseEvent, MouseEventKind};
+
+use std::time::{Duration, Instant};
+
+/// ## ClickCounter
+///
+/// Keeps track of the last mouse button press, in order to detect double and triple clicks.
+/// Two consecutive `Down` events of the same button are considered part of the same sequence if they
+/// occur on the same cell within `threshold`.
+pub(crate) struct ClickCounter {
+    threshold: Duration,
+    last_click: Option<(MouseEvent, Instant)>,
+}
+
+impl ClickCounter {
+    /// Max amount of clicks reported in a sequence; after that the counter starts over from 1
+    const MAX_CLICKS: u8 = 3;
+
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last_click: None,
+        }
+    }
+
+    /// ### set_threshold
+    ///
+    /// Set the max time between two clicks to consider them part of the same sequence
+    pub fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = threshold;
+    }
+
+    /// ### count
+    ///
+    /// Set the `click_count` for the provided mouse event.
+    /// Only `Down` events are counted, any other event is returned untouched.
+    pub fn count(&mut self, ev: MouseEvent) -> MouseEvent {
+        self.count_at(ev, Instant::now())
+    }
+
+    /// ### count_at
+    ///
+    /// Same as `count`, but using `now` as the time of the event
+    fn count_at(&mut self, mut ev: MouseEvent, now: Instant) -> MouseEvent {
+        if !matches!(ev.kind, MouseEventKind::Down(_)) {
+            return ev;
+        }
+        ev.click_count = match self.last_click {
+            Some((last, at))
+                if last.kind == ev.kind
+                    && last.column == ev.column
+                    && last.row == ev.row
+                    && now.duration_since(at) <= self.threshold
+                    && last.click_count < Self::MAX_CLICKS =>
+            {
+                last.click_count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some((ev, now));
+        ev
+    }
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(300))
+    }
+}
+
+/// ### coalesce_scroll
+///
+/// Merge `ev` into `acc` if both are scroll events in the same direction and with the same modifiers.
+/// The position of `acc` is updated to the latest one and the deltas are summed.
+/// Returns whether the event has been merged.
+#[cfg(feature = "with-crossterm")]
+pub(crate) fn coalesce_scroll(acc: &mut MouseEvent, ev: &MouseEvent) -> bool {
+    if !acc.is_scroll() || acc.kind != ev.kind || acc.modifiers != ev.modifiers {
+        return false;
+    }
+    acc.delta = acc.delta.saturating_add(ev.delta);
+    acc.column = ev.column;
+    acc.row = ev.row;
+    true
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::event::{KeyModifiers, MouseButton};
+
+    use pretty_assertions::assert_eq;
+
+    fn left_click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            KeyModifiers::NONE,
+        )
+    }
+
+    #[cfg(feature = "with-crossterm")]
+    fn scroll(kind: MouseEventKind, modifiers: KeyModifiers) -> MouseEvent {
+        MouseEvent::new(kind, 1, 1, modifiers)
+    }
+
+    #[test]
+    #[cfg(feature = "with-crossterm")]
+    fn should_coalesce_scroll_events() {
+        let mut acc = scroll(MouseEventKind::ScrollDown, KeyModifiers::NONE);
+        assert!(coalesce_scroll(
+            &mut acc,
+            &scroll(MouseEventKind::ScrollDown, KeyModifiers::NONE)
+        ));
+        assert!(coalesce_scroll(
+            &mut acc,
+            &scroll(MouseEventKind::ScrollDown, KeyModifiers::NONE)
+        ));
+        assert_eq!(acc.delta, 3);
+        let mut acc = scroll(MouseEventKind::ScrollUp, KeyModifiers::NONE);
+        assert!(coalesce_scroll(
+            &mut acc,
+            &scroll(MouseEventKind::ScrollUp, KeyModifiers::NONE)
+        ));
+        assert_eq!(acc.delta, -2);
+    }
+
+    #[test]
+    #[cfg(feature = "with-crossterm")]
+    fn should_not_coalesce_different_events() {
+        let mut acc = scroll(MouseEventKind::ScrollDown, KeyModifiers::NONE);
+        // Different direction
+        assert_eq!(
+            coalesce_scroll(
+                &mut acc,
+                &scroll(MouseEventKind::ScrollUp, KeyModifiers::NONE)
+            ),
+            false
+        );
+        // Different modifiers
+        assert_eq!(
+            coalesce_scroll(
+                &mut acc,
+                &scroll(MouseEventKind::ScrollDown, KeyModifiers::CONTROL)
+            ),
+            false
+        );
+        // Not a scroll
+        assert_eq!(coalesce_scroll(&mut acc, &left_click(1, 1)), false);
+        let mut acc = left_click(1, 1);
+        assert_eq!(coalesce_scroll(&mut acc, &left_click(1, 1)), false);
+        assert_eq!(acc.delta, 0);
+    }
+
+    #[test]
+    fn should_count_double_and_triple_clicks() {
+        let mut counter = ClickCounter::default();
+        let t0 = Instant::now();
+        assert_eq!(counter.count_at(left_click(4, 2), t0).click_count, 1);
+        let t1 = t0 + Duration::from_millis(100);
+        assert_eq!(counter.count_at(left_click(4, 2), t1).click_count, 2);
+        let t2 = t1 + Duration::from_millis(100);
+        assert_eq!(counter.count_at(left_click(4, 2), t2).click_count, 3);
+        // Starts over
+        let t3 = t2 + Duration::from_millis(100);
+        assert_eq!(counter.count_at(left_click(4, 2), t3).click_count, 1);
+    }
+
+    #[test]
+    fn should_reset_count_on_slow_click() {
+        let mut counter = ClickCounter::default();
+        let t0 = Instant::now();
+        assert_eq!(counter.count_at(left_click(4, 2), t0).click_count, 1);
+        let t1 = t0 + Duration::from_millis(500);
+        assert_eq!(counter.count_at(left_click(4, 2), t1).click_count, 1);
+        // Custom threshold
+        counter.set_threshold(Duration::from_secs(1));
+        let t2 = t1 + Duration::from_millis(500);
+        assert_eq!(counter.count_at(left_click(4, 2), t2).click_count, 2);
+    }
+
+    #[test]
+    fn should_reset_count_on_different_cell_or_button() {
+        let mut counter = ClickCounter::default();
+        let t0 = Instant::now();
+        assert_eq!(counter.count_at(left_click(4, 2), t0).click_count, 1);
+        let t1 = t0 + Duration::from_millis(50);
+        assert_eq!(counter.count_at(left_click(5, 2), t1).click_count, 1);
+        let t2 = t1 + Duration::from_millis(50);
+        let right = MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Right),
+            5,
+            2,
+            KeyModifiers::NONE,
+        );
+        assert_eq!(counter.count_at(right, t2).click_count, 1);
+        // Other events are not counted
+        let up = MouseEvent::new(
+            MouseEventKind::Up(MouseButton::Right),
+            5,
+            2,
+            KeyModifiers::NONE,
+        );
+        assert_eq!(counter.count_at(up, t2).click_count, 0);
+    }
+}
@@ -25,17 +25,20 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{Event, Key, KeyEvent, KeyModifiers};
+use super::{Event, Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
-use termion::event::{Event as TonEvent, Key as TonKey};
+use termion::event::{
+    Event as TonEvent, Key as TonKey, MouseButton as TonMouseButton, MouseEvent as TonMouseEvent,
+};
 
 impl<U> From<TonEvent> for Event<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
     fn from(e: TonEvent) -> Self {
         match e {
             TonEvent::Key(key) => Self::Keyboard(key.into()),
+            TonEvent::Mouse(mouse) => Self::Mouse(mouse.into()),
             _ => Self::None,
         }
     }
@@ -71,9 +74,39 @@ impl From<TonKey> for KeyEvent {
             TonKey::F(f) => Key::Function(f),
             TonKey::Null => Key::Null,
             TonKey::Esc => Key::Esc,
-            TonKey::__IsNotComplete => Key::Null,
+            TonKey::__IsNotComplete => Key::Unsupported,
         };
-        Self { code, modifiers }
+        Self::new(code, modifiers)
+    }
+}
+
+/// Termion doesn't report which button is being released or dragged, so `Left` is assumed.
+/// Termion coordinates are 1-based, so they're converted to 0-based.
+impl From<TonMouseEvent> for MouseEvent {
+    fn from(e: TonMouseEvent) -> Self {
+        let (kind, x, y) = match e {
+            TonMouseEvent::Press(TonMouseButton::WheelUp, x, y) => (MouseEventKind::ScrollUp, x, y),
+            TonMouseEvent::Press(TonMouseButton::WheelDown, x, y) => {
+                (MouseEventKind::ScrollDown, x, y)
+            }
+            TonMouseEvent::Press(TonMouseButton::Left, x, y) => {
+                (MouseEventKind::Down(MouseButton::Left), x, y)
+            }
+            TonMouseEvent::Press(TonMouseButton::Right, x, y) => {
+                (MouseEventKind::Down(MouseButton::Right), x, y)
+            }
+            TonMouseEvent::Press(TonMouseButton::Middle, x, y) => {
+                (MouseEventKind::Down(MouseButton::Middle), x, y)
+            }
+            TonMouseEvent::Release(x, y) => (MouseEventKind::Up(MouseButton::Left), x, y),
+            TonMouseEvent::Hold(x, y) => (MouseEventKind::Drag(MouseButton::Left), x, y),
+        };
+        Self::new(
+            kind,
+            x.saturating_sub(1),
+            y.saturating_sub(1),
+            KeyModifiers::NONE,
+        )
     }
 }
 
@@ -85,7 +118,26 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
-    use termion::event::MouseEvent;
+    #[test]
+    #[cfg(feature = "with-crossterm")]
+    fn adapt_termion_and_crossterm_events_to_the_same_event() {
+        use crossterm::event::{
+            KeyCode as XtermKeyCode, KeyEvent as XtermKeyEvent, KeyModifiers as XtermKeyModifiers,
+        };
+        assert_eq!(
+            KeyEvent::from(TonKey::Ctrl('b')),
+            KeyEvent::from(XtermKeyEvent::new(
+                XtermKeyCode::Char('b'),
+                XtermKeyModifiers::CONTROL
+            ))
+        );
+        assert_eq!(
+            Event::<MockEvent>::from(TonEvent::Key(TonKey::Esc)),
+            Event::<MockEvent>::from(crossterm::event::Event::Key(XtermKeyEvent::from(
+                XtermKeyCode::Esc
+            )))
+        );
+    }
 
     #[test]
     fn adapt_termion_key_event() {
@@ -103,24 +155,15 @@ mod test {
         );
         assert_eq!(
             KeyEvent::from(TonKey::Ctrl('b')),
-            KeyEvent::from(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::CONTROL,
-            })
+            KeyEvent::new(Key::Char('b'), KeyModifiers::CONTROL)
         );
         assert_eq!(
             KeyEvent::from(TonKey::Alt('b')),
-            KeyEvent::from(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::ALT
-            })
+            KeyEvent::new(Key::Char('b'), KeyModifiers::ALT)
         );
         assert_eq!(
             KeyEvent::from(TonKey::Char('B')),
-            KeyEvent::from(KeyEvent {
-                code: Key::Char('b'),
-                modifiers: KeyModifiers::SHIFT,
-            })
+            KeyEvent::new(Key::Char('b'), KeyModifiers::SHIFT)
         );
         assert_eq!(KeyEvent::from(TonKey::Delete), KeyEvent::from(Key::Delete));
         assert_eq!(KeyEvent::from(TonKey::Down), KeyEvent::from(Key::Down));
@@ -148,7 +191,49 @@ mod test {
         assert_eq!(KeyEvent::from(TonKey::Up), KeyEvent::from(Key::Up));
         assert_eq!(
             KeyEvent::from(TonKey::__IsNotComplete),
-            KeyEvent::from(Key::Null)
+            KeyEvent::from(Key::Unsupported)
+        );
+    }
+
+    #[test]
+    fn adapt_termion_mouse_event() {
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Press(TonMouseButton::Left, 5, 3)),
+            MouseEvent::new(
+                MouseEventKind::Down(MouseButton::Left),
+                4,
+                2,
+                KeyModifiers::NONE
+            )
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Press(TonMouseButton::Right, 1, 1)).kind,
+            MouseEventKind::Down(MouseButton::Right)
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Press(TonMouseButton::Middle, 1, 1)).kind,
+            MouseEventKind::Down(MouseButton::Middle)
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Press(TonMouseButton::WheelUp, 1, 1)).kind,
+            MouseEventKind::ScrollUp
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Press(TonMouseButton::WheelDown, 1, 1)).kind,
+            MouseEventKind::ScrollDown
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Release(1, 1)).kind,
+            MouseEventKind::Up(MouseButton::Left)
+        );
+        assert_eq!(
+            MouseEvent::from(TonMouseEvent::Hold(0, 0)),
+            MouseEvent::new(
+                MouseEventKind::Drag(MouseButton::Left),
+                0,
+                0,
+                KeyModifiers::NONE
+            )
         );
     }
 
@@ -160,7 +245,16 @@ mod test {
             Event::Keyboard(KeyEvent::from(Key::Backspace))
         );
         assert_eq!(
-            AppEvent::from(TonEvent::Mouse(MouseEvent::Hold(0, 0))),
+            AppEvent::from(TonEvent::Mouse(TonMouseEvent::Hold(1, 1))),
+            Event::Mouse(MouseEvent::new(
+                MouseEventKind::Drag(MouseButton::Left),
+                0,
+                0,
+                KeyModifiers::NONE
+            ))
+        );
+        assert_eq!(
+            AppEvent::from(TonEvent::Unsupported(vec![0x1b])),
             Event::None
         );
     }
@@ -25,33 +25,116 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::Event;
+use super::{ClickCounter, Event, EventMapFn};
 
 use crate::listener::{ListenerError, ListenerResult, Poll};
 use std::io::stdin;
 use std::marker::PhantomData;
 use std::time::Duration;
+use termion::event::Event as TonEvent;
 use termion::input::TermRead;
+use termion::terminal_size;
 
 /// ## TermionInputListener
 ///
 /// The input listener for termion.
 /// If termion is enabled, this will already be exported as `InputEventListener` in the `adapter` module
-/// or you can use it directly in the event listener, calling `default_input_listener()` in the `EventListenerCfg`
+/// or you can use it directly in the event listener, calling `default_input_listener()` in the `EventListenerCfg`.
+/// The user event doesn't need to be `Send`, so the listener can be used by the `LocalEventListener` too
+/// (see `LocalEventListener::input_listener()`).
+///
+/// Termion doesn't report resize events, so the listener checks the terminal size on each poll and reports
+/// `Event::WindowResize(columns, rows)` when it changes. Since termion reads from stdin in blocking mode,
+/// a resize is reported only on the first poll after it happened.
 pub struct TermionInputListener<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
-    ghost: PhantomData<U>,
+    ghost: PhantomData<fn() -> U>,
+    clicks: ClickCounter,
+    /// Last known terminal size
+    size: Option<(u16, u16)>,
+    /// Whether unsupported events are reported as `Event::Raw`
+    passthrough_raw: bool,
+    /// If set, is applied to the events read before reporting them
+    mapper: Option<EventMapFn<U>>,
 }
 
 impl<U> TermionInputListener<U>
 where
-    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    U: Eq + PartialEq + Clone + PartialOrd,
 {
     pub fn new(_interval: Duration) -> Self {
         Self {
-            ghost: PhantomData::default(),
+            ghost: PhantomData,
+            clicks: ClickCounter::default(),
+            size: None,
+            passthrough_raw: false,
+            mapper: None,
+        }
+    }
+
+    /// ### passthrough_raw
+    ///
+    /// If `passthrough` is true, the sequences which termion can't map to an event are reported as `Event::Raw`
+    /// with the bytes read, instead of being reported as `Event::None`. Default: false
+    pub fn passthrough_raw(mut self, passthrough: bool) -> Self {
+        self.passthrough_raw = passthrough;
+        self
+    }
+
+    /// ### event_mapper
+    ///
+    /// Set a function applied to each event after it has been converted from the termion event:
+    /// the event returned is reported instead, while if `None` is returned, the event is dropped
+    pub fn event_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: FnMut(Event<U>) -> Option<Event<U>> + Send + 'static,
+    {
+        self.mapper = Some(Box::new(mapper));
+        self
+    }
+
+    /// ### map
+    ///
+    /// Apply the event mapper, if any, to the event
+    fn map(&mut self, ev: Event<U>) -> Option<Event<U>> {
+        match self.mapper.as_mut() {
+            Some(mapper) => mapper(ev),
+            None => Some(ev),
+        }
+    }
+
+    /// ### map_event
+    ///
+    /// Convert the termion event into an `Event`
+    fn map_event(&mut self, ev: TonEvent) -> Event<U> {
+        match ev {
+            TonEvent::Unsupported(bytes) if self.passthrough_raw => Event::Raw(bytes),
+            ev => match Event::from(ev) {
+                Event::Mouse(ev) => Event::Mouse(self.clicks.count(ev)),
+                ev => ev,
+            },
+        }
+    }
+
+    /// ### multi_click_threshold
+    ///
+    /// Set the max time between two clicks on the same cell to report them as a double (or triple) click.
+    /// Default: 300ms
+    pub fn multi_click_threshold(mut self, threshold: Duration) -> Self {
+        self.clicks.set_threshold(threshold);
+        self
+    }
+
+    /// ### resized
+    ///
+    /// Update the last known terminal size and returns the new size if it has changed.
+    /// The first size observed is not reported, since the terminal has not been resized.
+    fn resized(&mut self, size: (u16, u16)) -> Option<(u16, u16)> {
+        match self.size.replace(size) {
+            Some(prev) if prev != size => Some(size),
+            _ => None,
         }
     }
 }
@@ -61,10 +144,86 @@ where
     U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
 {
     fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.read_event()
+    }
+}
+
+impl<U> TermionInputListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    /// ### read_event
+    ///
+    /// Read the next event, as `Poll::poll()`, without requiring the user event to be `Send`
+    pub(crate) fn read_event(&mut self) -> ListenerResult<Option<Event<U>>> {
+        if let Some((columns, rows)) = terminal_size().ok().and_then(|x| self.resized(x)) {
+            return Ok(self.map(Event::WindowResize(columns, rows)));
+        }
         match stdin().events().next() {
-            Some(Ok(ev)) => Ok(Some(Event::from(ev))),
-            Some(Err(_)) => Err(ListenerError::PollFailed),
+            Some(Ok(ev)) => {
+                let ev = self.map_event(ev);
+                Ok(self.map(ev))
+            }
+            Some(Err(err)) => Err(ListenerError::PollFailed(err.to_string())),
             None => Ok(None),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::event::{Key, KeyEvent};
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_report_resize() {
+        let mut listener = TermionInputListener::<MockEvent>::new(Duration::from_millis(10));
+        assert_eq!(listener.resized((80, 24)), None);
+        assert_eq!(listener.resized((80, 24)), None);
+        assert_eq!(listener.resized((120, 40)), Some((120, 40)));
+        assert_eq!(listener.resized((120, 40)), None);
+    }
+
+    #[test]
+    fn should_passthrough_raw_events() {
+        let mut listener = TermionInputListener::<MockEvent>::new(Duration::from_millis(10));
+        assert_eq!(
+            listener.map_event(TonEvent::Unsupported(vec![0x1b, b']'])),
+            Event::None
+        );
+        let mut listener = listener.passthrough_raw(true);
+        assert_eq!(
+            listener.map_event(TonEvent::Unsupported(vec![0x1b, b']'])),
+            Event::Raw(vec![0x1b, b']'])
+        );
+        assert_eq!(
+            listener.map_event(TonEvent::Key(termion::event::Key::Esc)),
+            Event::Keyboard(KeyEvent::from(Key::Esc))
+        );
+    }
+
+    #[test]
+    fn should_apply_event_mapper() {
+        let mut listener = TermionInputListener::<MockEvent>::new(Duration::from_millis(10));
+        assert_eq!(listener.map(Event::Tick), Some(Event::Tick));
+        let mut listener = listener.event_mapper(|ev| match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('q'),
+                ..
+            }) => Some(Event::Keyboard(KeyEvent::from(Key::Esc))),
+            Event::WindowResize(_, _) => None,
+            ev => Some(ev),
+        });
+        let ev = listener.map_event(TonEvent::Key(termion::event::Key::Char('q')));
+        assert_eq!(
+            listener.map(ev),
+            Some(Event::Keyboard(KeyEvent::from(Key::Esc)))
+        );
+        assert_eq!(listener.map(Event::WindowResize(80, 24)), None);
+        assert_eq!(listener.map(Event::Tick), Some(Event::Tick));
+    }
+}
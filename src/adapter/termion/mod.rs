@@ -29,12 +29,16 @@ extern crate termion;
 
 mod event;
 mod listener;
+#[cfg(not(feature = "with-crossterm"))]
 mod terminal;
 
 // -- export
 pub use listener::TermionInputListener;
 
-use super::{Event, Key, KeyEvent, KeyModifiers};
+use super::{
+    ClickCounter, Event, EventMapFn, Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use crate::tui::{backend::TermionBackend, Frame as TuiFrame, Terminal as TuiTerminal};
 use std::io::Stdout;
 use termion::{input::MouseTerminal, raw::RawTerminal, screen::AlternateScreen};
@@ -31,7 +31,7 @@ use crate::Terminal;
 use std::io::stdout;
 #[cfg(target_family = "unix")]
 use termion::screen::AlternateScreen;
-use termion::{input::MouseTerminal, raw::IntoRawMode};
+use termion::{cursor::DetectCursorPos, input::MouseTerminal, raw::IntoRawMode};
 
 impl TerminalBridge {
     pub(crate) fn adapt_new_terminal() -> TerminalResult<Terminal> {
@@ -57,6 +57,15 @@ impl TerminalBridge {
             .map_err(|_| TerminalError::CannotClear)
     }
 
+    pub(crate) fn adapt_cursor_position(&mut self) -> TerminalResult<(u16, u16)> {
+        // Termion reports a 1-based position
+        self.raw_mut()
+            .backend_mut()
+            .cursor_pos()
+            .map(|(col, row)| (col.saturating_sub(1), row.saturating_sub(1)))
+            .map_err(|_| TerminalError::CannotGetCursorPosition)
+    }
+
     pub(crate) fn adapt_enable_raw_mode(&mut self) -> TerminalResult<()> {
         Err(TerminalError::Unsupported)
     }
@@ -0,0 +1,205 @@
+//! ## Action
+//!
+//! This module exposes the `ActionMap`, which resolves key events into semantic actions, so that key bindings
+//! can be configured in a single place instead of matching raw key events in each component.
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::event::{Key, KeyEvent, KeyEventKind};
+
+use std::collections::HashMap;
+
+/// ## Action
+///
+/// A semantic action, resolved from a key event by the `ActionMap`
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PageUp,
+    PageDown,
+    /// Go to the first item
+    Home,
+    /// Go to the last item
+    End,
+    Submit,
+    Cancel,
+    /// Move to the next element (e.g. focus the next component)
+    Next,
+    /// Move to the previous element (e.g. focus the previous component)
+    Previous,
+    /// A user defined action
+    Custom(&'static str),
+}
+
+/// ## ActionMap
+///
+/// Maps key events to actions.
+/// The default map binds:
+///
+/// - arrows to `MoveUp`, `MoveDown`, `MoveLeft` and `MoveRight`
+/// - `PageUp`, `PageDown`, `Home` and `End` to the actions with the same name
+/// - `Enter` to `Submit` and `Esc` to `Cancel`
+/// - `Tab` to `Next` and `BackTab` to `Previous`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionMap {
+    bindings: HashMap<KeyEvent, Action>,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        [
+            (Key::Up, Action::MoveUp),
+            (Key::Down, Action::MoveDown),
+            (Key::Left, Action::MoveLeft),
+            (Key::Right, Action::MoveRight),
+            (Key::PageUp, Action::PageUp),
+            (Key::PageDown, Action::PageDown),
+            (Key::Home, Action::Home),
+            (Key::End, Action::End),
+            (Key::Enter, Action::Submit),
+            (Key::Esc, Action::Cancel),
+            (Key::Tab, Action::Next),
+            (Key::BackTab, Action::Previous),
+        ]
+        .into_iter()
+        .fold(Self::empty(), |map, (key, action)| {
+            map.bind(KeyEvent::from(key), action)
+        })
+    }
+}
+
+impl ActionMap {
+    /// ### empty
+    ///
+    /// Create a new `ActionMap` without any binding
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// ### bind
+    ///
+    /// Bind `key` to `action`, replacing the previous binding of `key`, if any
+    pub fn bind(mut self, key: KeyEvent, action: Action) -> Self {
+        self.bindings.insert(Self::lookup_key(key), action);
+        self
+    }
+
+    /// ### unbind
+    ///
+    /// Remove the binding of `key`
+    pub fn unbind(mut self, key: KeyEvent) -> Self {
+        self.bindings.remove(&Self::lookup_key(key));
+        self
+    }
+
+    /// ### resolve
+    ///
+    /// Returns the action bound to `key`, if any.
+    /// Key events are normalized (see `KeyEvent::normalized()`) and auto-repeats resolve as key presses
+    pub fn resolve(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&Self::lookup_key(*key)).copied()
+    }
+
+    /// ### lookup_key
+    ///
+    /// Returns the form of `key` used as key in the bindings
+    fn lookup_key(key: KeyEvent) -> KeyEvent {
+        KeyEvent {
+            kind: KeyEventKind::Press,
+            ..key.normalized()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::event::KeyModifiers;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_resolve_default_bindings() {
+        let map = ActionMap::default();
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Up)), Some(Action::MoveUp));
+        assert_eq!(
+            map.resolve(&KeyEvent::from(Key::Enter)),
+            Some(Action::Submit)
+        );
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Esc)), Some(Action::Cancel));
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Tab)), Some(Action::Next));
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Char('a'))), None);
+        // Modifiers must match
+        assert_eq!(
+            map.resolve(&KeyEvent::new(Key::Up, KeyModifiers::CONTROL)),
+            None
+        );
+        // Repeats resolve as presses
+        let mut repeat = KeyEvent::from(Key::Down);
+        repeat.kind = KeyEventKind::Repeat;
+        assert_eq!(map.resolve(&repeat), Some(Action::MoveDown));
+        assert_eq!(ActionMap::empty().resolve(&KeyEvent::from(Key::Up)), None);
+    }
+
+    #[test]
+    fn should_override_bindings() {
+        let map = ActionMap::default()
+            .bind(KeyEvent::from(Key::Char('k')), Action::MoveUp)
+            .bind(KeyEvent::from(Key::Enter), Action::Custom("open"))
+            .bind(
+                KeyEvent::new(Key::Char('q'), KeyModifiers::CONTROL),
+                Action::Cancel,
+            )
+            .unbind(KeyEvent::from(Key::Tab));
+        assert_eq!(
+            map.resolve(&KeyEvent::from(Key::Char('k'))),
+            Some(Action::MoveUp)
+        );
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Up)), Some(Action::MoveUp));
+        assert_eq!(
+            map.resolve(&KeyEvent::from(Key::Enter)),
+            Some(Action::Custom("open"))
+        );
+        assert_eq!(
+            map.resolve(&KeyEvent::new(Key::Char('q'), KeyModifiers::CONTROL)),
+            Some(Action::Cancel)
+        );
+        assert_eq!(map.resolve(&KeyEvent::from(Key::Tab)), None);
+        // Bindings are normalized
+        let map = ActionMap::empty().bind(
+            KeyEvent::new(Key::Char('g'), KeyModifiers::SHIFT),
+            Action::End,
+        );
+        assert_eq!(
+            map.resolve(&KeyEvent::from(Key::Char('G'))),
+            Some(Action::End)
+        );
+    }
+}
@@ -26,7 +26,10 @@
  * SOFTWARE.
  */
 use super::{Subscription, View, WrappedComponent};
-use crate::listener::{EventListener, EventListenerCfg, ListenerError};
+use crate::listener::{
+    EventListener, EventListenerCfg, EventReceiver, Events, GrabResult, IterationTiming,
+    ListenerError, PauseGuard, Port, PortId, PortKind, PortStatus, ScheduleHandle,
+};
 use crate::tui::layout::Rect;
 use crate::{AttrValue, Attribute, Event, Frame, State, Sub, SubEventClause, ViewError};
 
@@ -57,6 +60,8 @@ where
     subs: Vec<Subscription<ComponentId, UserEvent>>,
     /// If true, subs won't be processed. (Default: False)
     sub_lock: bool,
+    /// Amount of ticks received from the listener
+    ticks: u64,
     view: View<ComponentId, Msg, UserEvent>,
 }
 
@@ -75,10 +80,20 @@ where
             listener: listener_cfg.start(),
             subs: Vec::new(),
             sub_lock: false,
+            ticks: 0,
             view: View::default(),
         }
     }
 
+    /// ### try_init
+    ///
+    /// Initialize a new `Application`, as `init()`, but validating the event listener configuration first
+    /// (see `EventListenerCfg::validate()`): if it's not valid, the error is returned and the listener is not started.
+    pub fn try_init(listener_cfg: EventListenerCfg<UserEvent>) -> ApplicationResult<Self> {
+        listener_cfg.validate()?;
+        Ok(Self::init(listener_cfg))
+    }
+
     /// ### restart_listener
     ///
     /// Restart listener in case the previous listener has died or if you want to start a new one with a new configuration.
@@ -93,6 +108,226 @@ where
         Ok(())
     }
 
+    /// ### try_restart_listener
+    ///
+    /// Restart listener, as `restart_listener()`, but validating the new configuration first
+    /// (see `EventListenerCfg::validate()`): if it's not valid, the error is returned and the current listener is kept.
+    pub fn try_restart_listener(
+        &mut self,
+        listener_cfg: EventListenerCfg<UserEvent>,
+    ) -> ApplicationResult<()> {
+        listener_cfg.validate()?;
+        self.restart_listener(listener_cfg)
+    }
+
+    /// ### listener_config_snapshot
+    ///
+    /// Returns a configuration to start a new listener equivalent to the current one (ports intervals, tick interval,
+    /// options and pause state, including runtime changes). Pass it to `restart_listener()` to reinitialize the listener,
+    /// e.g. after a configuration change.
+    ///
+    /// > Only ports added with `EventListenerCfg::port_factory()` (or the default input listener) can be rebuilt;
+    /// > ports added with `port()` are skipped, since stateful ports can't be recreated without a factory.
+    pub fn listener_config_snapshot(&self) -> EventListenerCfg<UserEvent> {
+        self.listener.config_snapshot()
+    }
+
+    /// ### stop_listener
+    ///
+    /// Stop the event listener, waiting at most `timeout` for it to terminate.
+    /// Use this on shutdown to prevent a misbehaving port from blocking your application forever;
+    /// if the listener doesn't stop in time, its thread is detached and `ListenerError::CouldNotStop` is returned.
+    /// Once stopped, no more events will be received, until `restart_listener()` is called.
+    pub fn stop_listener(&mut self, timeout: Duration) -> ApplicationResult<()> {
+        self.listener
+            .stop_timeout(timeout)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### set_port_interval
+    ///
+    /// Set a new poll interval for the port identified by `id` (its index in registration order).
+    /// This can be used to implement adaptive polling, e.g. backing off a network port when idle.
+    pub fn set_port_interval(&mut self, id: PortId, interval: Duration) -> ApplicationResult<()> {
+        self.listener
+            .set_port_interval(id, interval)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### set_ports
+    ///
+    /// Replace all the ports of the event listener with `ports` at once (e.g. when switching input modes).
+    /// See `EventListener::set_ports()`
+    pub fn set_ports(&mut self, ports: Vec<Port<UserEvent>>) -> ApplicationResult<()> {
+        self.listener
+            .set_ports(ports)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### event_receiver
+    ///
+    /// Returns an additional receiver of the events produced by the event listener (e.g. for a macro recorder),
+    /// which gets every event independently from the application. See `EventReceiver`.
+    pub fn event_receiver(&self) -> EventReceiver<UserEvent> {
+        self.listener.subscribe()
+    }
+
+    /// ### pause_all_ports_except
+    ///
+    /// Pause all the ports of the event listener except the one identified by `id`, which keeps being polled
+    /// (e.g. to keep a cancel key working while a modal is shown). If `keep_ticks` is false, ticks are paused too.
+    /// Call `unpause_all_ports()` to resume the paused ports.
+    pub fn pause_all_ports_except(
+        &mut self,
+        id: PortId,
+        keep_ticks: bool,
+    ) -> ApplicationResult<()> {
+        self.listener
+            .pause_all_except(id, keep_ticks)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### unpause_all_ports
+    ///
+    /// Resume the ports paused with `pause_all_ports_except()`
+    pub fn unpause_all_ports(&mut self) -> ApplicationResult<()> {
+        self.listener.unpause_all().map_err(ApplicationError::from)
+    }
+
+    /// ### pause_ports_of_kind
+    ///
+    /// Pause all the ports of the event listener of `kind` (e.g. `PortKind::Background`); ticks are not affected.
+    /// Call `unpause_ports_of_kind()` to resume them.
+    pub fn pause_ports_of_kind(&mut self, kind: PortKind) -> ApplicationResult<()> {
+        self.listener
+            .pause_kind(kind)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### unpause_ports_of_kind
+    ///
+    /// Resume the ports of `kind` paused with `pause_ports_of_kind()`
+    pub fn unpause_ports_of_kind(&mut self, kind: PortKind) -> ApplicationResult<()> {
+        self.listener
+            .unpause_kind(kind)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### wait_listener_ready
+    ///
+    /// Blocks until the event listener is up and running, waiting at most for `timeout`.
+    /// Returns whether the listener is ready.
+    pub fn wait_listener_ready(&self, timeout: Duration) -> bool {
+        self.listener.wait_ready(timeout)
+    }
+
+    /// ### listener_ports
+    ///
+    /// Returns the status of the ports registered in the event listener (interval, last poll, events count...).
+    /// The status is a point-in-time snapshot and may be slightly stale. Useful for debug panels.
+    pub fn listener_ports(&self) -> Vec<PortStatus> {
+        self.listener.ports()
+    }
+
+    /// ### listener_iteration_timing
+    ///
+    /// Returns how long the event listener worker spends in each iteration (see `IterationTiming`).
+    /// Useful to tune the poll intervals of the ports
+    pub fn listener_iteration_timing(&self) -> IterationTiming {
+        self.listener.iteration_timing()
+    }
+
+    /// ### listener_tick_interval
+    ///
+    /// Returns the current tick interval of the event listener; `None` if ticks are disabled
+    pub fn listener_tick_interval(&self) -> Option<Duration> {
+        self.listener.tick_interval()
+    }
+
+    /// ### listener_port_interval
+    ///
+    /// Returns the current poll interval of the port identified by `id`; `None` if there's no such port
+    pub fn listener_port_interval(&self, id: PortId) -> Option<Duration> {
+        self.listener.port_interval(id)
+    }
+
+    /// ### inject_event
+    ///
+    /// Push `event` to the event listener queue, as if it had been produced by a port (e.g. for macros or tests).
+    /// The event is processed by `tick()` after the events already queued.
+    pub fn inject_event(&self, event: Event<UserEvent>) -> ApplicationResult<()> {
+        self.listener.inject(event).map_err(ApplicationError::from)
+    }
+
+    /// ### schedule_event
+    ///
+    /// Schedule `event` to be sent once by the event listener after `delay` (e.g. to dismiss a notification after a while).
+    /// Returns the handle to cancel it with `cancel_scheduled_event()`
+    pub fn schedule_event(
+        &self,
+        delay: Duration,
+        event: Event<UserEvent>,
+    ) -> ApplicationResult<ScheduleHandle> {
+        self.listener
+            .schedule_once(delay, event)
+            .map_err(ApplicationError::from)
+    }
+
+    /// ### cancel_scheduled_event
+    ///
+    /// Cancel the event scheduled with `schedule_event()`; returns whether it was still scheduled
+    pub fn cancel_scheduled_event(&self, handle: ScheduleHandle) -> bool {
+        self.listener.cancel_scheduled(handle)
+    }
+
+    /// ### grab_input
+    ///
+    /// Intercept the events in the event listener with `handler` (e.g. for a command palette overlay),
+    /// until `release_input_grab()` is called. The handler returns `GrabResult::Consume` to drop the event,
+    /// or `GrabResult::Pass` to have it processed by `tick()` as usual.
+    /// See `EventListenerCfg::grab_ticks()` to grab ticks too.
+    ///
+    /// > The handler runs on the event listener thread: it must not block, nor call `grab_input()`
+    /// > or `release_input_grab()`, which would deadlock.
+    pub fn grab_input<F>(&self, handler: F)
+    where
+        F: FnMut(Event<UserEvent>) -> GrabResult + Send + 'static,
+    {
+        self.listener.grab(handler);
+    }
+
+    /// ### release_input_grab
+    ///
+    /// Remove the handler installed with `grab_input()`
+    pub fn release_input_grab(&self) {
+        self.listener.release_grab();
+    }
+
+    /// ### flush_events
+    ///
+    /// Discard all the events pending in the event listener (ticks included), returning the amount of discarded events.
+    /// Useful to drop the input queued up during a slow operation, e.g. when switching view.
+    pub fn flush_events(&self) -> usize {
+        self.listener.flush()
+    }
+
+    /// ### listener_poll_timeout
+    ///
+    /// Returns the timeout the event listener waits for an event, when polled
+    pub fn listener_poll_timeout(&self) -> Duration {
+        self.listener.poll_timeout()
+    }
+
+    /// ### set_tick_interval
+    ///
+    /// Change the tick interval of the event listener at runtime (e.g. when the user changes the refresh rate).
+    /// If `None`, ticks are disabled. The listener keeps running, so no event is lost.
+    pub fn set_tick_interval(&mut self, interval: Option<Duration>) -> ApplicationResult<()> {
+        self.listener
+            .set_tick_interval(interval)
+            .map_err(ApplicationError::from)
+    }
+
     /// ### lock_ports
     ///
     /// Lock ports. As long as Ports are locked, ports won't be polled.
@@ -101,6 +336,23 @@ where
         self.listener.pause().map_err(ApplicationError::from)
     }
 
+    /// ### lock_ports_guard
+    ///
+    /// Lock ports until the returned guard is dropped. This is useful to pause the event listener around
+    /// a blocking operation (e.g. running an external editor), without having to remember to call `unlock_ports()`.
+    /// Guards can be nested: ports are unlocked only once the last guard has been dropped,
+    /// and only if they weren't already locked with `lock_ports()` when the first guard was created.
+    ///
+    /// ```rust,ignore
+    /// {
+    ///     let _guard = app.lock_ports_guard();
+    ///     run_editor();
+    /// }
+    /// ```
+    pub fn lock_ports_guard(&mut self) -> PauseGuard {
+        self.listener.pause_guard()
+    }
+
     /// ### unlock_ports
     ///
     /// Unlock Ports. Once called, the event listener will resume polling Ports.
@@ -347,19 +599,45 @@ where
         }
     }
 
+    /// ### events
+    ///
+    /// Returns an iterator over the events available from the event listener, without forwarding them to
+    /// the components. This is useful to handle events on your own or to drain the pending events.
+    /// Each item is the result of a poll; the iteration ends as soon as no event is available within
+    /// the poll timeout (see `Events`).
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use tuirealm::listener::IteratorPoll;
+    /// use tuirealm::{Application, Event, EventListenerCfg, NoUserEvent};
+    ///
+    /// let mut app: Application<String, (), NoUserEvent> = Application::init(
+    ///     EventListenerCfg::default().port(
+    ///         Box::new(IteratorPoll::new(vec![Event::WindowResize(80, 24)])),
+    ///         Duration::from_millis(1),
+    ///     ),
+    /// );
+    /// app.wait_listener_ready(Duration::from_secs(1));
+    /// for ev in app.events() {
+    ///     match ev {
+    ///         Ok(Event::WindowResize(width, height)) => assert_eq!((width, height), (80, 24)),
+    ///         Ok(_) => {}
+    ///         Err(err) => panic!("listener error: {}", err),
+    ///     }
+    /// }
+    /// ```
+    pub fn events(&mut self) -> Events<'_, UserEvent> {
+        self.listener.iter()
+    }
+
     /// ### poll_times
     ///
     /// Poll event listener up to `t` times
     fn poll_times(&mut self, t: usize) -> ApplicationResult<Vec<Event<UserEvent>>> {
-        let mut evs: Vec<Event<UserEvent>> = Vec::with_capacity(t);
-        for _ in 0..t {
-            match self.poll_listener() {
-                Err(err) => return Err(err),
-                Ok(None) => break,
-                Ok(Some(ev)) => evs.push(ev),
-            }
-        }
-        Ok(evs)
+        self.events()
+            .take(t)
+            .collect::<Result<Vec<Event<UserEvent>>, ListenerError>>()
+            .map_err(ApplicationError::from)
     }
 
     /// ### poll_with_timeout
@@ -403,6 +681,9 @@ where
         let mut messages: Vec<Msg> = Vec::new();
         // NOTE: don't touch this code again and don't try to use iterators, cause it's not gonna work :)
         for ev in events.iter() {
+            if ev.is_tick() {
+                self.ticks = self.ticks.wrapping_add(1);
+            }
             for sub in self.subs.iter() {
                 // ! Active component must be different from sub !
                 if self.view.has_focus(sub.target()) {
@@ -410,6 +691,7 @@ where
                 }
                 if !sub.forward(
                     ev,
+                    self.ticks,
                     |id, q| self.view.query(id, q).ok().flatten(),
                     |id| self.view.state(id).ok(),
                     |id| self.view.mounted(id),
@@ -470,7 +752,7 @@ impl From<ViewError> for ApplicationError {
 mod test {
 
     use super::*;
-    use crate::event::{Key, KeyEvent};
+    use crate::event::{Key, KeyEvent, KeyEventKind};
     use crate::mock::{MockBarInput, MockComponentId, MockEvent, MockFooInput, MockMsg, MockPoll};
     use crate::{StateValue, SubClause};
 
@@ -493,6 +775,139 @@ mod test {
         assert!(application.restart_listener(listener_config()).is_ok());
     }
 
+    #[test]
+    fn should_restart_listener_from_snapshot() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config_with_tick(Duration::from_secs(1)));
+        let snapshot = application.listener_config_snapshot();
+        assert!(application.restart_listener(snapshot).is_ok());
+    }
+
+    #[test]
+    fn should_set_port_interval() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application
+            .set_port_interval(0, Duration::from_millis(50))
+            .is_ok());
+        assert_eq!(
+            application.listener_port_interval(0),
+            Some(Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn should_get_event_receiver() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        let receiver = application.event_receiver();
+        assert!(application.tick(PollStrategy::Once).is_ok());
+        assert_eq!(
+            receiver
+                .recv_timeout(Duration::from_millis(500))
+                .ok()
+                .unwrap(),
+            Some(Event::Keyboard(KeyEvent::from(Key::Enter)))
+        );
+    }
+
+    #[test]
+    fn should_get_listener_ports() {
+        let application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        let ports = application.listener_ports();
+        assert_eq!(ports.len(), 1);
+        assert_eq!(ports[0].id, 0);
+    }
+
+    #[test]
+    fn should_pause_all_ports_except_one() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application.pause_all_ports_except(0, true).is_ok());
+        assert!(application.unpause_all_ports().is_ok());
+    }
+
+    #[test]
+    fn should_get_listener_iteration_timing() {
+        let application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application.wait_listener_ready(Duration::from_secs(5)));
+        assert!(application.listener_iteration_timing().iterations > 0);
+    }
+
+    #[test]
+    fn should_wait_listener_ready() {
+        let application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application.wait_listener_ready(Duration::from_secs(5)));
+        assert!(application.listener_ports()[0].last_poll.is_some());
+    }
+
+    #[test]
+    fn should_set_tick_interval() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config_with_tick(Duration::from_secs(1)));
+        assert_eq!(
+            application.listener_tick_interval(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            application.listener_poll_timeout(),
+            Duration::from_millis(10)
+        );
+        assert!(application
+            .set_tick_interval(Some(Duration::from_millis(500)))
+            .is_ok());
+        assert_eq!(
+            application.listener_tick_interval(),
+            Some(Duration::from_millis(500))
+        );
+        assert!(application.set_tick_interval(None).is_ok());
+        assert_eq!(application.listener_tick_interval(), None);
+    }
+
+    #[test]
+    fn should_validate_listener_config() {
+        assert!(
+            Application::<MockComponentId, MockMsg, MockEvent>::try_init(
+                listener_config_with_tick(Duration::from_secs(1))
+            )
+            .is_ok()
+        );
+        let invalid = || {
+            listener_config()
+                .poll_timeout(Duration::from_millis(500))
+                .tick_interval(Duration::from_millis(100))
+        };
+        assert!(matches!(
+            Application::<MockComponentId, MockMsg, MockEvent>::try_init(invalid()),
+            Err(ApplicationError::Listener(
+                ListenerError::PollTimeoutTooLong { .. }
+            ))
+        ));
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application.try_restart_listener(invalid()).is_err());
+        // Current listener is kept
+        assert!(application.wait_listener_ready(Duration::from_secs(5)));
+        assert!(application
+            .try_restart_listener(listener_config_with_tick(Duration::from_secs(1)))
+            .is_ok());
+        // Not validated by init and restart_listener
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(invalid());
+        assert!(application.restart_listener(invalid()).is_ok());
+    }
+
+    #[test]
+    fn should_stop_listener_with_timeout() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application.stop_listener(Duration::from_secs(1)).is_ok());
+        assert!(application.restart_listener(listener_config()).is_ok());
+    }
+
     #[test]
     fn should_manipulate_components() {
         let mut application: Application<MockComponentId, MockMsg, MockEvent> =
@@ -772,6 +1187,122 @@ mod test {
         assert!(events.len() >= 2);
     }
 
+    #[test]
+    fn should_inject_events() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(EventListenerCfg::default());
+        assert!(application
+            .mount(
+                MockComponentId::InputFoo,
+                Box::new(MockFooInput::default()),
+                vec![]
+            )
+            .is_ok());
+        assert!(application.active(&MockComponentId::InputFoo).is_ok());
+        assert!(application
+            .inject_event(Event::Keyboard(KeyEvent::from(Key::Enter)))
+            .is_ok());
+        assert_eq!(
+            application
+                .tick(PollStrategy::Once)
+                .ok()
+                .unwrap()
+                .as_slice(),
+            &[MockMsg::FooSubmit(String::from(""))]
+        );
+    }
+
+    #[test]
+    fn should_schedule_events() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(EventListenerCfg::default());
+        assert!(application
+            .mount(
+                MockComponentId::InputFoo,
+                Box::new(MockFooInput::default()),
+                vec![]
+            )
+            .is_ok());
+        assert!(application.active(&MockComponentId::InputFoo).is_ok());
+        let handle = application
+            .schedule_event(
+                Duration::from_millis(50),
+                Event::Keyboard(KeyEvent::from(Key::Enter)),
+            )
+            .ok()
+            .unwrap();
+        assert_eq!(
+            application
+                .tick(PollStrategy::TryFor(Duration::from_millis(300)))
+                .ok()
+                .unwrap()
+                .as_slice(),
+            &[MockMsg::FooSubmit(String::from(""))]
+        );
+        assert_eq!(application.cancel_scheduled_event(handle), false);
+    }
+
+    #[test]
+    fn should_forward_tick_every_n_ticks() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application
+            .mount(
+                MockComponentId::InputFoo,
+                Box::new(MockFooInput::default()),
+                vec![]
+            )
+            .is_ok());
+        assert!(application
+            .mount(
+                MockComponentId::InputBar,
+                Box::new(MockBarInput::default()),
+                vec![Sub::new(SubEventClause::TickEvery(4), SubClause::Always)]
+            )
+            .is_ok());
+        assert!(application.active(&MockComponentId::InputFoo).is_ok());
+        // One in four ticks is forwarded to BAR
+        assert_eq!(
+            application.forward_to_subscriptions(vec![Event::Tick; 3]),
+            vec![]
+        );
+        assert_eq!(
+            application.forward_to_subscriptions(vec![Event::Tick; 6]),
+            vec![MockMsg::BarTick, MockMsg::BarTick]
+        );
+        assert_eq!(application.ticks, 9);
+    }
+
+    #[test]
+    fn should_forward_key_repeats_to_subscriptions() {
+        let mut application: Application<MockComponentId, MockMsg, MockEvent> =
+            Application::init(listener_config());
+        assert!(application
+            .mount(
+                MockComponentId::InputFoo,
+                Box::new(MockFooInput::default()),
+                vec![]
+            )
+            .is_ok());
+        assert!(application
+            .mount(
+                MockComponentId::InputBar,
+                Box::new(MockBarInput::default()),
+                vec![Sub::new(
+                    SubEventClause::Keyboard(KeyEvent::from(Key::Enter)),
+                    SubClause::Always
+                )]
+            )
+            .is_ok());
+        assert!(application.active(&MockComponentId::InputFoo).is_ok());
+        let mut repeat = KeyEvent::from(Key::Enter);
+        repeat.kind = KeyEventKind::Repeat;
+        assert_eq!(
+            application.forward_to_subscriptions(vec![Event::Keyboard(repeat)]),
+            vec![MockMsg::BarSubmit(String::new())]
+        );
+    }
+
     #[test]
     fn should_not_propagate_event_when_subs_are_locked() {
         let mut application: Application<MockComponentId, MockMsg, MockEvent> =
@@ -25,9 +25,15 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use crate::listener::PortId;
+
 use bitflags::bitflags;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
 
 // -- event
 
@@ -41,10 +47,48 @@ where
 {
     /// A keyboard event
     Keyboard(KeyEvent),
-    /// This event is raised after the terminal window is resized
+    /// A mouse event
+    Mouse(MouseEvent),
+    /// A higher-level mouse event, aggregated from a sequence of `Mouse` events.
+    /// It's raised after the mouse event which completes the gesture, only if enabled with
+    /// `EventListenerCfg::mouse_gestures()`
+    MouseGesture(MouseGesture),
+    /// Text pasted into the terminal, reported at once rather than as a sequence of `Keyboard` events,
+    /// so that components can tell pasted text from typed text (e.g. to skip per-key validation).
+    /// Since the backends currently supported don't report bracketed paste, paste events are raised only
+    /// by the paste heuristic (see `EventListenerCfg::paste_heuristic()` and `EventListenerCfg::paste_events()`)
+    Paste(String),
+    /// This event is raised after the terminal window is resized.
+    /// It carries the new size of the terminal as (columns, rows), as reported by the backend
     WindowResize(u16, u16),
+    /// This event is raised after a `WindowResize` which has brought the terminal below the minimum size
+    /// set with `EventListenerCfg::min_size()`. It carries the size of the terminal and the minimum size,
+    /// as (columns, rows)
+    TooSmall {
+        cols: u16,
+        rows: u16,
+        min: (u16, u16),
+    },
+    /// This event is raised after a `WindowResize` which has brought the terminal back to the minimum size
+    /// set with `EventListenerCfg::min_size()` (or above), after `TooSmall` has been raised
+    SizeOk,
     /// A ui tick event (should be configurable)
     Tick,
+    /// A diagnostic warning raised by the event listener (see `ListenerWarning`)
+    ListenerWarning(ListenerWarning),
+    /// This event is raised when the terminal gains focus.
+    /// Since the backends currently supported don't report focus changes, it can only be raised by a custom port
+    FocusGained,
+    /// This event is raised when the terminal loses focus (see `FocusGained`)
+    FocusLost,
+    /// This event is raised when the event listener is paused (e.g. with `Application::lock_ports()`
+    /// or a `PauseGuard`). It's raised only if enabled with `EventListenerCfg::pause_events()`
+    ListenerPaused,
+    /// This event is raised when the event listener is resumed after `ListenerPaused`
+    ListenerResumed,
+    /// An event which the backend couldn't map to any other event, carrying the raw bytes read from the terminal.
+    /// It's raised only by the termion input listener, if enabled with `EventListenerCfg::passthrough_raw()`
+    Raw(Vec<u8>),
     /// Unhandled event; Empty event
     None,
     /// User event; won't be used by standard library or by default input event listener;
@@ -56,7 +100,10 @@ impl<U> Event<U>
 where
     U: Eq + PartialEq + Clone + PartialOrd,
 {
-    pub(crate) fn is_keyboard(&self) -> Option<&KeyEvent> {
+    /// ### is_keyboard
+    ///
+    /// Returns the key event, if the event is a keyboard event
+    pub fn is_keyboard(&self) -> Option<&KeyEvent> {
         if let Event::Keyboard(k) = self {
             Some(k)
         } else {
@@ -64,14 +111,79 @@ where
         }
     }
 
+    /// ### is_mouse
+    ///
+    /// Returns the mouse event, if the event is a mouse event
+    pub fn is_mouse(&self) -> Option<&MouseEvent> {
+        if let Event::Mouse(m) = self {
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    /// ### is_mouse_gesture
+    ///
+    /// Returns the mouse gesture, if the event is a mouse gesture event
+    pub fn is_mouse_gesture(&self) -> Option<&MouseGesture> {
+        if let Event::MouseGesture(g) = self {
+            Some(g)
+        } else {
+            None
+        }
+    }
+
+    /// ### is_paste
+    ///
+    /// Returns the pasted text, if the event is a paste event
+    pub fn is_paste(&self) -> Option<&str> {
+        if let Event::Paste(text) = self {
+            Some(text.as_str())
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn is_window_resize(&self) -> bool {
         matches!(self, Self::WindowResize(_, _))
     }
 
+    pub(crate) fn is_too_small(&self) -> bool {
+        matches!(self, Self::TooSmall { .. })
+    }
+
+    pub(crate) fn is_size_ok(&self) -> bool {
+        matches!(self, Self::SizeOk)
+    }
+
+    pub(crate) fn is_focus_gained(&self) -> bool {
+        matches!(self, Self::FocusGained)
+    }
+
+    pub(crate) fn is_focus_lost(&self) -> bool {
+        matches!(self, Self::FocusLost)
+    }
+
+    pub(crate) fn is_listener_paused(&self) -> bool {
+        matches!(self, Self::ListenerPaused)
+    }
+
+    pub(crate) fn is_listener_resumed(&self) -> bool {
+        matches!(self, Self::ListenerResumed)
+    }
+
     pub(crate) fn is_tick(&self) -> bool {
         matches!(self, Self::Tick)
     }
 
+    pub(crate) fn is_listener_warning(&self) -> Option<&ListenerWarning> {
+        if let Event::ListenerWarning(w) = self {
+            Some(w)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn is_user(&self) -> Option<&U> {
         if let Event::User(u) = self {
             Some(u)
@@ -81,6 +193,18 @@ where
     }
 }
 
+/// ## ListenerWarning
+///
+/// Diagnostic warnings raised by the event listener, which describe scheduling problems.
+/// They're meant to be logged by the application.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+pub enum ListenerWarning {
+    /// The port `port_id` has not been polled for `elapsed`, which exceeds the starvation threshold
+    PortStarved { port_id: PortId, elapsed: Duration },
+    /// A port couldn't keep up with its event source and `skipped` events have been dropped
+    EventsDropped { skipped: u64 },
+}
+
 /// ## NoUserEvent
 ///
 /// When using event you can use this as type parameter if you don't want to use user events
@@ -101,6 +225,32 @@ pub enum NoUserEvent {}
 pub struct KeyEvent {
     pub code: Key,
     pub modifiers: KeyModifiers,
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_key_press")
+    )]
+    pub kind: KeyEventKind,
+}
+
+/// Key presses are not serialized, in order to keep keybindings in config files short
+#[cfg(feature = "serialize")]
+fn is_key_press(kind: &KeyEventKind) -> bool {
+    *kind == KeyEventKind::Press
+}
+
+/// ## KeyEventKind
+///
+/// Describes whether the key has just been pressed or whether it's being held
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+#[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
+pub enum KeyEventKind {
+    /// Key has been pressed
+    #[default]
+    Press,
+    /// Key is being held and the event is an auto-repeat of the previous one.
+    /// The backends currently supported don't report repeats, but they can be synthesized by the event listener
+    /// (see `EventListenerCfg::synth_key_repeat()`)
+    Repeat,
 }
 
 /// ## Key
@@ -151,6 +301,9 @@ pub enum Key {
     Null,
     /// Escape key.
     Esc,
+    /// A key reported by the backend which can't be represented by the other variants.
+    /// Applications can use it to detect that a key has been pressed anyway.
+    Unsupported,
 }
 
 bitflags! {
@@ -163,12 +316,221 @@ bitflags! {
         const SHIFT = 0b0000_0001;
         const CONTROL = 0b0000_0010;
         const ALT = 0b0000_0100;
+        /// Super (or "windows") key. Not reported by the current backends, but can be used in keybindings
+        const SUPER = 0b0000_1000;
+    }
+}
+
+/// ## KeyParseError
+///
+/// Error returned when parsing keys from a string
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum KeyParseError {
+    #[error("unknown key modifier `{0}`")]
+    UnknownModifier(String),
+}
+
+impl FromStr for KeyModifiers {
+    type Err = KeyParseError;
+
+    /// ### from_str
+    ///
+    /// Parse key modifiers from a string, such as `CTRL|SHIFT` or `ctrl+alt`.
+    /// Modifiers can be separated by `|`, `+` or whitespaces and names are case-insensitive:
+    /// `ctrl` (or `control`), `alt` (or `meta`), `shift`, `super` and `none`.
+    /// An empty string is parsed as `KeyModifiers::NONE`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|c: char| c == '|' || c == '+' || c.is_whitespace())
+            .filter(|x| !x.is_empty())
+            .try_fold(KeyModifiers::NONE, |acc, token| {
+                let modifier = match token.to_lowercase().as_str() {
+                    "ctrl" | "control" => KeyModifiers::CONTROL,
+                    "alt" | "meta" => KeyModifiers::ALT,
+                    "shift" => KeyModifiers::SHIFT,
+                    "super" => KeyModifiers::SUPER,
+                    "none" => KeyModifiers::NONE,
+                    _ => return Err(KeyParseError::UnknownModifier(token.to_string())),
+                };
+                Ok(acc | modifier)
+            })
+    }
+}
+
+impl fmt::Display for KeyModifiers {
+    /// ### fmt
+    ///
+    /// Format key modifiers in their canonical form, e.g. `CTRL+SHIFT`; no modifiers are formatted as `NONE`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = [
+            (KeyModifiers::CONTROL, "CTRL"),
+            (KeyModifiers::ALT, "ALT"),
+            (KeyModifiers::SHIFT, "SHIFT"),
+            (KeyModifiers::SUPER, "SUPER"),
+        ]
+        .iter()
+        .filter(|(modifier, _)| self.contains(*modifier))
+        .map(|(_, name)| *name)
+        .collect();
+        match names.is_empty() {
+            true => write!(f, "NONE"),
+            false => write!(f, "{}", names.join("+")),
+        }
     }
 }
 
 impl KeyEvent {
     pub fn new(code: Key, modifiers: KeyModifiers) -> Self {
-        Self { code, modifiers }
+        Self {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+        }
+    }
+
+    /// ### is_repeat
+    ///
+    /// Returns whether the key event is an auto-repeat
+    pub fn is_repeat(&self) -> bool {
+        self.kind == KeyEventKind::Repeat
+    }
+
+    /// ### has_ctrl
+    ///
+    /// Returns whether Ctrl is held, regardless of the other modifiers
+    pub fn has_ctrl(&self) -> bool {
+        self.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    /// ### has_alt
+    ///
+    /// Returns whether Alt is held, regardless of the other modifiers
+    pub fn has_alt(&self) -> bool {
+        self.modifiers.contains(KeyModifiers::ALT)
+    }
+
+    /// ### has_shift
+    ///
+    /// Returns whether Shift is held, regardless of the other modifiers
+    pub fn has_shift(&self) -> bool {
+        self.modifiers.contains(KeyModifiers::SHIFT)
+    }
+
+    /// ### has_super
+    ///
+    /// Returns whether Super is held, regardless of the other modifiers
+    pub fn has_super(&self) -> bool {
+        self.modifiers.contains(KeyModifiers::SUPER)
+    }
+
+    /// ### only
+    ///
+    /// Returns whether exactly `modifiers` are held (e.g. `only(KeyModifiers::CONTROL)` is false for Ctrl+Shift).
+    /// `only(KeyModifiers::NONE)` tells whether no modifier is held
+    pub fn only(&self, modifiers: KeyModifiers) -> bool {
+        self.modifiers == modifiers
+    }
+
+    /// ### normalized
+    ///
+    /// Returns the key event in a terminal-independent form, to be used in keymap lookups.
+    /// Terminals report shifted characters differently (e.g. `Char('A')`, `Char('a') + SHIFT` or `Char('A') + SHIFT`):
+    /// in the normalized form, `SHIFT` is folded into the character, so all of them become `Char('A')`.
+    /// Other keys and modifiers are left untouched.
+    pub fn normalized(mut self) -> KeyEvent {
+        if let Key::Char(ch) = self.code {
+            if self.modifiers.contains(KeyModifiers::SHIFT) {
+                let mut upper = ch.to_uppercase();
+                if let (Some(upper), None) = (upper.next(), upper.next()) {
+                    self.code = Key::Char(upper);
+                }
+                self.modifiers.remove(KeyModifiers::SHIFT);
+            }
+        }
+        self
+    }
+
+    /// ### to_id
+    ///
+    /// Encode the key event into a stable numeric id, which can be decoded back with `from_id()`
+    /// (e.g. to send keymaps over compact wire formats). Unlike `Hash`, the encoding is a reversible packing:
+    ///
+    /// - bits 0-31: key payload: the code point for `Char`, the number for `Function`; 0 for the other keys
+    /// - bits 32-39: key code, as the index of the `Key` variant in declaration order (`Backspace` is 0,
+    ///   `Unsupported` is 18); new keys are only ever appended, so ids are stable across versions
+    /// - bits 40-47: modifiers bits (`KeyModifiers::bits()`)
+    /// - bit 48: kind (0 for `Press`, 1 for `Repeat`)
+    /// - bits 49-63: unused, always 0
+    pub fn to_id(&self) -> u64 {
+        let (code, payload) = match self.code {
+            Key::Backspace => (0, 0),
+            Key::Enter => (1, 0),
+            Key::Left => (2, 0),
+            Key::Right => (3, 0),
+            Key::Up => (4, 0),
+            Key::Down => (5, 0),
+            Key::Home => (6, 0),
+            Key::End => (7, 0),
+            Key::PageUp => (8, 0),
+            Key::PageDown => (9, 0),
+            Key::Tab => (10, 0),
+            Key::BackTab => (11, 0),
+            Key::Delete => (12, 0),
+            Key::Insert => (13, 0),
+            Key::Function(n) => (14, n as u32),
+            Key::Char(ch) => (15, ch as u32),
+            Key::Null => (16, 0),
+            Key::Esc => (17, 0),
+            Key::Unsupported => (18, 0),
+        };
+        let kind = match self.kind {
+            KeyEventKind::Press => 0,
+            KeyEventKind::Repeat => 1,
+        };
+        (payload as u64) | (code << 32) | ((self.modifiers.bits() as u64) << 40) | (kind << 48)
+    }
+
+    /// ### from_id
+    ///
+    /// Decode a key event from an id built by `to_id()`.
+    /// Returns `None` if the id is not a valid encoding (e.g. unknown key code, invalid character or unused bits set)
+    pub fn from_id(id: u64) -> Option<KeyEvent> {
+        if id >> 49 != 0 {
+            return None;
+        }
+        let payload = (id & 0xffff_ffff) as u32;
+        let no_payload = |key: Key| (payload == 0).then_some(key);
+        let code = match (id >> 32) & 0xff {
+            0 => no_payload(Key::Backspace),
+            1 => no_payload(Key::Enter),
+            2 => no_payload(Key::Left),
+            3 => no_payload(Key::Right),
+            4 => no_payload(Key::Up),
+            5 => no_payload(Key::Down),
+            6 => no_payload(Key::Home),
+            7 => no_payload(Key::End),
+            8 => no_payload(Key::PageUp),
+            9 => no_payload(Key::PageDown),
+            10 => no_payload(Key::Tab),
+            11 => no_payload(Key::BackTab),
+            12 => no_payload(Key::Delete),
+            13 => no_payload(Key::Insert),
+            14 => u8::try_from(payload).ok().map(Key::Function),
+            15 => char::from_u32(payload).map(Key::Char),
+            16 => no_payload(Key::Null),
+            17 => no_payload(Key::Esc),
+            18 => no_payload(Key::Unsupported),
+            _ => None,
+        }?;
+        let modifiers = KeyModifiers::from_bits(((id >> 40) & 0xff) as u8)?;
+        let kind = match (id >> 48) & 1 {
+            0 => KeyEventKind::Press,
+            _ => KeyEventKind::Repeat,
+        };
+        Some(KeyEvent {
+            code,
+            modifiers,
+            kind,
+        })
     }
 }
 
@@ -178,6 +540,241 @@ impl From<Key> for KeyEvent {
     }
 }
 
+// -- mouse
+
+/// ## MouseEvent
+///
+/// A mouse event
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Deserialize, Serialize),
+    serde(tag = "type")
+)]
+pub struct MouseEvent {
+    /// The kind of mouse event
+    pub kind: MouseEventKind,
+    /// Key modifiers active when the event occurred (e.g. Ctrl held while scrolling, see `is_zoom()`).
+    /// Termion doesn't report the modifiers of mouse events, so they're always empty with the termion backend.
+    pub modifiers: KeyModifiers,
+    /// Column where the event occurred (0-based)
+    pub column: u16,
+    /// Row where the event occurred (0-based)
+    pub row: u16,
+    /// Number of consecutive clicks (1 = single, 2 = double, 3 = triple).
+    /// Only meaningful for `MouseEventKind::Down`; it is `0` for any other kind.
+    pub click_count: u8,
+    /// Amount of lines scrolled. The sign follows the scroll direction:
+    /// negative for `MouseEventKind::ScrollUp` and positive for `MouseEventKind::ScrollDown`.
+    /// It is `0` for any other kind.
+    /// A single wheel tick has a magnitude of 1, but it may be greater if scroll coalescing is enabled
+    /// (see `EventListenerCfg::coalesce_scroll()`).
+    pub delta: i16,
+}
+
+/// ## MouseEventKind
+///
+/// Describes the kind of mouse event
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Deserialize, Serialize),
+    serde(tag = "type", content = "args")
+)]
+pub enum MouseEventKind {
+    /// Mouse button has been pressed
+    Down(MouseButton),
+    /// Mouse button has been released
+    Up(MouseButton),
+    /// Mouse has been moved while the button was pressed
+    Drag(MouseButton),
+    /// Mouse has been moved without any button pressed
+    Moved,
+    /// Mouse wheel has been scrolled down
+    ScrollDown,
+    /// Mouse wheel has been scrolled up
+    ScrollUp,
+}
+
+/// ## MouseGesture
+///
+/// A mouse gesture, aggregated from a sequence of mouse events
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Deserialize, Serialize),
+    serde(tag = "type")
+)]
+pub enum MouseGesture {
+    /// The mouse is being dragged with `button` pressed.
+    /// It's raised on each `Drag` event, with the position where the button has been pressed as `start`
+    /// and the position of the drag event as `current`, both as (column, row)
+    Drag {
+        start: (u16, u16),
+        current: (u16, u16),
+        button: MouseButton,
+    },
+}
+
+/// ## MouseButton
+///
+/// Mouse button
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+#[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
+pub enum MouseButton {
+    /// Left mouse button
+    Left,
+    /// Right mouse button
+    Right,
+    /// Middle mouse button
+    Middle,
+}
+
+impl MouseEvent {
+    pub fn new(kind: MouseEventKind, column: u16, row: u16, modifiers: KeyModifiers) -> Self {
+        let click_count = match kind {
+            MouseEventKind::Down(_) => 1,
+            _ => 0,
+        };
+        let delta = match kind {
+            MouseEventKind::ScrollUp => -1,
+            MouseEventKind::ScrollDown => 1,
+            _ => 0,
+        };
+        Self {
+            kind,
+            modifiers,
+            column,
+            row,
+            click_count,
+            delta,
+        }
+    }
+
+    /// ### is_scroll
+    ///
+    /// Returns whether the mouse event is a scroll event
+    pub fn is_scroll(&self) -> bool {
+        matches!(
+            self.kind,
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+        )
+    }
+
+    /// ### is_zoom
+    ///
+    /// Returns whether the mouse event is a scroll event with Ctrl held, commonly used to zoom.
+    /// Always false with the termion backend, which doesn't report modifiers for mouse events
+    pub fn is_zoom(&self) -> bool {
+        self.is_scroll() && self.modifiers.intersects(KeyModifiers::CONTROL)
+    }
+}
+
+// -- macros
+
+/// ## key
+///
+/// Build a `KeyEvent` (with `KeyEventKind::Press`) from a key and its modifiers.
+/// Modifiers (`Ctrl`, `Alt`, `Shift`, `Super`) are joined to the key by `+`; the key is either a char literal,
+/// a function key (`F1`..`F12`) or any unit variant of `Key`.
+///
+/// ```rust
+/// use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+/// use tuirealm::key;
+///
+/// const QUIT: KeyEvent = key!(Ctrl + 'c');
+/// assert_eq!(QUIT, KeyEvent::new(Key::Char('c'), KeyModifiers::CONTROL));
+/// assert_eq!(key!(Enter), KeyEvent::from(Key::Enter));
+/// assert_eq!(key!(Alt + F5), KeyEvent::new(Key::Function(5), KeyModifiers::ALT));
+/// assert_eq!(
+///     key!(Ctrl + Shift + 'z'),
+///     KeyEvent::new(Key::Char('z'), KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+/// );
+/// ```
+///
+/// With no more than one modifier, the macro can be used as a pattern too (it matches key presses only):
+///
+/// ```rust
+/// use tuirealm::event::{Key, KeyEvent};
+/// use tuirealm::key;
+///
+/// let ev = KeyEvent::from(Key::Esc);
+/// assert!(matches!(ev, key!(Esc) | key!(Ctrl + 'c')));
+/// ```
+///
+/// Unknown modifiers are rejected at compile time:
+///
+/// ```rust,compile_fail
+/// use tuirealm::key;
+///
+/// let _ = key!(Meta + 'c');
+/// ```
+///
+/// As unknown keys are:
+///
+/// ```rust,compile_fail
+/// use tuirealm::key;
+///
+/// let _ = key!(Ctrl + Foo);
+/// ```
+#[macro_export]
+macro_rules! key {
+    // -- key codes
+    (@key F1) => { $crate::event::Key::Function(1) };
+    (@key F2) => { $crate::event::Key::Function(2) };
+    (@key F3) => { $crate::event::Key::Function(3) };
+    (@key F4) => { $crate::event::Key::Function(4) };
+    (@key F5) => { $crate::event::Key::Function(5) };
+    (@key F6) => { $crate::event::Key::Function(6) };
+    (@key F7) => { $crate::event::Key::Function(7) };
+    (@key F8) => { $crate::event::Key::Function(8) };
+    (@key F9) => { $crate::event::Key::Function(9) };
+    (@key F10) => { $crate::event::Key::Function(10) };
+    (@key F11) => { $crate::event::Key::Function(11) };
+    (@key F12) => { $crate::event::Key::Function(12) };
+    (@key $c:literal) => { $crate::event::Key::Char($c) };
+    (@key $k:ident) => { $crate::event::Key::$k };
+    // -- modifiers
+    (@mod Ctrl) => { $crate::event::KeyModifiers::CONTROL };
+    (@mod Alt) => { $crate::event::KeyModifiers::ALT };
+    (@mod Shift) => { $crate::event::KeyModifiers::SHIFT };
+    (@mod Super) => { $crate::event::KeyModifiers::SUPER };
+    (@mod $m:ident) => { compile_error!(concat!("unknown key modifier `", stringify!($m), "`; expected Ctrl, Alt, Shift or Super")) };
+    // -- key events
+    ($m1:ident + $m2:ident + $m3:ident + $k:tt) => {
+        $crate::event::KeyEvent {
+            code: $crate::key!(@key $k),
+            modifiers: $crate::event::KeyModifiers::from_bits_truncate(
+                $crate::key!(@mod $m1).bits() | $crate::key!(@mod $m2).bits() | $crate::key!(@mod $m3).bits(),
+            ),
+            kind: $crate::event::KeyEventKind::Press,
+        }
+    };
+    ($m1:ident + $m2:ident + $k:tt) => {
+        $crate::event::KeyEvent {
+            code: $crate::key!(@key $k),
+            modifiers: $crate::event::KeyModifiers::from_bits_truncate(
+                $crate::key!(@mod $m1).bits() | $crate::key!(@mod $m2).bits(),
+            ),
+            kind: $crate::event::KeyEventKind::Press,
+        }
+    };
+    ($m:ident + $k:tt) => {
+        $crate::event::KeyEvent {
+            code: $crate::key!(@key $k),
+            modifiers: $crate::key!(@mod $m),
+            kind: $crate::event::KeyEventKind::Press,
+        }
+    };
+    ($k:tt) => {
+        $crate::event::KeyEvent {
+            code: $crate::key!(@key $k),
+            modifiers: $crate::event::KeyModifiers::NONE,
+            kind: $crate::event::KeyEventKind::Press,
+        }
+    };
+}
+
 #[cfg(test)]
 mod test {
 
@@ -186,11 +783,209 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn key_modifiers_from_str() {
+        assert_eq!(
+            KeyModifiers::from_str("CTRL|SHIFT").ok().unwrap(),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        );
+        assert_eq!(
+            KeyModifiers::from_str("ctrl+alt").ok().unwrap(),
+            KeyModifiers::CONTROL | KeyModifiers::ALT
+        );
+        assert_eq!(
+            KeyModifiers::from_str(" Control  Meta super ")
+                .ok()
+                .unwrap(),
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER
+        );
+        assert_eq!(KeyModifiers::from_str("").ok().unwrap(), KeyModifiers::NONE);
+        assert_eq!(
+            KeyModifiers::from_str("none").ok().unwrap(),
+            KeyModifiers::NONE
+        );
+        assert_eq!(
+            KeyModifiers::from_str("ctrl+hyper").err().unwrap(),
+            KeyParseError::UnknownModifier(String::from("hyper"))
+        );
+    }
+
+    #[test]
+    fn key_modifiers_display() {
+        assert_eq!(KeyModifiers::NONE.to_string(), "NONE");
+        assert_eq!(
+            (KeyModifiers::SHIFT | KeyModifiers::CONTROL).to_string(),
+            "CTRL+SHIFT"
+        );
+        let all = KeyModifiers::all();
+        assert_eq!(all.to_string(), "CTRL+ALT+SHIFT+SUPER");
+        assert_eq!(KeyModifiers::from_str(&all.to_string()).ok().unwrap(), all);
+    }
+
+    #[test]
+    fn key_macro_should_build_key_events() {
+        assert_eq!(key!('a'), KeyEvent::from(Key::Char('a')));
+        assert_eq!(key!(Backspace), KeyEvent::from(Key::Backspace));
+        assert_eq!(key!(F12), KeyEvent::from(Key::Function(12)));
+        assert_eq!(key!(Unsupported), KeyEvent::from(Key::Unsupported));
+        assert_eq!(
+            key!(Shift + BackTab),
+            KeyEvent::new(Key::BackTab, KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            key!(Ctrl + Alt + Shift + Delete),
+            KeyEvent::new(
+                Key::Delete,
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT
+            )
+        );
+        match KeyEvent::new(Key::Char('c'), KeyModifiers::CONTROL) {
+            key!(Ctrl + 'c') => {}
+            _ => panic!("should match"),
+        }
+    }
+
+    #[test]
+    fn key_macro_constants_should_be_usable_as_patterns() {
+        const QUIT: KeyEvent = key!(Ctrl + 'c');
+        const SUBMIT: Event<NoUserEvent> = Event::Keyboard(key!(Enter));
+        match KeyEvent::new(Key::Char('c'), KeyModifiers::CONTROL) {
+            QUIT => {}
+            _ => panic!("should match"),
+        }
+        match Event::<NoUserEvent>::Keyboard(KeyEvent::from(Key::Enter)) {
+            SUBMIT => {}
+            _ => panic!("should match"),
+        }
+        // Auto-repeats don't match the constant
+        let mut repeat = QUIT;
+        repeat.kind = KeyEventKind::Repeat;
+        assert!(!matches!(repeat, QUIT));
+    }
+
     #[test]
     fn new_key_event() {
         let k = KeyEvent::new(Key::Down, KeyModifiers::CONTROL);
         assert_eq!(k.code, Key::Down);
         assert_eq!(k.modifiers, KeyModifiers::CONTROL);
+        assert_eq!(k.kind, KeyEventKind::Press);
+        assert_eq!(k.is_repeat(), false);
+    }
+
+    #[test]
+    fn should_normalize_shifted_chars() {
+        let expected = KeyEvent::from(Key::Char('A'));
+        // Terminal reporting the shifted char
+        assert_eq!(KeyEvent::from(Key::Char('A')).normalized(), expected);
+        // Terminal reporting the base char and shift
+        assert_eq!(
+            KeyEvent::new(Key::Char('a'), KeyModifiers::SHIFT).normalized(),
+            expected
+        );
+        // Terminal reporting both
+        assert_eq!(
+            KeyEvent::new(Key::Char('A'), KeyModifiers::SHIFT).normalized(),
+            expected
+        );
+        // Other modifiers are kept
+        assert_eq!(
+            KeyEvent::new(Key::Char('a'), KeyModifiers::CONTROL | KeyModifiers::SHIFT).normalized(),
+            KeyEvent::new(Key::Char('A'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyEvent::new(Key::Char('a'), KeyModifiers::CONTROL).normalized(),
+            KeyEvent::new(Key::Char('a'), KeyModifiers::CONTROL)
+        );
+        // Not a char
+        assert_eq!(
+            KeyEvent::new(Key::Tab, KeyModifiers::SHIFT).normalized(),
+            KeyEvent::new(Key::Tab, KeyModifiers::SHIFT)
+        );
+        // Char without a single uppercase char
+        assert_eq!(
+            KeyEvent::new(Key::Char('ß'), KeyModifiers::SHIFT).normalized(),
+            KeyEvent::from(Key::Char('ß'))
+        );
+    }
+
+    #[test]
+    fn key_event_id() {
+        let keys = [
+            Key::Backspace,
+            Key::Enter,
+            Key::Tab,
+            Key::Function(1),
+            Key::Function(255),
+            Key::Char('a'),
+            Key::Char('A'),
+            Key::Char('è'),
+            Key::Char('🦀'),
+            Key::Null,
+            Key::Esc,
+            Key::Unsupported,
+        ];
+        let modifiers = [
+            KeyModifiers::NONE,
+            KeyModifiers::SHIFT,
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+            KeyModifiers::all(),
+        ];
+        for key in keys {
+            for modifiers in modifiers {
+                for kind in [KeyEventKind::Press, KeyEventKind::Repeat] {
+                    let ev = KeyEvent {
+                        code: key,
+                        modifiers,
+                        kind,
+                    };
+                    assert_eq!(KeyEvent::from_id(ev.to_id()), Some(ev));
+                }
+            }
+        }
+        // Layout
+        assert_eq!(KeyEvent::from(Key::Backspace).to_id(), 0);
+        assert_eq!(KeyEvent::from(Key::Char('a')).to_id(), (15 << 32) | 97);
+        assert_eq!(
+            KeyEvent::new(Key::Enter, KeyModifiers::CONTROL).to_id(),
+            (1 << 32) | (0b10 << 40)
+        );
+        // Invalid ids
+        assert_eq!(KeyEvent::from_id(19 << 32), None);
+        assert_eq!(KeyEvent::from_id(1 << 49), None);
+        assert_eq!(KeyEvent::from_id((1 << 32) | 1), None);
+        assert_eq!(KeyEvent::from_id((15 << 32) | 0xd800), None);
+        assert_eq!(KeyEvent::from_id((14 << 32) | 256), None);
+        assert_eq!(KeyEvent::from_id(0b1000_0000 << 40), None);
+    }
+
+    #[test]
+    fn key_event_modifiers() {
+        let k = KeyEvent::new(Key::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(k.has_ctrl(), true);
+        assert_eq!(k.has_alt(), false);
+        assert_eq!(k.has_shift(), false);
+        assert_eq!(k.has_super(), false);
+        assert_eq!(k.only(KeyModifiers::CONTROL), true);
+        assert_eq!(k.only(KeyModifiers::NONE), false);
+        // Combined
+        let k = KeyEvent::new(Key::Char('C'), KeyModifiers::CONTROL | KeyModifiers::SHIFT);
+        assert_eq!(k.has_ctrl(), true);
+        assert_eq!(k.has_shift(), true);
+        assert_eq!(k.has_alt(), false);
+        assert_eq!(k.only(KeyModifiers::CONTROL), false);
+        assert_eq!(k.only(KeyModifiers::SHIFT), false);
+        assert_eq!(k.only(KeyModifiers::CONTROL | KeyModifiers::SHIFT), true);
+        let k = KeyEvent::new(Key::Left, KeyModifiers::ALT | KeyModifiers::SUPER);
+        assert_eq!(k.has_alt(), true);
+        assert_eq!(k.has_super(), true);
+        assert_eq!(k.only(KeyModifiers::ALT | KeyModifiers::SUPER), true);
+        // No modifiers
+        let k = KeyEvent::from(Key::Enter);
+        assert_eq!(
+            k.has_ctrl() || k.has_alt() || k.has_shift() || k.has_super(),
+            false
+        );
+        assert_eq!(k.only(KeyModifiers::NONE), true);
     }
 
     #[test]
@@ -200,6 +995,55 @@ mod test {
         assert_eq!(k.modifiers, KeyModifiers::empty());
     }
 
+    #[test]
+    fn new_mouse_event() {
+        let m = MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            10,
+            4,
+            KeyModifiers::NONE,
+        );
+        assert_eq!(m.kind, MouseEventKind::Down(MouseButton::Left));
+        assert_eq!(m.column, 10);
+        assert_eq!(m.row, 4);
+        assert_eq!(m.click_count, 1);
+        assert_eq!(m.delta, 0);
+        assert_eq!(m.is_scroll(), false);
+        let m = MouseEvent::new(MouseEventKind::Moved, 10, 4, KeyModifiers::NONE);
+        assert_eq!(m.click_count, 0);
+        let m = MouseEvent::new(MouseEventKind::ScrollUp, 10, 4, KeyModifiers::NONE);
+        assert_eq!(m.delta, -1);
+        assert!(m.is_scroll());
+        let m = MouseEvent::new(MouseEventKind::ScrollDown, 10, 4, KeyModifiers::NONE);
+        assert_eq!(m.delta, 1);
+        assert!(m.is_scroll());
+    }
+
+    #[test]
+    fn mouse_event_is_zoom() {
+        let m = MouseEvent::new(MouseEventKind::ScrollUp, 10, 4, KeyModifiers::NONE);
+        assert_eq!(m.is_zoom(), false);
+        let m = MouseEvent::new(MouseEventKind::ScrollUp, 10, 4, KeyModifiers::CONTROL);
+        assert_eq!(m.is_zoom(), true);
+        let m = MouseEvent::new(
+            MouseEventKind::ScrollDown,
+            10,
+            4,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+        assert_eq!(m.is_zoom(), true);
+        let m = MouseEvent::new(MouseEventKind::ScrollDown, 10, 4, KeyModifiers::ALT);
+        assert_eq!(m.is_zoom(), false);
+        // Not a scroll
+        let m = MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            10,
+            4,
+            KeyModifiers::CONTROL,
+        );
+        assert_eq!(m.is_zoom(), false);
+    }
+
     #[test]
     fn check_events() {
         let e: Event<MockEvent> = Event::Keyboard(KeyEvent::new(Key::Down, KeyModifiers::CONTROL));
@@ -208,6 +1052,14 @@ mod test {
         assert_eq!(e.is_tick(), false);
         assert_eq!(e.is_tick(), false);
         assert!(e.is_user().is_none());
+        assert!(e.is_mouse().is_none());
+        let e: Event<MockEvent> = Event::Mouse(MouseEvent::new(
+            MouseEventKind::Moved,
+            0,
+            0,
+            KeyModifiers::NONE,
+        ));
+        assert!(e.is_mouse().is_some());
         let e: Event<MockEvent> = Event::WindowResize(0, 24);
         assert!(e.is_window_resize());
         assert!(e.is_keyboard().is_none());
@@ -294,4 +1146,19 @@ mod test {
         let r_keys: KeyBindings = deserialize(&mut readable);
         assert_eq!(keys, r_keys);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn should_serialize_unsupported_keys() {
+        let temp = NamedTempFile::new().expect("Failed to open tempfile");
+        let keys = KeyBindings::new(
+            KeyEvent::from(Key::Unsupported),
+            KeyEvent::new(Key::Unsupported, KeyModifiers::SHIFT),
+        );
+        let mut config = File::create(temp.path()).expect("Failed to open file for write");
+        serialize(&keys, &mut config);
+        let mut readable = File::open(temp.path()).expect("Failed to open file for read");
+        let r_keys: KeyBindings = deserialize(&mut readable);
+        assert_eq!(keys, r_keys);
+    }
 }
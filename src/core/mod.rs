@@ -25,6 +25,7 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+pub mod action;
 pub mod application;
 pub mod command;
 mod component;
@@ -37,7 +38,9 @@ mod view;
 // -- export
 pub use command::Cmd;
 pub use component::{Component, MockComponent};
-pub use state::{State, StateValue};
+#[cfg(feature = "serialize")]
+pub use state::StateJsonError;
+pub use state::{State, StateMapBuilder, StateValue};
 pub use view::{View, ViewError};
 
 // -- internal
@@ -0,0 +1,193 @@
+//! ## Color
+//!
+//! Color parsing and constructors for props colors
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::Color;
+use crate::utils::parser::parse_color;
+
+use thiserror::Error;
+
+/// ## ColorParseError
+///
+/// Error returned when a color can't be parsed from a string
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ColorParseError {
+    #[error("invalid hex color `{0}`: expected `#rgb` or `#rrggbb`")]
+    InvalidHex(String),
+    #[error("unknown color `{0}`")]
+    UnknownColor(String),
+}
+
+/// ## ColorExt
+///
+/// Extends the props `Color` with parsing helpers.
+/// Since `Color` is re-exported from `tui`, `FromStr` can't be implemented on it;
+/// use `Color::parse` instead.
+pub trait ColorExt: Sized {
+    /// ### from_hex
+    ///
+    /// Parse a color in `#rgb` or `#rrggbb` format into `Color::Rgb`
+    fn from_hex(hex: &str) -> Result<Self, ColorParseError>;
+
+    /// ### parse
+    ///
+    /// Parse a color from either its hex representation (see `from_hex`) or its name (e.g. `cyan`).
+    /// Names are case insensitive; any other format supported by `utils::parser::parse_color` is accepted too.
+    fn parse(s: &str) -> Result<Self, ColorParseError>;
+
+    /// ### rgb
+    ///
+    /// Instantiate a `Color::Rgb` from its red, green and blue channels
+    fn rgb(r: u8, g: u8, b: u8) -> Self;
+
+    /// ### hsl
+    ///
+    /// Instantiate a `Color::Rgb` from hue (degrees, wrapped into `0..360`),
+    /// saturation and lightness (both clamped into `0.0..=1.0`)
+    fn hsl(h: f64, s: f64, l: f64) -> Self;
+}
+
+impl ColorExt for Color {
+    fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        let invalid = || ColorParseError::InvalidHex(hex.to_string());
+        let digits = hex.strip_prefix('#').ok_or_else(invalid)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).map_err(|_| invalid());
+        match digits.len() {
+            // Each digit is repeated (e.g. `#f0a` => `#ff00aa`)
+            3 => {
+                let expand = |i: usize| channel(&digits[i..=i].repeat(2));
+                Ok(Color::Rgb(expand(0)?, expand(1)?, expand(2)?))
+            }
+            6 => Ok(Color::Rgb(
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, ColorParseError> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            Self::from_hex(s)
+        } else {
+            parse_color(s).ok_or_else(|| ColorParseError::UnknownColor(s.to_string()))
+        }
+    }
+
+    fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color::Rgb(r, g, b)
+    }
+
+    fn hsl(h: f64, s: f64, l: f64) -> Self {
+        let h = h.rem_euclid(360.0);
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = l - chroma / 2.0;
+        let (r, g, b) = match (h / 60.0) as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+        Self::rgb(channel(r), channel(g), channel(b))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn color_from_hex() {
+        assert_eq!(Color::from_hex("#1e1e2e"), Ok(Color::Rgb(0x1e, 0x1e, 0x2e)));
+        assert_eq!(Color::from_hex("#AA33BC"), Ok(Color::Rgb(0xaa, 0x33, 0xbc)));
+        assert_eq!(Color::from_hex("#f0a"), Ok(Color::Rgb(0xff, 0x00, 0xaa)));
+        assert_eq!(
+            Color::from_hex("1e1e2e"),
+            Err(ColorParseError::InvalidHex("1e1e2e".to_string()))
+        );
+        assert!(Color::from_hex("#1e1e2").is_err());
+        assert!(Color::from_hex("#gggggg").is_err());
+        assert!(Color::from_hex("#+1+1+1").is_err());
+        assert!(Color::from_hex("#").is_err());
+    }
+
+    #[test]
+    fn color_parse() {
+        assert_eq!(Color::parse("cyan"), Ok(Color::Cyan));
+        assert_eq!(Color::parse("LightRed"), Ok(Color::LightRed));
+        assert_eq!(Color::parse(" #ff0000 "), Ok(Color::Rgb(255, 0, 0)));
+        assert_eq!(Color::parse("#abc"), Ok(Color::Rgb(0xaa, 0xbb, 0xcc)));
+        assert_eq!(
+            Color::parse("#12345"),
+            Err(ColorParseError::InvalidHex("#12345".to_string()))
+        );
+        assert_eq!(
+            Color::parse("notacolor"),
+            Err(ColorParseError::UnknownColor("notacolor".to_string()))
+        );
+    }
+
+    #[test]
+    fn color_rgb() {
+        assert_eq!(Color::rgb(30, 30, 46), Color::Rgb(30, 30, 46));
+        // Round trip through hex
+        let hex = match Color::rgb(0x1e, 0xa0, 0xff) {
+            Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            _ => unreachable!(),
+        };
+        assert_eq!(hex.as_str(), "#1ea0ff");
+        assert_eq!(Color::from_hex(&hex), Ok(Color::rgb(0x1e, 0xa0, 0xff)));
+    }
+
+    #[test]
+    fn color_hsl() {
+        assert_eq!(Color::hsl(0.0, 1.0, 0.5), Color::Rgb(255, 0, 0));
+        assert_eq!(Color::hsl(120.0, 1.0, 0.5), Color::Rgb(0, 255, 0));
+        assert_eq!(Color::hsl(240.0, 1.0, 0.5), Color::Rgb(0, 0, 255));
+        assert_eq!(Color::hsl(360.0, 1.0, 0.5), Color::Rgb(255, 0, 0));
+        assert_eq!(Color::hsl(-120.0, 1.0, 0.5), Color::Rgb(0, 0, 255));
+        assert_eq!(Color::hsl(0.0, 0.0, 1.0), Color::Rgb(255, 255, 255));
+        assert_eq!(Color::hsl(0.0, 0.0, 0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(Color::hsl(210.0, 0.5, 0.4), Color::Rgb(51, 102, 153));
+        assert_eq!(Color::from_hex("#336699"), Ok(Color::hsl(210.0, 0.5, 0.4)));
+        // Out of range values are clamped
+        assert_eq!(Color::hsl(0.0, 2.0, 0.5), Color::Rgb(255, 0, 0));
+    }
+}
@@ -30,6 +30,22 @@ use crate::tui::{
     symbols::Marker,
     widgets::{Dataset as TuiDataset, GraphType},
 };
+use std::borrow::Cow;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use thiserror::Error;
+#[cfg(feature = "time")]
+use time::{format_description, OffsetDateTime};
+
+/// ## DatasetCsvError
+///
+/// Error returned when reading a dataset from csv
+#[derive(Debug, Error)]
+pub enum DatasetCsvError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("malformed row at line {line}: {reason}")]
+    MalformedRow { line: usize, reason: String },
+}
 
 /// ## Dataset
 ///
@@ -40,7 +56,14 @@ pub struct Dataset {
     pub marker: Marker,
     pub graph_type: GraphType,
     pub style: Style,
+    /// Unit of the x values (e.g. "s"), to be used in axis titles and legends; not rendered by the chart
+    pub x_unit: Option<String>,
+    /// Unit of the y values (e.g. "°C"), to be used in axis titles and legends; not rendered by the chart
+    pub y_unit: Option<String>,
     data: Vec<(f64, f64)>,
+    point_styles: Option<Vec<Style>>,
+    /// Whether data is known to be sorted by x; set by `sort_by_x` and cleared by any mutation
+    sorted: bool,
 }
 
 impl Default for Dataset {
@@ -50,7 +73,11 @@ impl Default for Dataset {
             marker: Marker::Dot,
             graph_type: GraphType::Scatter,
             style: Style::default(),
+            x_unit: None,
+            y_unit: None,
             data: Vec::default(),
+            point_styles: None,
+            sorted: false,
         }
     }
 }
@@ -88,19 +115,90 @@ impl Dataset {
         self
     }
 
+    /// ### x_unit
+    ///
+    /// Set the unit of the x values for dataset
+    pub fn x_unit<S: AsRef<str>>(mut self, s: S) -> Self {
+        self.x_unit = Some(s.as_ref().to_string());
+        self
+    }
+
+    /// ### y_unit
+    ///
+    /// Set the unit of the y values for dataset
+    pub fn y_unit<S: AsRef<str>>(mut self, s: S) -> Self {
+        self.y_unit = Some(s.as_ref().to_string());
+        self
+    }
+
     /// ### data
     ///
     /// Set data for dataset; must be a vec of (f64, f64)
     pub fn data(mut self, data: Vec<(f64, f64)>) -> Self {
         self.data = data;
+        self.point_styles = None;
+        self.sorted = false;
+        self
+    }
+
+    /// ### with_data_xy
+    ///
+    /// Set data for dataset from separate x and y values (see `data_xy`)
+    pub fn with_data_xy(mut self, xs: &[f64], ys: &[f64]) -> Self {
+        self.data_xy(xs, ys);
+        self
+    }
+
+    /// ### data_xy
+    ///
+    /// Replace data of dataset with the points made of `xs` and `ys` zipped together.
+    /// If their lengths differ, the longer slice is truncated to the length of the shorter one
+    pub fn data_xy(&mut self, xs: &[f64], ys: &[f64]) {
+        self.data.clear();
+        self.data.extend(xs.iter().copied().zip(ys.iter().copied()));
+        self.point_styles = None;
+        self.sorted = false;
+    }
+
+    /// ### styled_data
+    ///
+    /// Set data for dataset, giving each point its own style; must be a vec of (f64, f64, Style).
+    /// Use `segments` to render a dataset with per-point styles
+    pub fn styled_data(mut self, data: Vec<(f64, f64, Style)>) -> Self {
+        let (data, styles) = data
+            .into_iter()
+            .map(|(x, y, style)| ((x, y), style))
+            .unzip();
+        self.data = data;
+        self.point_styles = Some(styles);
+        self.sorted = false;
+        self
+    }
+
+    /// ### threshold_style
+    ///
+    /// Style each point with `above` if its y is greater or equal than `value`, with `below` otherwise.
+    /// Use `segments` to render the dataset
+    pub fn threshold_style(mut self, value: f64, above: Style, below: Style) -> Self {
+        self.point_styles = Some(
+            self.data
+                .iter()
+                .map(|(_, y)| if *y >= value { above } else { below })
+                .collect(),
+        );
         self
     }
 
     /// ### push
     ///
-    /// Push a record to the back of dataset
+    /// Push a record to the back of dataset.
+    /// If the dataset has per-point styles, the point gets the dataset style
     pub fn push(&mut self, point: (f64, f64)) {
         self.data.push(point);
+        if let Some(styles) = self.point_styles.as_mut() {
+            styles.push(self.style);
+        }
+        self.sorted = false;
     }
 
     /// ### pop
@@ -108,6 +206,9 @@ impl Dataset {
     /// Pop last element of dataset
     pub fn pop(&mut self) {
         self.data.pop();
+        if let Some(styles) = self.point_styles.as_mut() {
+            styles.pop();
+        }
     }
 
     /// ### pop
@@ -117,6 +218,10 @@ impl Dataset {
         if !self.data.is_empty() {
             self.data.remove(0);
         }
+        if let Some(styles) = self.point_styles.as_mut().filter(|x| !x.is_empty()) {
+            styles.remove(0);
+        }
+        self.sorted = false;
     }
 
     /// ### get_data
@@ -125,18 +230,294 @@ impl Dataset {
     pub fn get_data(&self) -> &[(f64, f64)] {
         &self.data
     }
+
+    /// ### get_data_mut
+    ///
+    /// Get a mutable reference to data.
+    /// Since the points may be changed in any way, the dataset is conservatively marked as unsorted
+    pub fn get_data_mut(&mut self) -> &mut [(f64, f64)] {
+        self.sorted = false;
+        &mut self.data
+    }
+
+    /// ### sort_by_x
+    ///
+    /// Sort the dataset points by x, keeping each point with its own style.
+    /// The sort is stable, so points with the same x keep their order
+    pub fn sort_by_x(&mut self) {
+        if self.sorted {
+            return;
+        }
+        match self.point_styles.take() {
+            Some(styles) => {
+                let mut points: Vec<((f64, f64), Style)> =
+                    self.data.drain(..).zip(styles).collect();
+                points.sort_by(|a, b| a.0 .0.total_cmp(&b.0 .0));
+                let (data, styles) = points.into_iter().unzip();
+                self.data = data;
+                self.point_styles = Some(styles);
+            }
+            None => self.data.sort_by(|a, b| a.0.total_cmp(&b.0)),
+        }
+        self.sorted = true;
+    }
+
+    /// ### is_sorted
+    ///
+    /// Returns whether the dataset is known to be sorted by x, which is the case after `sort_by_x`
+    /// until the data is changed
+    pub fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// ### x_bounds
+    ///
+    /// Get the min and the max x of the dataset, or `None` if it's empty.
+    /// If the dataset is sorted, the bounds are taken from the first and the last point without scanning the data
+    pub fn x_bounds(&self) -> Option<(f64, f64)> {
+        if self.sorted {
+            return Some((self.data.first()?.0, self.data.last()?.0));
+        }
+        let min = self.data.iter().map(|(x, _)| *x).reduce(f64::min)?;
+        let max = self.data.iter().map(|(x, _)| *x).reduce(f64::max)?;
+        Some((min, max))
+    }
+
+    /// ### moving_average
+    ///
+    /// Returns a new dataset where each y is the mean of the `window` y values centered on it; x values are preserved.
+    /// For even windows, the window extends one more point forward than backward.
+    /// At the edges of the dataset the window shrinks to the available points, so the first and last values
+    /// are averaged over fewer points. A `window` of 0 or 1 returns a copy of the dataset.
+    pub fn moving_average(&self, window: usize) -> Dataset {
+        let window = window.max(1);
+        let before = (window - 1) / 2;
+        let after = window / 2;
+        let data = (0..self.data.len())
+            .map(|i| {
+                let values =
+                    &self.data[i.saturating_sub(before)..(i + after + 1).min(self.data.len())];
+                let mean = values.iter().map(|(_, y)| y).sum::<f64>() / values.len() as f64;
+                (self.data[i].0, mean)
+            })
+            .collect();
+        Self {
+            data,
+            ..self.clone()
+        }
+    }
+
+    /// ### resample_linear
+    ///
+    /// Returns a new dataset with `n` points, evenly spaced between the min and the max x of the dataset,
+    /// whose y values are linearly interpolated from the dataset points (which don't need to be sorted by x).
+    /// If the dataset has less than 2 points, a copy of it is returned.
+    /// Per-point styles are not kept in the resampled dataset
+    pub fn resample_linear(&self, n: usize) -> Dataset {
+        if self.data.len() < 2 {
+            return self.clone();
+        }
+        let points = match self.sorted {
+            true => Cow::Borrowed(&self.data),
+            false => {
+                let mut points = self.data.clone();
+                points.sort_by(|a, b| a.0.total_cmp(&b.0));
+                Cow::Owned(points)
+            }
+        };
+        let min = points[0].0;
+        let max = points[points.len() - 1].0;
+        let step = match n {
+            0 | 1 => 0.0,
+            _ => (max - min) / (n - 1) as f64,
+        };
+        let mut segment = 0;
+        let data = (0..n)
+            .map(|i| {
+                // Ensure the last point is exactly at max
+                let x = match i + 1 == n && n > 1 {
+                    true => max,
+                    false => min + step * i as f64,
+                };
+                while segment + 2 < points.len() && points[segment + 1].0 < x {
+                    segment += 1;
+                }
+                let ((x0, y0), (x1, y1)) = (points[segment], points[segment + 1]);
+                let y = if x1 == x0 {
+                    y0
+                } else {
+                    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+                };
+                (x, y)
+            })
+            .collect();
+        Self {
+            data,
+            point_styles: None,
+            sorted: true,
+            ..self.clone()
+        }
+    }
+
+    /// ### to_csv
+    ///
+    /// Write the dataset data to `w` as csv, one `x,y` row for each point.
+    /// If the dataset has a name, it is written first as a `# name` comment
+    pub fn to_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        if !self.name.is_empty() {
+            writeln!(w, "# {}", self.name)?;
+        }
+        for (x, y) in self.data.iter() {
+            writeln!(w, "{},{}", x, y)?;
+        }
+        Ok(())
+    }
+
+    /// ### from_csv
+    ///
+    /// Read a dataset from csv `x,y` rows, as written by `to_csv`.
+    /// Empty lines are ignored; comment lines start with `#` and the first one, if it comes before any row,
+    /// is used as the dataset name. Any other style attribute is left to its default
+    pub fn from_csv<R: Read>(r: R) -> Result<Dataset, DatasetCsvError> {
+        let mut dataset = Dataset::default();
+        for (i, line) in BufReader::new(r).lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if dataset.data.is_empty() && dataset.name.is_empty() {
+                    dataset.name = comment.trim().to_string();
+                }
+                continue;
+            }
+            let malformed = |reason: String| DatasetCsvError::MalformedRow {
+                line: i + 1,
+                reason,
+            };
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| malformed(format!("expected `x,y`, found `{}`", line)))?;
+            let parse = |v: &str| {
+                v.trim()
+                    .parse::<f64>()
+                    .map_err(|_| malformed(format!("`{}` is not a number", v.trim())))
+            };
+            dataset.data.push((parse(x)?, parse(y)?));
+        }
+        Ok(dataset)
+    }
+
+    /// ### visually_eq
+    ///
+    /// Returns whether the datasets are equal and are rendered the same way: unlike `==`,
+    /// which only compares name and data, marker, graph type, style and per-point styles are compared too
+    pub fn visually_eq(&self, other: &Self) -> bool {
+        // tui doesn't implement `PartialEq` for marker and graph type, but they're fieldless enums
+        self == other
+            && std::mem::discriminant(&self.marker) == std::mem::discriminant(&other.marker)
+            && std::mem::discriminant(&self.graph_type) == std::mem::discriminant(&other.graph_type)
+            && self.style == other.style
+            && self.point_styles == other.point_styles
+    }
+
+    /// ### point_style
+    ///
+    /// Get the style of the point at `index`; falls back to the dataset style if the dataset has no per-point styles
+    pub fn point_style(&self, index: usize) -> Option<Style> {
+        match self.point_styles.as_ref() {
+            Some(styles) => styles.get(index).copied(),
+            None if index < self.data.len() => Some(self.style),
+            None => None,
+        }
+    }
+
+    /// ### segments
+    ///
+    /// Split the dataset into one `TuiDataset` for each run of contiguous points sharing the same style.
+    /// Since tui can't render a single dataset with several colors, this is how per-point styles are rendered:
+    /// just pass all the segments to the chart.
+    ///
+    /// With `GraphType::Line`, each segment also includes the first point of the following one, so that
+    /// the line is continuous; the line between two points takes the style of the first point.
+    /// Only the first segment has the dataset name, so the legend shows the dataset once.
+    pub fn segments(&self) -> Vec<TuiDataset<'_>> {
+        let styles = match self.point_styles.as_ref() {
+            Some(styles) => styles,
+            None => return vec![TuiDataset::from(self)],
+        };
+        let mut segments = Vec::new();
+        let mut start = 0;
+        while start < self.data.len() {
+            let style = styles.get(start).copied().unwrap_or(self.style);
+            let mut end = start + 1;
+            while end < self.data.len() && styles.get(end).copied().unwrap_or(self.style) == style {
+                end += 1;
+            }
+            let last = match self.graph_type {
+                GraphType::Line => (end + 1).min(self.data.len()),
+                GraphType::Scatter => end,
+            };
+            let segment = TuiDataset::default()
+                .marker(self.marker)
+                .graph_type(self.graph_type)
+                .style(style)
+                .data(&self.data[start..last]);
+            segments.push(match start {
+                0 => segment.name(self.name.as_str()),
+                _ => segment,
+            });
+            start = end;
+        }
+        segments
+    }
+
+    /// ### time_labels
+    ///
+    /// Get `count` labels for a time-based x axis: the labels are taken from `count` evenly spaced values
+    /// between the min and the max x of the dataset, interpreted as seconds since the unix epoch (UTC)
+    /// and formatted with `fmt`, which must be a `time` format description (e.g. `"[hour]:[minute]"`).
+    ///
+    /// Returns an empty vec if the dataset is empty or if `fmt` is not a valid format description
+    #[cfg(feature = "time")]
+    pub fn time_labels(&self, count: usize, fmt: &str) -> Vec<String> {
+        let fmt = match format_description::parse_borrowed::<1>(fmt) {
+            Ok(fmt) => fmt,
+            Err(_) => return Vec::new(),
+        };
+        let (min, max) = match self.x_bounds() {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+        let step = match count {
+            0 | 1 => 0.0,
+            _ => (max - min) / (count - 1) as f64,
+        };
+        (0..count)
+            .map(|i| min + step * i as f64)
+            .filter_map(|x| {
+                OffsetDateTime::from_unix_timestamp_nanos((x * 1_000_000_000.0) as i128).ok()
+            })
+            .filter_map(|t| t.format(&fmt).ok())
+            .collect()
+    }
 }
 
+/// Datasets are equal if they have the same name and data; the way they're rendered (marker, graph type and style)
+/// is not compared. Use `Dataset::visually_eq()` to compare them entirely (e.g. to decide whether to re-render).
 impl PartialEq for Dataset {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name && self.data == other.data
     }
 }
 
+/// The conversion is one-way: tui doesn't expose the fields of its `Dataset`, so it can't be converted back.
+/// Keep the tui-realm `Dataset` around and convert it each time the chart is rendered.
 impl<'a> From<&'a Dataset> for TuiDataset<'a> {
     fn from(data: &'a Dataset) -> TuiDataset<'a> {
         TuiDataset::default()
-            .name(data.name.clone())
+            .name(data.name.as_str())
             .marker(data.marker)
             .graph_type(data.graph_type)
             .style(data.style)
@@ -149,7 +530,13 @@ mod test {
 
     use super::*;
 
-    use crate::tui::style::Color;
+    use crate::tui::{
+        backend::TestBackend,
+        layout::Rect,
+        style::Color,
+        widgets::{Axis, Chart},
+        Terminal,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -175,6 +562,8 @@ mod test {
             ]);
         assert_eq!(dataset.name.as_str(), "Avg temperatures");
         assert_eq!(dataset.style.fg.unwrap_or(Color::Reset), Color::Cyan);
+        assert_eq!(dataset.x_unit, None);
+        assert_eq!(dataset.y_unit, None);
         assert_eq!(dataset.get_data().len(), 12);
         // mut
         dataset.push((12.0, 1.0));
@@ -186,4 +575,276 @@ mod test {
         // From
         let _: TuiDataset = TuiDataset::from(&dataset);
     }
+
+    #[test]
+    fn dataset_units() {
+        let dataset = Dataset::default()
+            .name("Avg temperatures")
+            .x_unit("month")
+            .y_unit("°C")
+            .data(vec![(0.0, -1.0), (1.0, 1.0)]);
+        assert_eq!(dataset.x_unit.as_deref(), Some("month"));
+        assert_eq!(dataset.y_unit.as_deref(), Some("°C"));
+        // Units are kept by transformations
+        assert_eq!(dataset.moving_average(2).y_unit.as_deref(), Some("°C"));
+        assert_eq!(dataset.resample_linear(3).x_unit.as_deref(), Some("month"));
+        // and ignored by the tui conversion
+        let _: TuiDataset = TuiDataset::from(&dataset);
+    }
+
+    #[test]
+    fn dataset_data_xy() {
+        let dataset = Dataset::default().with_data_xy(&[0.0, 1.0, 2.0], &[4.0, 5.0, 6.0]);
+        assert_eq!(dataset.get_data(), &[(0.0, 4.0), (1.0, 5.0), (2.0, 6.0)]);
+        // Mismatched lengths are truncated to the shorter one
+        let mut dataset = dataset.styled_data(vec![(0.0, 0.0, Style::default().fg(Color::Red))]);
+        dataset.data_xy(&[0.0, 1.0, 2.0], &[4.0]);
+        assert_eq!(dataset.get_data(), &[(0.0, 4.0)]);
+        // Per-point styles are cleared
+        assert_eq!(dataset.point_style(0), Some(Style::default()));
+        dataset.data_xy(&[3.0], &[7.0, 8.0]);
+        assert_eq!(dataset.get_data(), &[(3.0, 7.0)]);
+        dataset.data_xy(&[], &[7.0, 8.0]);
+        assert!(dataset.get_data().is_empty());
+    }
+
+    #[test]
+    fn dataset_visually_eq() {
+        let dataset = Dataset::default()
+            .name("Avg temperatures")
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(vec![(0.0, -1.0), (1.0, 1.0)]);
+        assert!(dataset.visually_eq(&dataset.clone()));
+        // Different style: equal, but not visually
+        let red = dataset.clone().style(Style::default().fg(Color::Red));
+        assert_eq!(dataset, red);
+        assert_eq!(dataset.visually_eq(&red), false);
+        let braille = dataset.clone().marker(Marker::Braille);
+        assert_eq!(dataset, braille);
+        assert_eq!(dataset.visually_eq(&braille), false);
+        let scatter = dataset.clone().graph_type(GraphType::Scatter);
+        assert_eq!(dataset.visually_eq(&scatter), false);
+        let styled = dataset.clone().threshold_style(
+            0.0,
+            Style::default().fg(Color::Red),
+            Style::default().fg(Color::Blue),
+        );
+        assert_eq!(dataset.visually_eq(&styled), false);
+        // Different data
+        let other = dataset.clone().data(vec![(0.0, 0.0)]);
+        assert_eq!(dataset.visually_eq(&other), false);
+    }
+
+    #[test]
+    fn dataset_moving_average() {
+        let dataset = Dataset::default().name("sensor").data(vec![
+            (0.0, 1.0),
+            (1.0, 5.0),
+            (2.0, 3.0),
+            (3.0, 7.0),
+            (4.0, 2.0),
+        ]);
+        let smooth = dataset.moving_average(3);
+        assert_eq!(smooth.name.as_str(), "sensor");
+        assert_eq!(
+            smooth.get_data(),
+            &[(0.0, 3.0), (1.0, 3.0), (2.0, 5.0), (3.0, 4.0), (4.0, 4.5)]
+        );
+        assert_eq!(
+            dataset.moving_average(2).get_data(),
+            &[(0.0, 3.0), (1.0, 4.0), (2.0, 5.0), (3.0, 4.5), (4.0, 2.0)]
+        );
+        assert_eq!(dataset.moving_average(1), dataset);
+        assert_eq!(dataset.moving_average(0), dataset);
+        assert!(Dataset::default().moving_average(3).get_data().is_empty());
+    }
+
+    #[test]
+    fn dataset_resample_linear() {
+        // y = 2x + 1
+        let dataset =
+            Dataset::default()
+                .name("line")
+                .data(vec![(4.0, 9.0), (0.0, 1.0), (2.0, 5.0)]);
+        let resampled = dataset.resample_linear(5);
+        assert_eq!(resampled.name.as_str(), "line");
+        assert_eq!(
+            resampled.get_data(),
+            &[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0), (4.0, 9.0)]
+        );
+        assert_eq!(
+            dataset.resample_linear(3).get_data(),
+            &[(0.0, 1.0), (2.0, 5.0), (4.0, 9.0)]
+        );
+        assert_eq!(dataset.resample_linear(1).get_data(), &[(0.0, 1.0)]);
+        assert!(dataset.resample_linear(0).get_data().is_empty());
+        // Not a straight line
+        let dataset = Dataset::default().data(vec![(0.0, 0.0), (1.0, 10.0), (3.0, 0.0)]);
+        assert_eq!(
+            dataset.resample_linear(4).get_data(),
+            &[(0.0, 0.0), (1.0, 10.0), (2.0, 5.0), (3.0, 0.0)]
+        );
+        // Less than 2 points
+        let dataset = Dataset::default().data(vec![(1.0, 1.0)]);
+        assert_eq!(dataset.resample_linear(5), dataset);
+    }
+
+    #[test]
+    fn dataset_sorted() {
+        let mut dataset = Dataset::default().styled_data(vec![
+            (2.0, 4.0, Style::default().fg(Color::Red)),
+            (0.0, 1.0, Style::default().fg(Color::Green)),
+            (1.0, 2.0, Style::default().fg(Color::Blue)),
+        ]);
+        assert_eq!(dataset.is_sorted(), false);
+        assert_eq!(dataset.x_bounds(), Some((0.0, 2.0)));
+        dataset.sort_by_x();
+        assert_eq!(dataset.is_sorted(), true);
+        assert_eq!(dataset.get_data(), &[(0.0, 1.0), (1.0, 2.0), (2.0, 4.0)]);
+        // Styles follow their points
+        assert_eq!(
+            dataset.point_style(0),
+            Some(Style::default().fg(Color::Green))
+        );
+        assert_eq!(
+            dataset.point_style(2),
+            Some(Style::default().fg(Color::Red))
+        );
+        assert_eq!(dataset.x_bounds(), Some((0.0, 2.0)));
+        // Resampled datasets are sorted
+        assert_eq!(dataset.resample_linear(3).is_sorted(), true);
+        // Mutators
+        dataset.push((3.0, 6.0));
+        assert_eq!(dataset.is_sorted(), false);
+        dataset.sort_by_x();
+        dataset.pop_front();
+        assert_eq!(dataset.is_sorted(), false);
+        dataset.sort_by_x();
+        dataset.get_data_mut()[0].0 = 10.0;
+        assert_eq!(dataset.is_sorted(), false);
+        assert_eq!(dataset.x_bounds(), Some((2.0, 10.0)));
+        dataset.sort_by_x();
+        let mut dataset = dataset.data(vec![(1.0, 1.0), (0.0, 0.0)]);
+        assert_eq!(dataset.is_sorted(), false);
+        dataset.sort_by_x();
+        dataset.data_xy(&[1.0, 0.0], &[1.0, 0.0]);
+        assert_eq!(dataset.is_sorted(), false);
+        assert_eq!(Dataset::default().x_bounds(), None);
+    }
+
+    #[test]
+    fn dataset_csv() {
+        let dataset = Dataset::default().name("Avg temperatures").data(vec![
+            (0.0, -1.5),
+            (1.0, 1.0),
+            (2.0, 3.25),
+        ]);
+        let mut csv = Vec::new();
+        dataset.to_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv.clone()).unwrap().as_str(),
+            "# Avg temperatures\n0,-1.5\n1,1\n2,3.25\n"
+        );
+        assert_eq!(Dataset::from_csv(csv.as_slice()).unwrap(), dataset);
+        // Without name
+        let dataset = dataset.name("");
+        let mut csv = Vec::new();
+        dataset.to_csv(&mut csv).unwrap();
+        assert_eq!(Dataset::from_csv(csv.as_slice()).unwrap(), dataset);
+        // Spaces, empty lines and comments
+        let dataset = Dataset::from_csv("# name\n\n 1 , 2 \n# comment\n3,4\n".as_bytes()).unwrap();
+        assert_eq!(dataset.name.as_str(), "name");
+        assert_eq!(dataset.get_data(), &[(1.0, 2.0), (3.0, 4.0)]);
+        // Malformed
+        let err = Dataset::from_csv("1,2\n3\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string().as_str(),
+            "malformed row at line 2: expected `x,y`, found `3`"
+        );
+        assert!(matches!(
+            Dataset::from_csv("# name\n1,2\n3,abc\n".as_bytes()),
+            Err(DatasetCsvError::MalformedRow { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn dataset_point_styles() {
+        let red = Style::default().fg(Color::Red);
+        let green = Style::default().fg(Color::Green);
+        let mut dataset = Dataset::default()
+            .style(green)
+            .styled_data(vec![(0.0, 1.0, red), (1.0, 2.0, green)]);
+        assert_eq!(dataset.get_data(), &[(0.0, 1.0), (1.0, 2.0)]);
+        assert_eq!(dataset.point_style(0), Some(red));
+        assert_eq!(dataset.point_style(1), Some(green));
+        assert_eq!(dataset.point_style(2), None);
+        dataset.push((2.0, 3.0));
+        assert_eq!(dataset.point_style(2), Some(green));
+        dataset.pop_front();
+        assert_eq!(dataset.point_style(0), Some(green));
+        dataset.pop();
+        assert_eq!(dataset.point_style(1), None);
+        // data resets point styles
+        let dataset = dataset.data(vec![(0.0, 0.0)]);
+        assert_eq!(dataset.point_style(0), Some(green));
+        assert_eq!(dataset.segments().len(), 1);
+    }
+
+    #[test]
+    fn dataset_threshold_segments() {
+        let above = Style::default().fg(Color::Red);
+        let below = Style::default().fg(Color::Green);
+        let data = vec![(0.0, 0.0), (1.0, 1.0), (2.0, 6.0), (3.0, 8.0), (4.0, 2.0)];
+        let dataset = Dataset::default()
+            .name("temperature")
+            .data(data.clone())
+            .threshold_style(5.0, above, below);
+        assert_eq!(dataset.segments().len(), 3);
+        let dataset = dataset.graph_type(GraphType::Line);
+        assert_eq!(dataset.segments().len(), 3);
+        // Render the crossing dataset as a scatter and check the points colors
+        let dataset = Dataset::default()
+            .marker(Marker::Block)
+            .data(data)
+            .threshold_style(5.0, above, below);
+        let mut terminal = Terminal::new(TestBackend::new(5, 9)).unwrap();
+        terminal
+            .draw(|f| {
+                let chart = Chart::new(dataset.segments())
+                    .x_axis(Axis::default().bounds([0.0, 4.0]))
+                    .y_axis(Axis::default().bounds([0.0, 8.0]));
+                f.render_widget(chart, Rect::new(0, 0, 5, 9));
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        // (x, y) => (column, row)
+        assert_eq!(buffer.get(0, 8).fg, Color::Green);
+        assert_eq!(buffer.get(1, 7).fg, Color::Green);
+        assert_eq!(buffer.get(2, 2).fg, Color::Red);
+        assert_eq!(buffer.get(3, 0).fg, Color::Red);
+        assert_eq!(buffer.get(4, 6).fg, Color::Green);
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn dataset_time_labels() {
+        // 12:00 - 12:10 UTC
+        let dataset = Dataset::default().data(vec![
+            (1_640_606_400.0, 1.0),
+            (1_640_606_700.0, 2.0),
+            (1_640_607_000.0, 3.0),
+        ]);
+        assert_eq!(
+            dataset.time_labels(3, "[hour]:[minute]"),
+            vec!["12:00", "12:05", "12:10"]
+        );
+        assert_eq!(dataset.time_labels(1, "[hour]:[minute]"), vec!["12:00"]);
+        assert!(dataset.time_labels(0, "[hour]:[minute]").is_empty());
+        assert!(dataset.time_labels(3, "[invalid").is_empty());
+        assert!(Dataset::default()
+            .time_labels(3, "[hour]:[minute]")
+            .is_empty());
+    }
 }
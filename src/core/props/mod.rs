@@ -29,6 +29,7 @@ use std::collections::HashMap;
 
 // -- modules
 mod borders;
+mod color;
 mod dataset;
 mod direction;
 mod input_type;
@@ -41,7 +42,8 @@ mod value;
 pub use crate::tui::layout::Alignment;
 pub use crate::tui::style::{Color, Modifier as TextModifiers, Style};
 pub use borders::{BorderSides, BorderType, Borders};
-pub use dataset::Dataset;
+pub use color::{ColorExt, ColorParseError};
+pub use dataset::{Dataset, DatasetCsvError};
 pub use direction::Direction;
 pub use input_type::InputType;
 pub use layout::Layout;
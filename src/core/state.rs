@@ -27,7 +27,12 @@
  */
 use crate::props::Color;
 use crate::utils::{Email, PhoneNumber};
+#[cfg(feature = "serialize")]
+use serde_json::{Map as JsonMap, Number, Value};
+use std::cmp::Ordering;
 use std::collections::{HashMap, LinkedList};
+#[cfg(feature = "serialize")]
+use thiserror::Error;
 
 /// ## State
 ///
@@ -46,9 +51,20 @@ pub enum State {
 
 /// ## StateValue
 ///
-/// StateValue describes the value contained in a State
+/// StateValue describes the value contained in a State.
+///
+/// `StateValue::None` describes a missing value (e.g. an optional field which is not set) and is different from
+/// `State::None`, which describes a component without state. It is compared as a plain value, so
+/// `StateValue::None == StateValue::None`.
+///
+/// Values are ordered (`PartialOrd`) numerically (or lexicographically for strings) within the same variant,
+/// while values of different variants are ordered by variant, in declaration order
+/// (e.g. `None < Bool(_) < U8(_) < ... < String(_)`), even if they are both numeric.
+/// `F64(NaN)` can't be compared with other `F64` values (`partial_cmp` returns `None`); use `total_cmp`
+/// to sort values containing NaN. `Ord` is not implemented, since `F64` values are not totally ordered.
 #[derive(Debug, PartialEq, Clone)]
 pub enum StateValue {
+    None,
     Bool(bool),
     U8(u8),
     U16(u16),
@@ -69,3 +85,777 @@ pub enum StateValue {
     Email(Email),
     PhoneNumber(PhoneNumber),
 }
+
+impl StateValue {
+    /// ### as_option
+    ///
+    /// Returns `None` if the value is `StateValue::None`, otherwise a reference to the value
+    pub fn as_option(&self) -> Option<&StateValue> {
+        match self {
+            StateValue::None => None,
+            value => Some(value),
+        }
+    }
+
+    /// ### into_option
+    ///
+    /// Converts `StateValue::None` into `None`, and any other value into `Some(value)`
+    pub fn into_option(self) -> Option<StateValue> {
+        match self {
+            StateValue::None => None,
+            value => Some(value),
+        }
+    }
+
+    /// ### is_none
+    ///
+    /// Returns whether the value is `StateValue::None`
+    pub fn is_none(&self) -> bool {
+        matches!(self, StateValue::None)
+    }
+
+    /// ### total_cmp
+    ///
+    /// Compare two values with a total order, which is the same as `partial_cmp`, but `F64` values are compared
+    /// with `f64::total_cmp` (so NaN is greater than any other positive value, and negative NaN is less than
+    /// any negative value). Use it to sort values, e.g. `values.sort_by(StateValue::total_cmp)`
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (StateValue::F64(a), StateValue::F64(b)) => a.total_cmp(b),
+            (a, b) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        }
+    }
+
+    /// ### variant_index
+    ///
+    /// Returns the index of the variant, in declaration order
+    fn variant_index(&self) -> u8 {
+        match self {
+            StateValue::None => 0,
+            StateValue::Bool(_) => 1,
+            StateValue::U8(_) => 2,
+            StateValue::U16(_) => 3,
+            StateValue::U32(_) => 4,
+            StateValue::U64(_) => 5,
+            StateValue::U128(_) => 6,
+            StateValue::Usize(_) => 7,
+            StateValue::I8(_) => 8,
+            StateValue::I16(_) => 9,
+            StateValue::I32(_) => 10,
+            StateValue::I64(_) => 11,
+            StateValue::I128(_) => 12,
+            StateValue::F64(_) => 13,
+            StateValue::Isize(_) => 14,
+            StateValue::String(_) => 15,
+            StateValue::Color(_) => 16,
+            StateValue::Email(_) => 17,
+            StateValue::PhoneNumber(_) => 18,
+        }
+    }
+}
+
+impl PartialOrd for StateValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (StateValue::None, StateValue::None) => Some(Ordering::Equal),
+            (StateValue::Bool(a), StateValue::Bool(b)) => a.partial_cmp(b),
+            (StateValue::U8(a), StateValue::U8(b)) => a.partial_cmp(b),
+            (StateValue::U16(a), StateValue::U16(b)) => a.partial_cmp(b),
+            (StateValue::U32(a), StateValue::U32(b)) => a.partial_cmp(b),
+            (StateValue::U64(a), StateValue::U64(b)) => a.partial_cmp(b),
+            (StateValue::U128(a), StateValue::U128(b)) => a.partial_cmp(b),
+            (StateValue::Usize(a), StateValue::Usize(b)) => a.partial_cmp(b),
+            (StateValue::I8(a), StateValue::I8(b)) => a.partial_cmp(b),
+            (StateValue::I16(a), StateValue::I16(b)) => a.partial_cmp(b),
+            (StateValue::I32(a), StateValue::I32(b)) => a.partial_cmp(b),
+            (StateValue::I64(a), StateValue::I64(b)) => a.partial_cmp(b),
+            (StateValue::I128(a), StateValue::I128(b)) => a.partial_cmp(b),
+            (StateValue::F64(a), StateValue::F64(b)) => a.partial_cmp(b),
+            (StateValue::Isize(a), StateValue::Isize(b)) => a.partial_cmp(b),
+            (StateValue::String(a), StateValue::String(b)) => a.partial_cmp(b),
+            (StateValue::Color(a), StateValue::Color(b)) => Some(color_key(a).cmp(&color_key(b))),
+            (StateValue::Email(a), StateValue::Email(b)) => a.partial_cmp(b),
+            (StateValue::PhoneNumber(a), StateValue::PhoneNumber(b)) => a.partial_cmp(b),
+            (a, b) => a.variant_index().partial_cmp(&b.variant_index()),
+        }
+    }
+}
+
+/// ### color_key
+///
+/// Returns a key to order colors: named colors in declaration order, then rgb and indexed colors by value
+fn color_key(color: &Color) -> (u8, u8, u8, u8) {
+    match color {
+        Color::Reset => (0, 0, 0, 0),
+        Color::Black => (1, 0, 0, 0),
+        Color::Red => (2, 0, 0, 0),
+        Color::Green => (3, 0, 0, 0),
+        Color::Yellow => (4, 0, 0, 0),
+        Color::Blue => (5, 0, 0, 0),
+        Color::Magenta => (6, 0, 0, 0),
+        Color::Cyan => (7, 0, 0, 0),
+        Color::Gray => (8, 0, 0, 0),
+        Color::DarkGray => (9, 0, 0, 0),
+        Color::LightRed => (10, 0, 0, 0),
+        Color::LightGreen => (11, 0, 0, 0),
+        Color::LightYellow => (12, 0, 0, 0),
+        Color::LightBlue => (13, 0, 0, 0),
+        Color::LightMagenta => (14, 0, 0, 0),
+        Color::LightCyan => (15, 0, 0, 0),
+        Color::White => (16, 0, 0, 0),
+        Color::Rgb(r, g, b) => (17, *r, *g, *b),
+        Color::Indexed(i) => (18, *i, 0, 0),
+    }
+}
+
+/// Implement `From` for the types wrapped by `StateValue`
+macro_rules! impl_from_for_state_value {
+    ($($t:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$t> for StateValue {
+                fn from(value: $t) -> Self {
+                    StateValue::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_state_value! {
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    usize => Usize,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    f64 => F64,
+    isize => Isize,
+    String => String,
+    Color => Color,
+    Email => Email,
+    PhoneNumber => PhoneNumber,
+}
+
+impl From<&str> for StateValue {
+    fn from(value: &str) -> Self {
+        StateValue::String(value.to_string())
+    }
+}
+
+impl<T> From<Option<T>> for StateValue
+where
+    T: Into<StateValue>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => StateValue::None,
+            Some(value) => value.into(),
+        }
+    }
+}
+
+impl State {
+    /// ### map_from
+    ///
+    /// Build a `State::Map` from an iterator of `(key, value)` pairs.
+    /// If a key is repeated, the last value is kept
+    pub fn map_from<I, K, V>(iter: I) -> State
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<StateValue>,
+    {
+        State::Map(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    /// ### len
+    ///
+    /// Returns the amount of values in the state:
+    ///
+    /// - `One`: 1
+    /// - `Tup2`, `Tup3`, `Tup4`: the arity of the tuple (2, 3, 4)
+    /// - `Vec` and `Map`: the amount of values in the collection
+    /// - `Linked`: the amount of states in the list (nested states are not traversed)
+    /// - `None`: 0
+    pub fn len(&self) -> usize {
+        match self {
+            State::One(_) => 1,
+            State::Tup2(_) => 2,
+            State::Tup3(_) => 3,
+            State::Tup4(_) => 4,
+            State::Vec(v) => v.len(),
+            State::Map(m) => m.len(),
+            State::Linked(l) => l.len(),
+            State::None => 0,
+        }
+    }
+
+    /// ### is_empty
+    ///
+    /// Returns whether the state has no values (see `len()`)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// ### flatten
+    ///
+    /// Returns a flat view of the state, as a list of `(key, value)`, useful to log, diff and assert on states.
+    /// Keys are built as follows:
+    ///
+    /// - `One`: a single entry with an empty key
+    /// - `Tup2`, `Tup3`, `Tup4` and `Vec`: the index of the value (`0`, `1`, ...)
+    /// - `Map`: the key of the value; entries are sorted by key, so the output is deterministic
+    /// - `Linked`: the index of the nested state, followed by a dot and the keys of the nested state
+    ///   (e.g. `1.name`, `2.0`); for a nested `One`, just the index
+    /// - `None`: no entry
+    pub fn flatten(&self) -> Vec<(String, StateValue)> {
+        let indexed = |values: Vec<&StateValue>| {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v.clone()))
+                .collect()
+        };
+        match self {
+            State::One(v) => vec![(String::new(), v.clone())],
+            State::Tup2((a, b)) => indexed(vec![a, b]),
+            State::Tup3((a, b, c)) => indexed(vec![a, b, c]),
+            State::Tup4((a, b, c, d)) => indexed(vec![a, b, c, d]),
+            State::Vec(v) => indexed(v.iter().collect()),
+            State::Map(m) => {
+                let mut entries: Vec<(String, StateValue)> =
+                    m.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                entries
+            }
+            State::Linked(l) => l
+                .iter()
+                .enumerate()
+                .flat_map(|(i, state)| {
+                    state
+                        .flatten()
+                        .into_iter()
+                        .map(move |(k, v)| match k.is_empty() {
+                            true => (i.to_string(), v),
+                            false => (format!("{}.{}", i, k), v),
+                        })
+                })
+                .collect(),
+            State::None => Vec::new(),
+        }
+    }
+}
+
+/// ## StateMapBuilder
+///
+/// A builder for `State::Map`, which allows to insert the fields of the map in a single chain
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StateMapBuilder {
+    map: HashMap<String, StateValue>,
+}
+
+impl StateMapBuilder {
+    /// ### insert
+    ///
+    /// Insert `value` with `key` into the map. If the key is already set, its value is replaced
+    pub fn insert<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<StateValue>,
+    {
+        self.map.insert(key.into(), value.into());
+        self
+    }
+
+    /// ### build
+    ///
+    /// Build the `State::Map`
+    pub fn build(self) -> State {
+        State::Map(self.map)
+    }
+}
+
+// -- json
+
+/// ## StateJsonError
+///
+/// Error returned when converting a json value into a `State`
+#[cfg(feature = "serialize")]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StateJsonError {
+    #[error("nested value at `{0}` can't be represented in a State")]
+    UnsupportedNesting(String),
+}
+
+#[cfg(feature = "serialize")]
+impl State {
+    /// ### from_json
+    ///
+    /// Convert a json value into a `State`:
+    ///
+    /// - `null` => `State::None`
+    /// - scalars => `State::One`
+    /// - arrays of scalars => `State::Vec`
+    /// - arrays containing arrays or objects => `State::Linked`, converting each element recursively
+    /// - objects => `State::Map`; the values of an object must be scalars, since `State::Map` can't hold
+    ///   nested states, otherwise `StateJsonError::UnsupportedNesting` is returned
+    ///
+    /// Scalars are converted to the matching `StateValue` (see `StateValue::from_json`)
+    pub fn from_json(value: Value) -> Result<State, StateJsonError> {
+        match value {
+            Value::Null => Ok(State::None),
+            Value::Array(values) if values.iter().all(is_json_scalar) => Ok(State::Vec(
+                values.into_iter().map(StateValue::from_json).collect(),
+            )),
+            Value::Array(values) => values
+                .into_iter()
+                .map(State::from_json)
+                .collect::<Result<LinkedList<State>, StateJsonError>>()
+                .map(State::Linked),
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(key, value)| match is_json_scalar(&value) {
+                    true => Ok((key, StateValue::from_json(value))),
+                    false => Err(StateJsonError::UnsupportedNesting(key)),
+                })
+                .collect::<Result<HashMap<String, StateValue>, StateJsonError>>()
+                .map(State::Map),
+            scalar => Ok(State::One(StateValue::from_json(scalar))),
+        }
+    }
+
+    /// ### to_json
+    ///
+    /// Convert the state into a json value: `State::None` is converted to `null`, `State::One` to a scalar,
+    /// tuples, `State::Vec` and `State::Linked` to arrays and `State::Map` to an object.
+    /// Values are converted with `StateValue::to_json`
+    pub fn to_json(&self) -> Value {
+        match self {
+            State::None => Value::Null,
+            State::One(x) => x.to_json(),
+            State::Tup2((a, b)) => Value::Array(vec![a.to_json(), b.to_json()]),
+            State::Tup3((a, b, c)) => Value::Array(vec![a.to_json(), b.to_json(), c.to_json()]),
+            State::Tup4((a, b, c, d)) => {
+                Value::Array(vec![a.to_json(), b.to_json(), c.to_json(), d.to_json()])
+            }
+            State::Vec(values) => Value::Array(values.iter().map(StateValue::to_json).collect()),
+            State::Map(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), v.to_json()))
+                    .collect::<JsonMap<String, Value>>(),
+            ),
+            State::Linked(states) => Value::Array(states.iter().map(State::to_json).collect()),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl StateValue {
+    /// ### from_json
+    ///
+    /// Convert a json scalar into a `StateValue`:
+    ///
+    /// - `null` => `StateValue::None`
+    /// - booleans => `StateValue::Bool`
+    /// - integers => the narrowest fitting integer variant: unsigned variants (`U8`..`U64`) for positive numbers,
+    ///   signed variants (`I8`..`I64`) for negative numbers
+    /// - other numbers => `StateValue::F64`
+    /// - strings => `StateValue::String`
+    ///
+    /// Arrays and objects are not scalars and are converted to their json representation as `StateValue::String`
+    pub fn from_json(value: Value) -> StateValue {
+        match value {
+            Value::Null => StateValue::None,
+            Value::Bool(x) => StateValue::Bool(x),
+            Value::Number(x) => Self::from_json_number(&x),
+            Value::String(x) => StateValue::String(x),
+            other => StateValue::String(other.to_string()),
+        }
+    }
+
+    /// ### from_json_number
+    ///
+    /// Convert a json number into the narrowest fitting `StateValue`
+    fn from_json_number(number: &Number) -> StateValue {
+        if let Some(x) = number.as_u64() {
+            if let Ok(x) = u8::try_from(x) {
+                StateValue::U8(x)
+            } else if let Ok(x) = u16::try_from(x) {
+                StateValue::U16(x)
+            } else if let Ok(x) = u32::try_from(x) {
+                StateValue::U32(x)
+            } else {
+                StateValue::U64(x)
+            }
+        } else if let Some(x) = number.as_i64() {
+            if let Ok(x) = i8::try_from(x) {
+                StateValue::I8(x)
+            } else if let Ok(x) = i16::try_from(x) {
+                StateValue::I16(x)
+            } else if let Ok(x) = i32::try_from(x) {
+                StateValue::I32(x)
+            } else {
+                StateValue::I64(x)
+            }
+        } else {
+            StateValue::F64(number.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+
+    /// ### to_json
+    ///
+    /// Convert the value into a json scalar. `StateValue::None` and non-finite floats are converted to `null`;
+    /// 128 bit integers are converted to numbers if they fit 64 bits, otherwise to strings;
+    /// colors, emails and phone numbers are converted to strings
+    pub fn to_json(&self) -> Value {
+        match self {
+            StateValue::None => Value::Null,
+            StateValue::Bool(x) => Value::from(*x),
+            StateValue::U8(x) => Value::from(*x),
+            StateValue::U16(x) => Value::from(*x),
+            StateValue::U32(x) => Value::from(*x),
+            StateValue::U64(x) => Value::from(*x),
+            StateValue::U128(x) => u64::try_from(*x)
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::from(x.to_string())),
+            StateValue::Usize(x) => Value::from(*x),
+            StateValue::I8(x) => Value::from(*x),
+            StateValue::I16(x) => Value::from(*x),
+            StateValue::I32(x) => Value::from(*x),
+            StateValue::I64(x) => Value::from(*x),
+            StateValue::I128(x) => i64::try_from(*x)
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::from(x.to_string())),
+            StateValue::F64(x) => Number::from_f64(*x)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            StateValue::Isize(x) => Value::from(*x),
+            StateValue::String(x) => Value::from(x.as_str()),
+            StateValue::Color(Color::Rgb(r, g, b)) => {
+                Value::from(format!("#{:02x}{:02x}{:02x}", r, g, b))
+            }
+            StateValue::Color(x) => Value::from(format!("{:?}", x)),
+            StateValue::Email(x) => Value::from(format!("{}@{}", x.name, x.agent)),
+            StateValue::PhoneNumber(x) => Value::from(x.phone_number()),
+        }
+    }
+}
+
+/// ### is_json_scalar
+///
+/// Returns whether the json value is neither an array nor an object
+#[cfg(feature = "serialize")]
+fn is_json_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_flatten_state() {
+        assert_eq!(
+            State::One(StateValue::U8(1)).flatten(),
+            vec![(String::new(), StateValue::U8(1))]
+        );
+        assert!(State::None.flatten().is_empty());
+        let mut linked = LinkedList::new();
+        linked.push_back(State::One(StateValue::Bool(true)));
+        linked.push_back(
+            StateMapBuilder::default()
+                .insert("name", "omar")
+                .insert("age", 32u8)
+                .build(),
+        );
+        linked.push_back(State::Tup2((StateValue::U8(1), StateValue::U8(2))));
+        linked.push_back(State::Vec(vec![StateValue::from("a")]));
+        linked.push_back(State::None);
+        let mut nested = LinkedList::new();
+        nested.push_back(State::One(StateValue::I32(-1)));
+        linked.push_back(State::Linked(nested));
+        assert_eq!(
+            State::Linked(linked).flatten(),
+            vec![
+                (String::from("0"), StateValue::Bool(true)),
+                (String::from("1.age"), StateValue::U8(32)),
+                (
+                    String::from("1.name"),
+                    StateValue::String(String::from("omar"))
+                ),
+                (String::from("2.0"), StateValue::U8(1)),
+                (String::from("2.1"), StateValue::U8(2)),
+                (String::from("3.0"), StateValue::String(String::from("a"))),
+                (String::from("5.0"), StateValue::I32(-1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_build_state_map() {
+        let mut expected = HashMap::new();
+        expected.insert(
+            String::from("name"),
+            StateValue::String(String::from("omar")),
+        );
+        expected.insert(String::from("age"), StateValue::U8(32));
+        expected.insert(String::from("subscribed"), StateValue::Bool(true));
+        expected.insert(String::from("email"), StateValue::None);
+        let expected = State::Map(expected);
+        assert_eq!(
+            State::map_from(vec![
+                (
+                    String::from("name"),
+                    StateValue::String(String::from("omar"))
+                ),
+                (String::from("age"), StateValue::U8(32)),
+                (String::from("subscribed"), StateValue::Bool(true)),
+                (String::from("email"), StateValue::None),
+            ]),
+            expected
+        );
+        assert_eq!(
+            StateMapBuilder::default()
+                .insert("name", "omar")
+                .insert("age", 32u8)
+                .insert("subscribed", true)
+                .insert("email", None::<u8>)
+                .build(),
+            expected
+        );
+        // Last value wins
+        assert_eq!(
+            StateMapBuilder::default()
+                .insert("age", 31u8)
+                .insert("age", 32u8)
+                .build(),
+            State::map_from([("age", 32u8)])
+        );
+        assert_eq!(
+            StateMapBuilder::default().build(),
+            State::Map(HashMap::new())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn state_from_json() {
+        use serde_json::json;
+        assert_eq!(State::from_json(json!(null)), Ok(State::None));
+        assert_eq!(
+            State::from_json(json!(true)),
+            Ok(State::One(StateValue::Bool(true)))
+        );
+        assert_eq!(
+            State::from_json(json!([
+                1,
+                300,
+                70000,
+                5_000_000_000u64,
+                -1,
+                -300,
+                0.5,
+                "a",
+                null
+            ])),
+            Ok(State::Vec(vec![
+                StateValue::U8(1),
+                StateValue::U16(300),
+                StateValue::U32(70000),
+                StateValue::U64(5_000_000_000),
+                StateValue::I8(-1),
+                StateValue::I16(-300),
+                StateValue::F64(0.5),
+                StateValue::String(String::from("a")),
+                StateValue::None,
+            ]))
+        );
+        let mut map = HashMap::new();
+        map.insert(
+            String::from("name"),
+            StateValue::String(String::from("omar")),
+        );
+        map.insert(String::from("age"), StateValue::U8(32));
+        assert_eq!(
+            State::from_json(json!({ "name": "omar", "age": 32 })),
+            Ok(State::Map(map.clone()))
+        );
+        // Nested arrays and objects
+        let mut linked = LinkedList::new();
+        linked.push_back(State::Map(map));
+        linked.push_back(State::Vec(vec![StateValue::U8(1), StateValue::U8(2)]));
+        linked.push_back(State::One(StateValue::Bool(false)));
+        assert_eq!(
+            State::from_json(json!([{ "name": "omar", "age": 32 }, [1, 2], false])),
+            Ok(State::Linked(linked))
+        );
+        assert_eq!(
+            State::from_json(json!({ "name": "omar", "address": { "city": "Udine" } })),
+            Err(StateJsonError::UnsupportedNesting(String::from("address")))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn state_to_json() {
+        use serde_json::json;
+        assert_eq!(State::None.to_json(), json!(null));
+        assert_eq!(
+            State::Tup2((StateValue::U32(1), StateValue::F64(f64::NAN))).to_json(),
+            json!([1, null])
+        );
+        assert_eq!(
+            State::Vec(vec![
+                StateValue::U128(u128::MAX),
+                StateValue::I128(-1),
+                StateValue::Color(Color::Rgb(255, 0, 128)),
+                StateValue::Color(Color::Red),
+                StateValue::Email(Email::new("foo", "bar.com")),
+            ])
+            .to_json(),
+            json!([
+                "340282366920938463463374607431768211455",
+                -1,
+                "#ff0080",
+                "Red",
+                "foo@bar.com"
+            ])
+        );
+        // Round trip
+        let json = json!([{ "name": "omar", "age": 32, "height": 1.75 }, [1, 2], null]);
+        assert_eq!(State::from_json(json.clone()).unwrap().to_json(), json);
+    }
+
+    #[test]
+    fn state_value_ordering() {
+        // Same variant
+        assert!(StateValue::U8(1) < StateValue::U8(2));
+        assert!(StateValue::I32(-5) < StateValue::I32(3));
+        assert!(StateValue::F64(-0.5) < StateValue::F64(0.25));
+        assert!(StateValue::Bool(false) < StateValue::Bool(true));
+        assert!(StateValue::String(String::from("a")) < StateValue::String(String::from("b")));
+        assert!(StateValue::String(String::from("10")) < StateValue::String(String::from("9")));
+        assert!(StateValue::Color(Color::Red) < StateValue::Color(Color::Rgb(0, 0, 0)));
+        assert!(StateValue::Color(Color::Rgb(0, 0, 1)) < StateValue::Color(Color::Rgb(0, 1, 0)));
+        assert_eq!(
+            StateValue::U64(7).partial_cmp(&StateValue::U64(7)),
+            Some(Ordering::Equal)
+        );
+        // Mixed numeric variants are ordered by variant
+        assert!(StateValue::U8(200) < StateValue::U16(1));
+        assert!(StateValue::U128(1) < StateValue::I8(-1));
+        assert!(StateValue::I64(i64::MAX) < StateValue::F64(f64::MIN));
+        assert!(StateValue::None < StateValue::Bool(false));
+        assert!(StateValue::F64(1.0) < StateValue::String(String::from("")));
+        // NaN
+        let nan = StateValue::F64(f64::NAN);
+        assert_eq!(nan.partial_cmp(&StateValue::F64(1.0)), None);
+        assert_eq!(nan.total_cmp(&StateValue::F64(1.0)), Ordering::Greater);
+        assert!(nan > StateValue::I8(0));
+        // Sort
+        let mut values = [
+            StateValue::F64(2.5),
+            StateValue::String(String::from("b")),
+            StateValue::F64(f64::NAN),
+            StateValue::U8(3),
+            StateValue::F64(-1.0),
+            StateValue::String(String::from("a")),
+            StateValue::U8(1),
+            StateValue::None,
+        ];
+        values.sort_by(StateValue::total_cmp);
+        assert_eq!(
+            &values[0..5],
+            &[
+                StateValue::None,
+                StateValue::U8(1),
+                StateValue::U8(3),
+                StateValue::F64(-1.0),
+                StateValue::F64(2.5),
+            ]
+        );
+        assert!(matches!(values[5], StateValue::F64(x) if x.is_nan()));
+        assert_eq!(
+            &values[6..],
+            &[
+                StateValue::String(String::from("a")),
+                StateValue::String(String::from("b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn state_len() {
+        assert_eq!(State::One(StateValue::Bool(true)).len(), 1);
+        assert_eq!(State::Tup2((StateValue::U8(1), StateValue::U8(2))).len(), 2);
+        assert_eq!(
+            State::Tup3((StateValue::U8(1), StateValue::U8(2), StateValue::U8(3))).len(),
+            3
+        );
+        assert_eq!(
+            State::Tup4((
+                StateValue::U8(1),
+                StateValue::U8(2),
+                StateValue::U8(3),
+                StateValue::U8(4)
+            ))
+            .len(),
+            4
+        );
+        assert_eq!(State::Vec(vec![StateValue::U8(1); 5]).len(), 5);
+        let mut map = HashMap::new();
+        map.insert(String::from("a"), StateValue::U8(1));
+        assert_eq!(State::Map(map).len(), 1);
+        let mut list = LinkedList::new();
+        list.push_back(State::Vec(vec![StateValue::U8(1); 3]));
+        list.push_back(State::None);
+        assert_eq!(State::Linked(list).len(), 2);
+        assert_eq!(State::None.len(), 0);
+    }
+
+    #[test]
+    fn state_value_from() {
+        assert_eq!(StateValue::from(true), StateValue::Bool(true));
+        assert_eq!(StateValue::from(8_u8), StateValue::U8(8));
+        assert_eq!(StateValue::from(-8_i64), StateValue::I64(-8));
+        assert_eq!(
+            StateValue::from("abc"),
+            StateValue::String(String::from("abc"))
+        );
+        assert_eq!(StateValue::from(Color::Red), StateValue::Color(Color::Red));
+        assert_eq!(StateValue::from(Some(3_usize)), StateValue::Usize(3));
+        assert_eq!(StateValue::from(None::<String>), StateValue::None);
+    }
+
+    #[test]
+    fn state_value_as_option() {
+        assert_eq!(StateValue::None.as_option(), None);
+        assert_eq!(StateValue::None.is_none(), true);
+        assert_eq!(StateValue::None.into_option(), None);
+        assert_eq!(StateValue::None, StateValue::None);
+        assert_eq!(StateValue::U8(1).as_option(), Some(&StateValue::U8(1)));
+        assert_eq!(StateValue::U8(1).is_none(), false);
+        assert_eq!(StateValue::U8(1).into_option(), Some(StateValue::U8(1)));
+    }
+
+    #[test]
+    fn state_is_empty() {
+        assert_eq!(State::None.is_empty(), true);
+        assert_eq!(State::Vec(vec![]).is_empty(), true);
+        assert_eq!(State::Map(HashMap::new()).is_empty(), true);
+        assert_eq!(State::One(StateValue::U8(0)).is_empty(), false);
+    }
+}
@@ -111,10 +111,12 @@ where
 
     /// ### forward
     ///
-    /// Returns whether to forward event to component
+    /// Returns whether to forward event to component.
+    /// `ticks` is the amount of ticks received by the application so far, including `ev`
     pub(crate) fn forward<HasAttrFn, GetStateFn, MountedFn>(
         &self,
         ev: &Event<U>,
+        ticks: u64,
         has_attr_fn: HasAttrFn,
         get_state_fn: GetStateFn,
         mounted_fn: MountedFn,
@@ -124,7 +126,9 @@ where
         GetStateFn: Fn(&K) -> Option<State>,
         MountedFn: Fn(&K) -> bool,
     {
-        self.ev.forward(ev) && self.when.forward(has_attr_fn, get_state_fn, mounted_fn)
+        self.ev.forward(ev)
+            && self.ev.tick_due(ticks)
+            && self.when.forward(has_attr_fn, get_state_fn, mounted_fn)
     }
 }
 
@@ -140,10 +144,33 @@ where
     Any,
     /// Check whether a certain key has been pressed
     Keyboard(KeyEvent),
+    /// Check whether a mouse event has been raised
+    Mouse,
+    /// Check whether a mouse gesture has been raised
+    MouseGesture,
+    /// Check whether text has been pasted
+    Paste,
     /// Check whether window has been resized
     WindowResize,
+    /// Check whether the window has been resized below the minimum size
+    TooSmall,
+    /// Check whether the window has been resized back to the minimum size
+    SizeOk,
+    /// Check whether the terminal has gained focus
+    FocusGained,
+    /// Check whether the terminal has lost focus
+    FocusLost,
+    /// Check whether the event listener has been paused
+    ListenerPaused,
+    /// Check whether the event listener has been resumed
+    ListenerResumed,
     /// The event will be forwarded on a tick
     Tick,
+    /// The event will be forwarded once every `n` ticks (e.g. with `TickEvery(4)`, on the 4th, 8th, 12th... tick).
+    /// Ticks are counted by the application since its initialization. `TickEvery(0)` never forwards
+    TickEvery(u64),
+    /// The event will be forwarded on any listener warning
+    ListenerWarning,
     /// Event will be forwarded on this specific user event.
     /// The way user event is matched, depends on its partialEq implementation
     User(UserEvent),
@@ -161,19 +188,54 @@ where
     ///
     /// - Any: Forward, no matter what kind of event
     /// - Keyboard: everything must match
+    /// - Mouse: matches only event type, not position or button
+    /// - MouseGesture: matches only event type, not the gesture
+    /// - Paste: matches only event type, not the text pasted
     /// - WindowResize: matches only event type, not sizes
+    /// - TooSmall: matches only event type, not sizes
+    /// - SizeOk: matches size ok event
+    /// - FocusGained, FocusLost: match focus gained and focus lost events
+    /// - ListenerPaused, ListenerResumed: match listener paused and resumed events
     /// - Tick: matches tick event
+    /// - TickEvery: matches tick event (whether the tick is due is checked by `tick_due`)
+    /// - ListenerWarning: matches any listener warning
     /// - None: matches None event
     /// - UserEvent: depends on UserEvent PartialEq
     fn forward(&self, ev: &Event<U>) -> bool {
         match self {
             EventClause::Any => true,
-            EventClause::Keyboard(k) => Some(k) == ev.is_keyboard(),
+            // Auto-repeats match the subscriptions of the key press
+            EventClause::Keyboard(k) => ev
+                .is_keyboard()
+                .map(|x| x.code == k.code && x.modifiers == k.modifiers)
+                .unwrap_or(false),
+            EventClause::Mouse => ev.is_mouse().is_some(),
+            EventClause::MouseGesture => ev.is_mouse_gesture().is_some(),
+            EventClause::Paste => ev.is_paste().is_some(),
             EventClause::WindowResize => ev.is_window_resize(),
-            EventClause::Tick => ev.is_tick(),
+            EventClause::TooSmall => ev.is_too_small(),
+            EventClause::SizeOk => ev.is_size_ok(),
+            EventClause::FocusGained => ev.is_focus_gained(),
+            EventClause::FocusLost => ev.is_focus_lost(),
+            EventClause::ListenerPaused => ev.is_listener_paused(),
+            EventClause::ListenerResumed => ev.is_listener_resumed(),
+            EventClause::Tick | EventClause::TickEvery(_) => ev.is_tick(),
+            EventClause::ListenerWarning => ev.is_listener_warning().is_some(),
             EventClause::User(u) => Some(u) == ev.is_user(),
         }
     }
+
+    /// ### tick_due
+    ///
+    /// Check whether the clause is satisfied for the `ticks`-th tick.
+    /// Always true for clauses which don't depend on the tick count
+    #[allow(clippy::manual_is_multiple_of)] // `u64::is_multiple_of` requires rust 1.87
+    fn tick_due(&self, ticks: u64) -> bool {
+        match self {
+            EventClause::TickEvery(n) => *n != 0 && ticks % *n == 0,
+            _ => true,
+        }
+    }
 }
 
 /// ## SubClause
@@ -343,11 +405,15 @@ where
 mod test {
 
     use super::*;
-    use crate::event::Key;
+    use crate::event::{
+        Key, KeyEventKind, KeyModifiers, ListenerWarning, MouseButton, MouseEvent, MouseEventKind,
+        MouseGesture,
+    };
     use crate::mock::{MockComponentId, MockEvent, MockFooInput};
     use crate::{command::Cmd, MockComponent, StateValue};
 
     use pretty_assertions::assert_eq;
+    use std::time::Duration;
 
     #[test]
     fn subscription_should_forward() {
@@ -378,6 +444,7 @@ mod test {
         assert_eq!(
             sub.forward(
                 &ev,
+                0,
                 |_: &MockComponentId, q| component.query(q),
                 |_: &MockComponentId| Some(component.state()),
                 |_: &MockComponentId| true
@@ -389,6 +456,7 @@ mod test {
         assert_eq!(
             sub.forward(
                 &ev,
+                0,
                 |_: &MockComponentId, q| component.query(q),
                 |_: &MockComponentId| Some(component.state()),
                 |_: &MockComponentId| true
@@ -399,6 +467,7 @@ mod test {
         assert_eq!(
             sub.forward(
                 &Event::User(MockEvent::Foo),
+                0,
                 |_: &MockComponentId, q| component.query(q),
                 |_: &MockComponentId| Some(component.state()),
                 |_: &MockComponentId| true
@@ -409,6 +478,7 @@ mod test {
         assert_eq!(
             sub.forward(
                 &Event::WindowResize(0, 0),
+                0,
                 |_: &MockComponentId, q| component.query(q),
                 |_: &MockComponentId| Some(component.state()),
                 |_: &MockComponentId| true
@@ -422,6 +492,17 @@ mod test {
         assert!(EventClause::<MockEvent>::Any.forward(&Event::Tick));
     }
 
+    #[test]
+    fn event_clause_keyboard_should_forward_repeats() {
+        let mut repeat = KeyEvent::from(Key::Enter);
+        repeat.kind = KeyEventKind::Repeat;
+        assert_eq!(
+            EventClause::<MockEvent>::Keyboard(KeyEvent::from(Key::Enter))
+                .forward(&Event::Keyboard(repeat)),
+            true
+        );
+    }
+
     #[test]
     fn event_clause_keyboard_should_forward() {
         assert_eq!(
@@ -440,6 +521,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn event_clause_mouse_should_forward() {
+        assert_eq!(
+            EventClause::<MockEvent>::Mouse.forward(&Event::Mouse(MouseEvent::new(
+                MouseEventKind::ScrollUp,
+                3,
+                5,
+                KeyModifiers::NONE
+            ))),
+            true
+        );
+        assert_eq!(EventClause::<MockEvent>::Mouse.forward(&Event::Tick), false);
+        assert_eq!(
+            EventClause::<MockEvent>::MouseGesture.forward(&Event::MouseGesture(
+                MouseGesture::Drag {
+                    start: (1, 1),
+                    current: (3, 2),
+                    button: MouseButton::Left,
+                }
+            )),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::MouseGesture.forward(&Event::Tick),
+            false
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::Paste.forward(&Event::Paste(String::from("hello"))),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::Paste.forward(&Event::Keyboard(KeyEvent::from(Key::Enter))),
+            false
+        );
+    }
+
     #[test]
     fn event_clause_window_resize_should_forward() {
         assert_eq!(
@@ -450,6 +567,46 @@ mod test {
             EventClause::<MockEvent>::WindowResize.forward(&Event::Tick),
             false
         );
+        assert_eq!(
+            EventClause::<MockEvent>::TooSmall.forward(&Event::TooSmall {
+                cols: 10,
+                rows: 10,
+                min: (80, 24)
+            }),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::TooSmall.forward(&Event::WindowResize(10, 10)),
+            false
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::SizeOk.forward(&Event::SizeOk),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::SizeOk.forward(&Event::Tick),
+            false
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::FocusGained.forward(&Event::FocusGained),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::FocusLost.forward(&Event::FocusGained),
+            false
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::ListenerPaused.forward(&Event::ListenerPaused),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::ListenerPaused.forward(&Event::ListenerResumed),
+            false
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::ListenerResumed.forward(&Event::ListenerResumed),
+            true
+        );
     }
 
     #[test]
@@ -461,6 +618,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn event_clause_tick_every_should_forward() {
+        let clause = EventClause::<MockEvent>::TickEvery(4);
+        assert_eq!(clause.forward(&Event::Tick), true);
+        assert_eq!(clause.forward(&Event::WindowResize(0, 0)), false);
+        assert_eq!(
+            (1..=12)
+                .filter(|x| clause.tick_due(*x))
+                .collect::<Vec<u64>>(),
+            vec![4, 8, 12]
+        );
+        assert_eq!(EventClause::<MockEvent>::TickEvery(0).tick_due(4), false);
+        assert_eq!(EventClause::<MockEvent>::Tick.tick_due(3), true);
+    }
+
+    #[test]
+    fn event_clause_listener_warning_should_forward() {
+        assert_eq!(
+            EventClause::<MockEvent>::ListenerWarning.forward(&Event::ListenerWarning(
+                ListenerWarning::PortStarved {
+                    port_id: 0,
+                    elapsed: Duration::from_secs(1)
+                }
+            )),
+            true
+        );
+        assert_eq!(
+            EventClause::<MockEvent>::ListenerWarning.forward(&Event::Tick),
+            false
+        );
+    }
+
     #[test]
     fn event_clause_user_should_forward() {
         assert_eq!(
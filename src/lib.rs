@@ -31,11 +31,16 @@
 //!
 //! Supported features are:
 //!
+//! - `async-tokio`: add `BroadcastPoll`, a poll which receives events from a tokio broadcast channel.
 //! - `derive` (*default*): add the `#[derive(MockComponent)]` proc macro to automatically implement `MockComponent` for `Component`. [Read more](https://github.com/veeso/tuirealm_derive).
 //! - `with-crossterm` (*default*): use [crossterm](https://github.com/crossterm-rs/crossterm) as backend for tui.
 //! - `with-termion` (*default*): use [termion](https://github.com/redox-os/termion) as backend for tui.
+//! - `time`: add `Dataset::time_labels()`, to format the x values of a time series as axis labels.
+//! - `test-util`: add `MockClock` and `EventListenerCfg::clock()`, to test the scheduling of polls and ticks without waiting for the wall clock.
+//! - `watch`: add `FileWatchPoll`, a poll which watches a file for changes from a dedicated thread.
 //!
-//! > ⚠️ You can enable only one backend at the time and at least one must be enabled in order to build.
+//! > ⚠️ At least one backend must be enabled in order to build. If both are enabled, crossterm is used as backend,
+//! > while the termion input listener and event conversions are still available from `adapter::termion`.
 //! > ❗ You don't need tui as a dependency, since you can access to tui types via `use tuirealm::tui::`
 //!
 //! ### Create a tui-realm application 🪂
@@ -105,14 +110,22 @@ pub mod terminal;
 pub mod tui;
 pub mod utils;
 // -- export
+pub use self::core::action::{self, Action, ActionMap};
 pub use self::core::application::{self, Application, ApplicationError, PollStrategy};
 pub use self::core::command;
 pub use self::core::event::{self, Event, NoUserEvent};
 pub use self::core::props::{self, AttrValue, Attribute, Props};
 pub use self::core::subscription::{EventClause as SubEventClause, Sub, SubClause};
-pub use self::core::{Component, MockComponent, State, StateValue, Update, ViewError};
+#[cfg(feature = "serialize")]
+pub use self::core::StateJsonError;
+pub use self::core::{
+    Component, MockComponent, State, StateMapBuilder, StateValue, Update, ViewError,
+};
 pub use adapter::{Frame, Terminal};
-pub use listener::{EventListenerCfg, ListenerError};
+pub use listener::{
+    EventListenerCfg, EventReceiver, Events, GrabResult, IterationTiming, ListenerError,
+    LocalEventListener, PauseGuard, PortId, PortKind, PortStatus, ScheduleHandle, TickPriority,
+};
 
 // -- derive
 #[cfg(feature = "derive")]
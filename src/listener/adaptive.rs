@@ -0,0 +1,156 @@
+//! ## Adaptive
+//!
+//! adaptive poll interval, which backs off when ports are idle
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::cmp::{max, min};
+use std::time::{Duration, Instant};
+
+/// Minimum backoff, used when the base interval is zero
+const MIN_BACKOFF: Duration = Duration::from_millis(1);
+
+/// ## AdaptivePoll
+///
+/// Keeps track of consecutive empty polls. Once `idle_threshold` polls in a row haven't returned any event,
+/// ports are polled less frequently: the delay between polls doubles after each empty poll, up to `max_interval`.
+/// As soon as an event is received, the normal interval is restored.
+#[derive(Debug, Clone)]
+pub(crate) struct AdaptivePoll {
+    idle_threshold: usize,
+    max_interval: Duration,
+    /// Consecutive empty polls
+    idle: usize,
+    /// Current backoff
+    backoff: Duration,
+    /// Ports mustn't be polled before this instant
+    idle_until: Option<Instant>,
+}
+
+impl AdaptivePoll {
+    pub fn new(idle_threshold: usize, max_interval: Duration) -> Self {
+        Self {
+            idle_threshold,
+            max_interval,
+            idle: 0,
+            backoff: Duration::ZERO,
+            idle_until: None,
+        }
+    }
+
+    /// ### fresh
+    ///
+    /// Returns a new `AdaptivePoll` with the same configuration, but not backing off
+    pub fn fresh(&self) -> Self {
+        Self::new(self.idle_threshold, self.max_interval)
+    }
+
+    /// ### idle_until
+    ///
+    /// Returns the instant before which ports shouldn't be polled, if backing off
+    pub fn idle_until(&self) -> Option<Instant> {
+        self.idle_until
+    }
+
+    /// ### is_backing_off
+    ///
+    /// Returns whether ports shouldn't be polled at `now`
+    pub fn is_backing_off(&self, now: Instant) -> bool {
+        self.idle_until.map(|x| now < x).unwrap_or(false)
+    }
+
+    /// ### reset
+    ///
+    /// Reset the idle state, restoring the configured poll intervals
+    pub fn reset(&mut self) {
+        self.idle = 0;
+        self.backoff = Duration::ZERO;
+        self.idle_until = None;
+    }
+
+    /// ### update
+    ///
+    /// Update the state after polling ports at `now`.
+    /// `events` tells whether any event has been returned, while `base` is the smallest port interval.
+    pub fn update(&mut self, events: bool, base: Duration, now: Instant) {
+        if events {
+            self.reset();
+            return;
+        }
+        self.idle = self.idle.saturating_add(1);
+        if self.idle >= self.idle_threshold {
+            self.backoff = match self.backoff.is_zero() {
+                true => max(base, MIN_BACKOFF),
+                false => self.backoff * 2,
+            };
+            self.backoff = min(self.backoff, self.max_interval);
+            self.idle_until = Some(now + self.backoff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_back_off_when_idle() {
+        let mut adaptive = AdaptivePoll::new(2, Duration::from_millis(100));
+        let base = Duration::from_millis(20);
+        let now = Instant::now();
+        adaptive.update(false, base, now);
+        assert_eq!(adaptive.idle_until(), None);
+        assert_eq!(adaptive.is_backing_off(now), false);
+        adaptive.update(false, base, now);
+        assert_eq!(adaptive.idle_until(), Some(now + Duration::from_millis(20)));
+        assert_eq!(adaptive.is_backing_off(now), true);
+        adaptive.update(false, base, now);
+        assert_eq!(adaptive.idle_until(), Some(now + Duration::from_millis(40)));
+        adaptive.update(false, base, now);
+        adaptive.update(false, base, now);
+        // Capped at max interval
+        assert_eq!(
+            adaptive.idle_until(),
+            Some(now + Duration::from_millis(100))
+        );
+        assert_eq!(
+            adaptive.is_backing_off(now + Duration::from_millis(100)),
+            false
+        );
+    }
+
+    #[test]
+    fn should_restore_interval_on_event() {
+        let mut adaptive = AdaptivePoll::new(1, Duration::from_secs(1));
+        let now = Instant::now();
+        adaptive.update(false, Duration::ZERO, now);
+        assert_eq!(adaptive.idle_until(), Some(now + MIN_BACKOFF));
+        adaptive.update(true, Duration::ZERO, now);
+        assert_eq!(adaptive.idle_until(), None);
+        assert_eq!(adaptive.is_backing_off(now), false);
+    }
+}
@@ -25,7 +25,17 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{Duration, EventListener, InputEventListener, Poll, Port};
+use super::{
+    AdaptivePoll, Dedup, DragTracker, DrainHook, Duration, Event, EventFilter, EventListener,
+    InputEventListener, KeyRepeat, ListenerError, ListenerResult, PasteBurst, Poll, Port,
+    PortFactory, PortKind, SizeQuery, TickHook, TickPriority, WorkerOptions,
+};
+#[cfg(feature = "with-crossterm")]
+use crate::adapter::crossterm::{capabilities, CaptureFlags, PasteMode};
+#[cfg(feature = "test-util")]
+use crate::listener::Clock;
+
+use std::sync::{Arc, Mutex};
 
 /// ## EventListenerCfg
 ///
@@ -39,8 +49,46 @@ where
     ports: Vec<Port<U>>,
     tick_interval: Option<Duration>,
     poll_timeout: Duration,
+    input: Arc<Mutex<InputOptions<U>>>,
+    options: WorkerOptions<U>,
 }
 
+/// ## InputOptions
+///
+/// Options of the input listeners added with `default_input_listener()` and `crossterm_input_listener()`.
+/// They're shared with the factories of the input ports and read when the input listener is built,
+/// so they apply whether they're set before or after the input listener is added.
+struct InputOptions<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    multi_click_threshold: Duration,
+    #[cfg(feature = "with-crossterm")]
+    scroll_window: Option<Duration>,
+    #[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+    passthrough_raw: bool,
+    event_mapper: Option<EventMapper<U>>,
+}
+
+impl<U> Default for InputOptions<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn default() -> Self {
+        Self {
+            multi_click_threshold: Duration::from_millis(300),
+            #[cfg(feature = "with-crossterm")]
+            scroll_window: None,
+            #[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+            passthrough_raw: false,
+            event_mapper: None,
+        }
+    }
+}
+
+/// Function applied by the input listener to the events it reads (see `EventListenerCfg::with_event_mapper()`)
+type EventMapper<U> = Arc<Mutex<dyn FnMut(Event<U>) -> Option<Event<U>> + Send>>;
+
 impl<U> Default for EventListenerCfg<U>
 where
     U: Eq + PartialEq + Clone + PartialOrd + Send,
@@ -50,6 +98,8 @@ where
             ports: Vec::default(),
             poll_timeout: Duration::from_millis(10),
             tick_interval: None,
+            input: Arc::new(Mutex::new(InputOptions::default())),
+            options: WorkerOptions::default(),
         }
     }
 }
@@ -61,14 +111,46 @@ where
     /// ### start
     ///
     /// Create the event listener with the parameters provided and start the workers
-    pub(crate) fn start(self) -> EventListener<U> {
-        EventListener::start(self.ports, self.poll_timeout, self.tick_interval)
+    pub(crate) fn start(mut self) -> EventListener<U> {
+        // Rebuild the input ports, in order to apply the input options set after they were added
+        self.ports
+            .iter_mut()
+            .filter(|x| x.kind() == PortKind::Input)
+            .for_each(|x| x.rebuild());
+        EventListener::start(
+            self.ports,
+            self.poll_timeout,
+            self.tick_interval,
+            self.options,
+        )
+    }
+
+    /// ### validate
+    ///
+    /// Check whether the configuration is consistent, returning an error otherwise:
+    ///
+    /// - the poll timeout must not be longer than the tick interval, otherwise the caller may block on `poll()`
+    ///   for longer than a tick and ticks can't be delivered on time (`ListenerError::PollTimeoutTooLong`)
+    ///
+    /// The configuration is validated by `Application::try_init()` and `Application::try_restart_listener()`,
+    /// while `Application::init()` and `Application::restart_listener()` accept any configuration.
+    pub fn validate(&self) -> ListenerResult<()> {
+        match self.tick_interval {
+            Some(tick_interval) if self.poll_timeout > tick_interval => {
+                Err(ListenerError::PollTimeoutTooLong {
+                    poll_timeout: self.poll_timeout,
+                    tick_interval,
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
     /// ### poll_timeout
     ///
     /// Set poll timeout.
     /// Poll timeout is the maximum time to wait when fetching the thread receiver.
+    /// It shouldn't be longer than the tick interval (see `validate()`); a few milliseconds are usually enough.
     ///
     /// > Panics if timeout is 0
     pub fn poll_timeout(mut self, timeout: Duration) -> Self {
@@ -85,11 +167,79 @@ where
     ///
     /// Defines the tick interval for the event listener.
     /// If an interval is defined, this will also enable the `Tick` event.
+    /// It shouldn't be shorter than the poll timeout (see `validate()`).
     pub fn tick_interval(mut self, interval: Duration) -> Self {
         self.tick_interval = Some(interval);
         self
     }
 
+    /// ### synth_key_repeat
+    ///
+    /// Synthesize key repeats: when the same key event is received again within `delay` (or within `interval`
+    /// once the key is repeating), it is reported with `KeyEventKind::Repeat`.
+    /// `delay` and `interval` should match the OS key repeat settings (usually about 500ms and 30ms).
+    ///
+    /// > This is an heuristic for terminals which don't report key repeats (e.g. without the kitty keyboard protocol):
+    /// > the terminal doesn't report key releases, so pressing the same key very quickly may be reported as a repeat too.
+    pub fn synth_key_repeat(mut self, delay: Duration, interval: Duration) -> Self {
+        self.options.key_repeat = Some(KeyRepeat::new(delay, interval));
+        self
+    }
+
+    /// ### paste_heuristic
+    ///
+    /// Synthesize `Event::Paste` for terminals which don't support bracketed paste: a burst of at least `min_len`
+    /// printable keys (characters, `Enter` and `Tab`), each received within `max_gap` from the previous one,
+    /// is reported as a single paste event carrying the text. A `max_gap` of a few milliseconds is usually enough,
+    /// since pasted text is received much faster than anyone can type.
+    ///
+    /// > This is a best-effort heuristic: very fast typing may be reported as a paste, and a paste may be split
+    /// > by a slow terminal. Moreover, printable keys are delayed by up to `max_gap`, until the burst is over.
+    pub fn paste_heuristic(mut self, max_gap: Duration, min_len: usize) -> Self {
+        self.options.paste_burst = Some(PasteBurst::new(max_gap, min_len));
+        self
+    }
+
+    /// ### paste_events
+    ///
+    /// Request `Event::Paste` for pasted text, falling back on the paste heuristic where bracketed paste
+    /// can't be used. The effective mode is reported by `TerminalCapabilities::paste_mode()` for crossterm;
+    /// termion doesn't report bracketed paste at all. When falling back, the paste heuristic is enabled with
+    /// a gap of 5ms and a minimum length of 3 keys, unless it has already been configured with `paste_heuristic()`.
+    pub fn paste_events(mut self) -> Self {
+        #[cfg(feature = "with-crossterm")]
+        let fallback = capabilities().paste_mode() == PasteMode::Heuristic;
+        #[cfg(not(feature = "with-crossterm"))]
+        let fallback = true;
+        if fallback && self.options.paste_burst.is_none() {
+            self.options.paste_burst = Some(PasteBurst::new(Duration::from_millis(5), 3));
+        }
+        self
+    }
+
+    /// ### normalize_keys
+    ///
+    /// If `normalize` is true, key events are normalized by the event listener (see `KeyEvent::normalized()`),
+    /// so that shifted characters are reported the same way on any terminal (e.g. `Char('a') + SHIFT` becomes
+    /// `Char('A')`) and subscriptions and keymaps can match them reliably. Default: false
+    pub fn normalize_keys(mut self, normalize: bool) -> Self {
+        self.options.normalize_keys = normalize;
+        self
+    }
+
+    /// ### mouse_gestures
+    ///
+    /// If `enabled` is true, mouse events are aggregated into `Event::MouseGesture`, which is raised after the
+    /// mouse event completing the gesture. For instance, on each `Drag` event of a `Down -> Drag* -> Up` sequence,
+    /// a `MouseGesture::Drag` is raised with the position where the drag has started. Default: false
+    pub fn mouse_gestures(mut self, enabled: bool) -> Self {
+        self.options.drag_tracker = match enabled {
+            true => Some(DragTracker::default()),
+            false => None,
+        };
+        self
+    }
+
     /// ### port
     ///
     /// Add a new Port (Poll, Interval) to the the event listener
@@ -98,11 +248,417 @@ where
         self
     }
 
+    /// ### port_factory
+    ///
+    /// Add a new Port to the event listener, whose poll is built by `factory`.
+    /// Unlike ports added with `port()`, these ports can be rebuilt from scratch and so are kept in
+    /// the configuration returned by `Application::listener_config_snapshot()`.
+    pub fn port_factory<F>(mut self, factory: F, interval: Duration) -> Self
+    where
+        F: Fn() -> Box<dyn Poll<U>> + Send + Sync + 'static,
+    {
+        self.ports.push(Port::from_factory(
+            Arc::new(factory) as PortFactory<U>,
+            interval,
+        ));
+        self
+    }
+
+    /// ### tick_priority
+    ///
+    /// Defines whether a tick due in the same worker iteration as the events polled from the ports
+    /// is sent before or after them. Default: `TickPriority::AfterInput`
+    pub fn tick_priority(mut self, priority: TickPriority) -> Self {
+        self.options.tick_priority = priority;
+        self
+    }
+
+    /// ### tick_initial_delay
+    ///
+    /// Delay the first `Event::Tick` by `delay` after the listener has started; then ticks are sent
+    /// every `tick_interval` as usual. Useful to prevent the first tick from racing the first render.
+    /// Default: no delay (the first tick is sent immediately)
+    pub fn tick_initial_delay(mut self, delay: Duration) -> Self {
+        self.options.tick_initial_delay = delay;
+        self
+    }
+
+    /// ### high_res_ticks
+    ///
+    /// If enabled, the worker sleeps only till shortly (2ms) before a tick is due, then spin-waits for it,
+    /// so that ticks are sent with sub-millisecond precision, regardless of the OS sleep granularity.
+    /// Useful for smooth animations at high frame rates, but it costs CPU: the worker keeps a core busy
+    /// for up to 2ms before each event, so with short tick intervals it's almost always busy. Default: disabled
+    pub fn high_res_ticks(mut self, enabled: bool) -> Self {
+        self.options.high_res_ticks = enabled;
+        self
+    }
+
+    /// ### grab_ticks
+    ///
+    /// If enabled, ticks are sent to the grab handler installed with `Application::grab_input()`, as any other event;
+    /// otherwise ticks bypass the grab and are always sent to the application. Default: disabled
+    pub fn grab_ticks(mut self, enabled: bool) -> Self {
+        self.options.grab_ticks = enabled;
+        self
+    }
+
+    /// ### on_tick
+    ///
+    /// Set a callback invoked by the worker each time a tick fires, before the tick is sent to the application
+    /// (even if the tick is then dropped by the event filter). Useful for cheap side effects, such as advancing
+    /// an animation clock.
+    ///
+    /// > The callback runs on the worker thread, like `Poll::poll()`, so it must not block:
+    /// > heavy work belongs in the update loop of the application
+    pub fn on_tick<F>(mut self, on_tick: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.options.on_tick = Some(Arc::new(Mutex::new(on_tick)) as TickHook);
+        self
+    }
+
+    /// ### on_drain
+    ///
+    /// Set a callback which is given the events still queued when the event listener is stopped (or dropped),
+    /// which would be discarded otherwise (e.g. for a recorder or an audit log). Events are given in the order
+    /// they would have been polled, errors are discarded. The callback runs on the thread which stops the listener,
+    /// once the worker has terminated.
+    pub fn on_drain<F>(mut self, on_drain: F) -> Self
+    where
+        F: FnMut(Event<U>) + Send + 'static,
+    {
+        self.options.on_drain = Some(Arc::new(Mutex::new(on_drain)) as DrainHook<U>);
+        self
+    }
+
+    /// ### min_size
+    ///
+    /// Set the minimum usable size of the terminal. When a `WindowResize` brings the terminal below
+    /// `cols` x `rows`, it is followed by `Event::TooSmall`; when another resize brings it back to the minimum size
+    /// (or above), it is followed by `Event::SizeOk`. Default: disabled
+    pub fn min_size(mut self, cols: u16, rows: u16) -> Self {
+        self.options.min_size = Some((cols, rows));
+        self
+    }
+
+    /// ### pause_events
+    ///
+    /// If `enabled`, `Event::ListenerPaused` and `Event::ListenerResumed` are raised each time the event listener
+    /// is paused or resumed, whoever has paused it (e.g. a `PauseGuard` on another thread). Default: disabled
+    pub fn pause_events(mut self, enabled: bool) -> Self {
+        self.options.pause_events = enabled;
+        self
+    }
+
+    /// ### pause_ticks_on_blur
+    ///
+    /// If `enabled`, no tick is raised between an `Event::FocusLost` and the next `Event::FocusGained`,
+    /// to save CPU while the terminal is not focused; ports keep being polled, so focus changes are still received.
+    /// This requires a port reporting focus changes, since the backends currently supported don't. Default: disabled
+    pub fn pause_ticks_on_blur(mut self, enabled: bool) -> Self {
+        self.options.pause_ticks_on_blur = enabled;
+        self
+    }
+
+    /// ### emit_initial_size
+    ///
+    /// If `enabled`, the event listener raises `Event::WindowResize` with the current size of the terminal
+    /// as soon as it starts, so that the layout can be driven by resize events only, from the first frame.
+    /// Nothing is raised if the size can't be read from the backend (e.g. stdout is not a terminal). Default: disabled
+    pub fn emit_initial_size(mut self, enabled: bool) -> Self {
+        self.options.initial_size = match enabled {
+            true => Some(Arc::new(crate::adapter::terminal_size) as SizeQuery),
+            false => None,
+        };
+        self
+    }
+
+    /// ### start_paused
+    ///
+    /// If `paused` is true, the event listener starts paused, as if `Application::lock_ports()` was called
+    pub fn start_paused(mut self, paused: bool) -> Self {
+        self.options.paused = paused;
+        self
+    }
+
+    /// ### multi_click_threshold
+    ///
+    /// Defines the max time between two mouse clicks on the same cell, to report them as a double (or triple) click
+    /// through `MouseEvent::click_count`. Default: 300ms
+    pub fn multi_click_threshold(self, threshold: Duration) -> Self {
+        self.set_input(|x| x.multi_click_threshold = threshold)
+    }
+
+    /// ### coalesce_scroll
+    ///
+    /// Enable scroll coalescing for the default input listener: scroll events in the same direction read
+    /// within `window` are reported as a single `MouseEvent`, whose `delta` holds the amount of scrolled lines
+    /// (negative for `ScrollUp`, positive for `ScrollDown`).
+    /// This is useful with high-resolution mice and trackpads, which emit many events per gesture.
+    /// By default coalescing is disabled, so each wheel tick is reported with a delta of 1;
+    /// a window of about 25ms is usually enough to merge a gesture.
+    /// Not available with termion, which reads from stdin in blocking mode.
+    #[cfg(feature = "with-crossterm")]
+    pub fn coalesce_scroll(self, window: Duration) -> Self {
+        self.set_input(|x| x.scroll_window = Some(window))
+    }
+
+    /// ### passthrough_raw
+    ///
+    /// If `passthrough` is true, the default input listener reports the terminal sequences it can't map to any event
+    /// as `Event::Raw`, carrying the bytes read, instead of dropping them. This is an escape hatch to handle sequences
+    /// which tui-realm doesn't model (e.g. OSC responses). Default: false, to avoid surprising event types.
+    /// Only available with termion: crossterm 0.20 maps every event it reads, so there is nothing to pass through.
+    #[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+    pub fn passthrough_raw(self, passthrough: bool) -> Self {
+        self.set_input(|x| x.passthrough_raw = passthrough)
+    }
+
+    /// ### clock
+    ///
+    /// Set the time source of the event listener worker, instead of the wall clock.
+    /// With a `MockClock`, polls and ticks are scheduled according to the time set by the test, so that the
+    /// scheduling of an application can be tested deterministically, without waiting for the wall clock.
+    #[cfg(feature = "test-util")]
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.options.clock = clock;
+        self
+    }
+
+    /// ### with_event_mapper
+    ///
+    /// Set a function which the default input listener applies to each event, after converting it from the backend
+    /// event: the event returned is reported instead, while if `None` is returned, the event is dropped.
+    /// This allows to remap keys or to drop specific inputs without patching the input listener.
+    /// Unlike the event filter (see `filter()`), which can only drop events, the mapper can rewrite them;
+    /// moreover it only applies to the events read from the terminal, not to the ones returned by other ports.
+    pub fn with_event_mapper<F>(self, mapper: F) -> Self
+    where
+        F: FnMut(Event<U>) -> Option<Event<U>> + Send + 'static,
+    {
+        let mapper: EventMapper<U> = Arc::new(Mutex::new(mapper));
+        self.set_input(|x| x.event_mapper = Some(mapper))
+    }
+
+    /// ### adaptive_poll
+    ///
+    /// Enable adaptive poll: once `idle_threshold` polls in a row haven't returned any event, ports are polled less
+    /// frequently, doubling the delay between polls up to `max_interval`.
+    /// As soon as any port returns an event, the configured poll intervals are restored; errors returned by ports
+    /// don't count as events. The backoff starts from the smallest interval among the ports which aren't paused.
+    /// Ticks are not affected by the backoff and are always sent on time.
+    /// This reduces the CPU usage of mostly-idle applications. Disabled by default.
+    pub fn adaptive_poll(mut self, idle_threshold: usize, max_interval: Duration) -> Self {
+        self.options.adaptive_poll = Some(AdaptivePoll::new(idle_threshold, max_interval));
+        self
+    }
+
+    /// ### idle_ignores_ticks
+    ///
+    /// Set whether ticks are ignored by the idle detection of the adaptive poll (see `adaptive_poll()`).
+    /// Since ticks are generated internally, by default only the events returned by ports reset the idle state,
+    /// so that the backoff is effective even in a ticking application (e.g. a clock).
+    /// Set to `false` to restore the configured poll intervals each time a tick fires.
+    pub fn idle_ignores_ticks(mut self, ignore: bool) -> Self {
+        self.options.idle_ignores_ticks = ignore;
+        self
+    }
+
+    /// ### dedup_window
+    ///
+    /// Drop the events returned by ports which are identical to one sent within `window`, even if other events
+    /// have been sent in between (e.g. duplicate resize reports). Ticks and errors are never dropped.
+    /// Only the most recent events are remembered (64), so the memory used is bounded. Default: disabled
+    pub fn dedup_window(mut self, window: Duration) -> Self {
+        self.options.dedup = Some(Dedup::new(window));
+        self
+    }
+
+    /// ### filter
+    ///
+    /// Set a global event filter, applied by the worker to every event (including ticks) before it is sent
+    /// to the application. Events for which `filter` returns `false` are dropped, regardless of the port
+    /// which produced them (e.g. drop all mouse events in a keyboard-only mode).
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Event<U>) -> bool + Send + Sync + 'static,
+    {
+        self.options.filter = Some(Arc::new(filter) as EventFilter<U>);
+        self
+    }
+
+    /// ### starvation_warnings
+    ///
+    /// Enable starvation warnings: when a port has been due for more than `threshold` times its interval
+    /// without being polled (e.g. because another port is blocking the worker), the event listener sends
+    /// `Event::ListenerWarning(ListenerWarning::PortStarved { .. })`, reporting the time elapsed since its last poll.
+    /// The warning is sent once, until the port is polled again. Disabled by default.
+    pub fn starvation_warnings(mut self, threshold: u32) -> Self {
+        self.options.starvation_threshold = Some(threshold);
+        self
+    }
+
     /// ### default_input_listener
     ///
     /// Add to the event listener the default input event listener for the backend configured.
     pub fn default_input_listener(self, interval: Duration) -> Self {
-        self.port(Box::new(InputEventListener::<U>::new(interval)), interval)
+        let factory = self.input_listener(interval);
+        self.port_factory(move || Box::new(factory()), interval)
+            .input_port()
+    }
+
+    /// ### default_app
+    ///
+    /// A preset for the most common setup, which is the same as:
+    ///
+    /// ```ignore
+    /// EventListenerCfg::default()
+    ///     .default_input_listener(poll_interval)
+    ///     .tick_interval(tick)
+    /// ```
+    ///
+    /// so the only port is the default input listener for the backend configured, polled every `poll_interval`,
+    /// and a `Tick` is raised every `tick`. Any other option keeps its default (e.g. the poll timeout is 10ms,
+    /// so `tick` should be longer than that) and can still be changed with the other builder methods,
+    /// including the options of the input listener, such as `multi_click_threshold()` or `with_event_mapper()`.
+    pub fn default_app(tick: Duration, poll_interval: Duration) -> Self {
+        Self::default()
+            .default_input_listener(poll_interval)
+            .tick_interval(tick)
+    }
+
+    /// ### crossterm_input_listener
+    ///
+    /// Add to the event listener the crossterm input listener, capturing the terminal features described by `flags`.
+    /// Capture is enabled once the listener starts polling and disabled when it's dropped, so you don't need to
+    /// setup the terminal on your own. Mouse events are reported only if `CaptureFlags::MOUSE` is set.
+    #[cfg(feature = "with-crossterm")]
+    pub fn crossterm_input_listener(self, interval: Duration, flags: CaptureFlags) -> Self {
+        let factory = self.input_listener(interval);
+        self.port_factory(move || Box::new(factory().capture(flags)), interval)
+            .input_port()
+    }
+
+    /// ### input_port
+    ///
+    /// Mark the last port added as an input port
+    fn input_port(mut self) -> Self {
+        if let Some(port) = self.ports.last_mut() {
+            port.set_kind(PortKind::Input);
+        }
+        self
+    }
+
+    /// ### set_input
+    ///
+    /// Update the input options
+    fn set_input<F>(self, update: F) -> Self
+    where
+        F: FnOnce(&mut InputOptions<U>),
+    {
+        if let Ok(mut input) = self.input.lock() {
+            update(&mut input);
+        }
+        self
+    }
+
+    /// ### input_listener
+    ///
+    /// Returns a function building the input listener for the backend configured,
+    /// applying the input options set in the configuration at the time the listener is built
+    fn input_listener(&self, interval: Duration) -> impl Fn() -> InputEventListener<U> {
+        let input = Arc::clone(&self.input);
+        move || {
+            let input = match input.lock() {
+                Ok(input) => input,
+                Err(err) => err.into_inner(),
+            };
+            let mut listener = InputEventListener::<U>::new(interval)
+                .multi_click_threshold(input.multi_click_threshold);
+            #[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+            {
+                listener = listener.passthrough_raw(input.passthrough_raw);
+            }
+            #[cfg(feature = "with-crossterm")]
+            if let Some(window) = input.scroll_window {
+                listener = listener.coalesce_scroll(window);
+            }
+            // The mapper is shared by the input listeners rebuilt from the factory
+            if let Some(mapper) = input.event_mapper.clone() {
+                listener = listener.event_mapper(move |ev| match mapper.lock() {
+                    Ok(mut mapper) => mapper(ev),
+                    Err(_) => Some(ev),
+                });
+            }
+            listener
+        }
+    }
+}
+
+/// ## ListenerSnapshot
+///
+/// Describes how to rebuild the configuration of a running event listener
+pub(crate) struct ListenerSnapshot<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    /// Factory and interval of each port; `None` if the port can't be rebuilt
+    pub ports: Vec<(Option<PortFactory<U>>, Duration, PortKind)>,
+    pub poll_timeout: Duration,
+    pub tick_interval: Option<Duration>,
+    pub options: WorkerOptions<U>,
+}
+
+impl<U> ListenerSnapshot<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    /// ### new
+    ///
+    /// Take a snapshot of the configuration of the listener, before starting it
+    pub fn new(
+        ports: &[Port<U>],
+        poll_timeout: Duration,
+        tick_interval: Option<Duration>,
+        options: &WorkerOptions<U>,
+    ) -> Self {
+        Self {
+            ports: ports
+                .iter()
+                .map(|x| (x.factory().cloned(), *x.interval(), x.kind()))
+                .collect(),
+            poll_timeout,
+            tick_interval,
+            options: options.fresh(),
+        }
+    }
+
+    /// ### config
+    ///
+    /// Build a configuration to start a listener equivalent to the snapshotted one.
+    /// Ports which haven't been built by a factory are skipped.
+    pub fn config(&self, paused: bool) -> EventListenerCfg<U> {
+        let mut options = self.options.fresh();
+        options.paused = paused;
+        EventListenerCfg {
+            ports: self
+                .ports
+                .iter()
+                .filter_map(|(factory, interval, kind)| {
+                    factory.as_ref().map(|factory| {
+                        let mut port = Port::from_factory(Arc::clone(factory), *interval);
+                        port.set_kind(*kind);
+                        port
+                    })
+                })
+                .collect(),
+            tick_interval: self.tick_interval,
+            poll_timeout: self.poll_timeout,
+            options,
+            ..EventListenerCfg::default()
+        }
     }
 }
 
@@ -114,6 +670,7 @@ mod test {
     use crate::mock::MockPoll;
 
     use pretty_assertions::assert_eq;
+    use std::time::Instant;
 
     #[test]
     fn should_configure_and_start_event_listener() {
@@ -121,6 +678,40 @@ mod test {
         assert!(builder.ports.is_empty());
         assert!(builder.tick_interval.is_none());
         assert_eq!(builder.poll_timeout, Duration::from_millis(10));
+        assert_eq!(
+            builder.input.lock().unwrap().multi_click_threshold,
+            Duration::from_millis(300)
+        );
+        let builder = builder.multi_click_threshold(Duration::from_millis(500));
+        assert_eq!(
+            builder.input.lock().unwrap().multi_click_threshold,
+            Duration::from_millis(500)
+        );
+        assert!(builder.options.key_repeat.is_none());
+        let builder =
+            builder.synth_key_repeat(Duration::from_millis(500), Duration::from_millis(30));
+        assert!(builder.options.key_repeat.is_some());
+        assert!(builder.options.adaptive_poll.is_none());
+        let builder = builder.adaptive_poll(10, Duration::from_secs(1));
+        assert!(builder.options.adaptive_poll.is_some());
+        assert_eq!(builder.options.idle_ignores_ticks, true);
+        let builder = builder.idle_ignores_ticks(false);
+        assert_eq!(builder.options.idle_ignores_ticks, false);
+        assert!(builder.options.filter.is_none());
+        let builder = builder.filter(|ev| ev.is_mouse().is_none());
+        assert!(builder.options.filter.is_some());
+        assert_eq!(builder.options.tick_priority, TickPriority::AfterInput);
+        let builder = builder.tick_priority(TickPriority::BeforeInput);
+        assert_eq!(builder.options.tick_priority, TickPriority::BeforeInput);
+        let builder = builder.starvation_warnings(5);
+        assert_eq!(builder.options.starvation_threshold, Some(5));
+        #[cfg(all(feature = "with-termion", not(feature = "with-crossterm")))]
+        let builder = {
+            assert_eq!(builder.input.lock().unwrap().passthrough_raw, false);
+            let builder = builder.passthrough_raw(true);
+            assert_eq!(builder.input.lock().unwrap().passthrough_raw, true);
+            builder
+        };
         let builder = builder.tick_interval(Duration::from_secs(10));
         assert_eq!(builder.tick_interval.unwrap(), Duration::from_secs(10));
         let builder = builder.poll_timeout(Duration::from_millis(50));
@@ -129,7 +720,326 @@ mod test {
             .default_input_listener(Duration::from_millis(200))
             .port(Box::new(MockPoll::default()), Duration::from_secs(300));
         assert_eq!(builder.ports.len(), 2);
+        #[cfg(feature = "with-crossterm")]
+        let builder =
+            builder.crossterm_input_listener(Duration::from_millis(200), CaptureFlags::MOUSE);
+        #[cfg(feature = "with-crossterm")]
+        assert_eq!(builder.ports.len(), 3);
+        let mut listener = builder.start();
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_snapshot_listener_config() {
+        let mut listener = EventListenerCfg::<MockEvent>::default()
+            .poll_timeout(Duration::from_millis(20))
+            .tick_interval(Duration::from_secs(1))
+            .starvation_warnings(5)
+            .port_factory(|| Box::new(MockPoll::default()), Duration::from_secs(60))
+            .port(Box::new(MockPoll::default()), Duration::from_secs(60))
+            .port_factory(|| Box::new(MockPoll::default()), Duration::from_secs(30))
+            .start();
+        assert!(listener
+            .set_port_interval(2, Duration::from_secs(10))
+            .is_ok());
+        assert!(listener.set_tick_interval(None).is_ok());
+        assert!(listener.pause().is_ok());
+        let config = listener.config_snapshot();
+        assert!(listener.stop().is_ok());
+        // Port without factory is skipped
+        assert_eq!(
+            config
+                .ports
+                .iter()
+                .map(|x| *x.interval())
+                .collect::<Vec<Duration>>(),
+            vec![Duration::from_secs(60), Duration::from_secs(10)]
+        );
+        assert!(config.ports.iter().all(|x| x.factory().is_some()));
+        assert_eq!(config.poll_timeout, Duration::from_millis(20));
+        assert!(config.tick_interval.is_none());
+        assert_eq!(config.options.starvation_threshold, Some(5));
+        assert_eq!(config.options.paused, true);
+        // Restart paused
+        let mut listener = config.start();
+        assert_eq!(*listener.paused.read().unwrap(), true);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_set_dedup_window() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.dedup.is_none());
+        let builder = builder.dedup_window(Duration::from_millis(100));
+        assert!(builder.options.dedup.is_some());
+    }
+
+    #[test]
+    fn should_set_grab_ticks() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert_eq!(builder.options.grab_ticks, false);
+        let builder = builder.grab_ticks(true);
+        assert_eq!(builder.options.grab_ticks, true);
+    }
+
+    #[test]
+    fn should_set_high_res_ticks() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert_eq!(builder.options.high_res_ticks, false);
+        let builder = builder.high_res_ticks(true);
+        assert_eq!(builder.options.high_res_ticks, true);
+    }
+
+    #[test]
+    fn should_delay_first_tick() {
+        let builder = EventListenerCfg::<MockEvent>::default()
+            .tick_interval(Duration::from_millis(10))
+            .tick_initial_delay(Duration::from_millis(200));
+        assert_eq!(
+            builder.options.tick_initial_delay,
+            Duration::from_millis(200)
+        );
+        let t_start = Instant::now();
+        let mut listener = builder.start();
+        let first_tick = loop {
+            if let Ok(Some(Event::Tick)) = listener.poll() {
+                break t_start.elapsed();
+            }
+            assert!(t_start.elapsed() < Duration::from_secs(5));
+        };
+        assert!(first_tick >= Duration::from_millis(200));
+        // Then ticks are sent every tick interval
+        let t_tick = Instant::now();
+        while !matches!(listener.poll(), Ok(Some(Event::Tick))) {}
+        assert!(t_tick.elapsed() < Duration::from_millis(150));
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_call_on_tick_hook() {
+        let hook_calls = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&hook_calls);
+        let mut listener = EventListenerCfg::<MockEvent>::default()
+            .tick_interval(Duration::from_millis(10))
+            .on_tick(move || *counter.lock().unwrap() += 1)
+            .start();
+        let mut ticks = 0;
+        while ticks < 5 {
+            if let Ok(Some(Event::Tick)) = listener.poll() {
+                ticks += 1;
+                // Hook is called before the tick is enqueued
+                assert!(*hook_calls.lock().unwrap() >= ticks);
+            }
+        }
+        assert!(listener.stop().is_ok());
+        // Every tick emitted has called the hook, and nothing else did
+        let mut emitted = ticks;
+        while let Ok(Some(_)) = listener.poll() {
+            emitted += 1;
+        }
+        assert_eq!(*hook_calls.lock().unwrap(), emitted);
+    }
+
+    #[test]
+    fn should_set_mouse_gestures() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.drag_tracker.is_none());
+        let builder = builder.mouse_gestures(true);
+        assert!(builder.options.drag_tracker.is_some());
+        let builder = builder.mouse_gestures(false);
+        assert!(builder.options.drag_tracker.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn should_set_clock() {
+        use crate::listener::MockClock;
+
+        let clock = MockClock::new();
+        let builder = EventListenerCfg::<MockEvent>::default().clock(Arc::new(clock.clone()));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(builder.options.clock.now(), clock.now());
+    }
+
+    #[test]
+    fn should_validate_config() {
+        assert!(EventListenerCfg::<MockEvent>::default().validate().is_ok());
+        assert!(EventListenerCfg::<MockEvent>::default()
+            .poll_timeout(Duration::from_millis(10))
+            .tick_interval(Duration::from_millis(50))
+            .validate()
+            .is_ok());
+        assert_eq!(
+            EventListenerCfg::<MockEvent>::default()
+                .poll_timeout(Duration::from_millis(100))
+                .tick_interval(Duration::from_millis(50))
+                .validate()
+                .err()
+                .unwrap(),
+            ListenerError::PollTimeoutTooLong {
+                poll_timeout: Duration::from_millis(100),
+                tick_interval: Duration::from_millis(50),
+            }
+        );
+        // Equal values are accepted
+        assert!(EventListenerCfg::<MockEvent>::default()
+            .poll_timeout(Duration::from_millis(50))
+            .tick_interval(Duration::from_millis(50))
+            .validate()
+            .is_ok());
+        assert!(EventListenerCfg::<MockEvent>::default()
+            .tick_interval(Duration::from_millis(10))
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn should_set_event_mapper() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.input.lock().unwrap().event_mapper.is_none());
+        let builder = builder
+            .with_event_mapper(|ev| match ev {
+                Event::Tick => None,
+                ev => Some(ev),
+            })
+            .default_input_listener(Duration::from_millis(10));
+        let input = builder.input.lock().unwrap();
+        let mapper = input.event_mapper.as_ref().unwrap();
+        assert_eq!((mapper.lock().unwrap())(Event::Tick), None);
+        assert_eq!(
+            (mapper.lock().unwrap())(Event::WindowResize(80, 24)),
+            Some(Event::WindowResize(80, 24))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "with-crossterm")]
+    fn should_set_coalesce_scroll() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.input.lock().unwrap().scroll_window.is_none());
+        let builder = builder
+            .default_input_listener(Duration::from_millis(10))
+            .coalesce_scroll(Duration::from_millis(25));
+        assert_eq!(
+            builder.input.lock().unwrap().scroll_window,
+            Some(Duration::from_millis(25))
+        );
+    }
+
+    #[test]
+    fn should_apply_input_options_set_after_input_listener() {
+        let builder = EventListenerCfg::<MockEvent>::default()
+            .default_input_listener(Duration::from_millis(10))
+            .multi_click_threshold(Duration::from_millis(500))
+            .with_event_mapper(Some);
+        assert_eq!(
+            builder.input.lock().unwrap().multi_click_threshold,
+            Duration::from_millis(500)
+        );
+        // The input listener is rebuilt on start, picking up the mapper set after it was added
+        let mapper = Arc::clone(builder.input.lock().unwrap().event_mapper.as_ref().unwrap());
+        assert_eq!(Arc::strong_count(&mapper), 2);
+        let mut listener = builder.start();
+        assert_eq!(Arc::strong_count(&mapper), 3);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_set_paste_heuristic() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.paste_burst.is_none());
+        let builder = builder.paste_heuristic(Duration::from_millis(5), 3);
+        assert!(builder.options.paste_burst.is_some());
+        let builder = builder.paste_events();
+        assert!(builder.options.paste_burst.is_some());
+        // Falls back on the heuristic
+        let builder = EventListenerCfg::<MockEvent>::default().paste_events();
+        assert!(builder.options.paste_burst.is_some());
+    }
+
+    #[test]
+    fn should_set_normalize_keys() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert_eq!(builder.options.normalize_keys, false);
+        let builder = builder.normalize_keys(true);
+        assert_eq!(builder.options.normalize_keys, true);
+    }
+
+    #[test]
+    fn should_set_min_size() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.min_size.is_none());
+        let builder = builder.min_size(80, 24);
+        assert_eq!(builder.options.min_size, Some((80, 24)));
+    }
+
+    #[test]
+    fn should_raise_pause_events() {
+        let mut listener = EventListenerCfg::<MockEvent>::default()
+            .pause_events(true)
+            .start();
+        let next_event = |listener: &EventListener<MockEvent>| {
+            let deadline = Instant::now() + Duration::from_secs(1);
+            while Instant::now() < deadline {
+                if let Some(ev) = listener.poll().ok().unwrap() {
+                    return Some(ev);
+                }
+            }
+            None
+        };
+        assert!(listener.pause().is_ok());
+        assert_eq!(next_event(&listener), Some(Event::ListenerPaused));
+        assert!(listener.unpause().is_ok());
+        assert_eq!(next_event(&listener), Some(Event::ListenerResumed));
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_build_default_app() {
+        let builder = EventListenerCfg::<MockEvent>::default_app(
+            Duration::from_secs(1),
+            Duration::from_millis(20),
+        );
+        assert_eq!(builder.tick_interval, Some(Duration::from_secs(1)));
+        assert_eq!(builder.poll_timeout, Duration::from_millis(10));
+        assert_eq!(builder.ports.len(), 1);
+        assert_eq!(builder.ports[0].interval(), &Duration::from_millis(20));
+        assert_eq!(builder.ports[0].kind(), PortKind::Input);
+        assert!(builder.validate().is_ok());
+        // Input options can still be set
+        let builder = builder.with_event_mapper(Some);
+        let mapper = Arc::clone(builder.input.lock().unwrap().event_mapper.as_ref().unwrap());
+        let mut listener = builder.start();
+        assert_eq!(Arc::strong_count(&mapper), 3);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_emit_initial_size() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.initial_size.is_none());
+        let builder = builder.emit_initial_size(true);
+        assert!(builder.options.initial_size.is_some());
+        let builder = builder.emit_initial_size(false);
+        assert!(builder.options.initial_size.is_none());
+    }
+
+    #[test]
+    fn should_set_on_drain() {
+        let builder = EventListenerCfg::<MockEvent>::default();
+        assert!(builder.options.on_drain.is_none());
+        let builder = builder.on_drain(|_| {});
+        assert!(builder.options.on_drain.is_some());
+        // Kept by snapshots
+        assert!(builder.start().config_snapshot().options.on_drain.is_some());
+    }
+
+    #[test]
+    fn should_start_paused() {
+        let builder = EventListenerCfg::<MockEvent>::default().start_paused(true);
+        assert_eq!(builder.options.paused, true);
         let mut listener = builder.start();
+        assert_eq!(*listener.paused.read().unwrap(), true);
         assert!(listener.stop().is_ok());
     }
 
@@ -0,0 +1,148 @@
+//! ## Clock
+//!
+//! the time source of the event listener worker
+
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::{Arc, Mutex};
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::time::{Duration, Instant};
+
+/// ## Clock
+///
+/// The time source used by the event listener worker to schedule polls and ticks.
+/// The worker uses the `SystemClock`, unless another clock is provided (see `EventListenerCfg::clock()`).
+pub trait Clock: Send + Sync {
+    /// ### now
+    ///
+    /// Returns the current time
+    fn now(&self) -> Instant;
+
+    /// ### wait_timeout
+    ///
+    /// Returns for how long the worker should actually block, when it has nothing to do for `timeout`
+    /// according to this clock. By default, it's `timeout`.
+    fn wait_timeout(&self, timeout: Duration) -> Duration {
+        timeout
+    }
+}
+
+/// ## SystemClock
+///
+/// The wall clock, based on `Instant::now()`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// ## MockClock
+///
+/// A clock which only moves forward when told to, with `advance()`.
+/// It allows to test the scheduling of polls and ticks deterministically, without waiting for the wall clock.
+/// Clones share the same time, so a clone can be kept to drive the clock given to the event listener.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    /// ### new
+    ///
+    /// Instantiates a new `MockClock`, which starts at the current time
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// ### advance
+    ///
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += duration;
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now
+            .lock()
+            .map(|x| *x)
+            .unwrap_or_else(|x| *x.into_inner())
+    }
+
+    /// Since the clock may be advanced at any time, the worker only blocks for a short while
+    fn wait_timeout(&self, timeout: Duration) -> Duration {
+        std::cmp::min(timeout, Duration::from_millis(1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_advance_mock_clock() {
+        let clock = MockClock::new();
+        let t = clock.now();
+        assert_eq!(clock.now(), t);
+        // Clones share the time
+        let driver = clock.clone();
+        driver.advance(Duration::from_secs(3));
+        assert_eq!(clock.now(), t + Duration::from_secs(3));
+        assert_eq!(
+            clock.wait_timeout(Duration::from_secs(60)),
+            Duration::from_millis(1)
+        );
+    }
+
+    #[test]
+    fn should_use_system_clock() {
+        let t = Instant::now();
+        assert!(SystemClock.now() >= t);
+        assert_eq!(
+            SystemClock.wait_timeout(Duration::from_secs(60)),
+            Duration::from_secs(60)
+        );
+    }
+}
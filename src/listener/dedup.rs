@@ -0,0 +1,140 @@
+//! ## Dedup
+//!
+//! Suppression of the events identical to one sent shortly before
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::Event;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Max amount of events remembered by `Dedup`
+const DEDUP_CAPACITY: usize = 64;
+
+/// ## Dedup
+///
+/// Drops the events identical to one sent within `window`, even if other events have been sent in between
+/// (e.g. duplicate resize reports, or bouncy hardware).
+/// Only the last `DEDUP_CAPACITY` sent events are remembered, so that memory is bounded even under high event rates.
+pub(crate) struct Dedup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    window: Duration,
+    /// Events sent within the window, with the time they've been sent, oldest first
+    recent: VecDeque<(Instant, Event<U>)>,
+}
+
+impl<U> Dedup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// ### fresh
+    ///
+    /// Returns a new `Dedup` with the same window, without any remembered event
+    pub fn fresh(&self) -> Self {
+        Self::new(self.window)
+    }
+
+    /// ### process_at
+    ///
+    /// Returns whether `ev`, about to be sent at `now`, must be sent; if so, it is remembered.
+    /// Duplicates which are dropped are not remembered, so the window starts from the event actually sent
+    pub fn process_at(&mut self, ev: &Event<U>, now: Instant) -> bool {
+        while let Some((sent, _)) = self.recent.front() {
+            match now.duration_since(*sent) >= self.window {
+                true => self.recent.pop_front(),
+                false => break,
+            };
+        }
+        if self.recent.iter().any(|(_, x)| x == ev) {
+            return false;
+        }
+        if self.recent.len() == DEDUP_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((now, ev.clone()));
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_drop_duplicates_within_window() {
+        let mut dedup = Dedup::<MockEvent>::new(Duration::from_millis(100));
+        let t = Instant::now();
+        let foo = Event::User(MockEvent::Foo);
+        let bar = Event::User(MockEvent::Bar);
+        assert_eq!(dedup.process_at(&foo, t), true);
+        assert_eq!(dedup.process_at(&bar, t + Duration::from_millis(10)), true);
+        // Not adjacent, but within the window
+        assert_eq!(dedup.process_at(&foo, t + Duration::from_millis(50)), false);
+        // Dropped duplicates don't extend the window
+        assert_eq!(dedup.process_at(&foo, t + Duration::from_millis(100)), true);
+        assert_eq!(
+            dedup.process_at(&bar, t + Duration::from_millis(100)),
+            false
+        );
+        assert_eq!(dedup.process_at(&bar, t + Duration::from_millis(110)), true);
+        assert_eq!(
+            dedup.process_at(&foo, t + Duration::from_millis(150)),
+            false
+        );
+        assert_eq!(dedup.fresh().process_at(&foo, t), true);
+    }
+
+    #[test]
+    fn should_bound_remembered_events() {
+        let mut dedup = Dedup::<MockEvent>::new(Duration::from_secs(60));
+        let t = Instant::now();
+        for i in 0..100 {
+            assert!(dedup.process_at(&Event::User(MockEvent::Hello(i.to_string())), t));
+        }
+        assert_eq!(dedup.recent.len(), DEDUP_CAPACITY);
+        // The oldest events have been forgotten
+        assert_eq!(
+            dedup.process_at(&Event::User(MockEvent::Hello(String::from("0"))), t),
+            true
+        );
+        assert_eq!(
+            dedup.process_at(&Event::User(MockEvent::Hello(String::from("99"))), t),
+            false
+        );
+    }
+}
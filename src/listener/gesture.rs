@@ -0,0 +1,171 @@
+//! ## Gesture
+//!
+//! aggregates mouse events into gestures
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::event::{MouseButton, MouseEventKind, MouseGesture};
+use crate::Event;
+
+/// ## DragTracker
+///
+/// Aggregates a `Down -> Drag* -> Up` sequence of mouse events on the same button into `MouseGesture::Drag`,
+/// which carries the position where the drag has started, besides the current one.
+/// The drag is reset when the button is released or when a different button is pressed.
+#[derive(Default)]
+pub(crate) struct DragTracker {
+    /// Button and position of the current drag
+    start: Option<(MouseButton, (u16, u16))>,
+}
+
+impl DragTracker {
+    /// ### fresh
+    ///
+    /// Returns a new `DragTracker`, without any drag in progress
+    pub fn fresh(&self) -> Self {
+        Self::default()
+    }
+
+    /// ### process
+    ///
+    /// Process the event, returning the gesture it causes, if any
+    pub fn process<U>(&mut self, ev: &Event<U>) -> Option<Event<U>>
+    where
+        U: Eq + PartialEq + Clone + PartialOrd,
+    {
+        let ev = ev.is_mouse()?;
+        let position = (ev.column, ev.row);
+        match ev.kind {
+            MouseEventKind::Down(button) => {
+                self.start = Some((button, position));
+                None
+            }
+            MouseEventKind::Up(_) => {
+                self.start = None;
+                None
+            }
+            MouseEventKind::Drag(button) => {
+                // Some terminals don't report the press: the drag starts at the first drag event then
+                let start = match self.start {
+                    Some((pressed, start)) if pressed == button => start,
+                    _ => {
+                        self.start = Some((button, position));
+                        position
+                    }
+                };
+                Some(Event::MouseGesture(MouseGesture::Drag {
+                    start,
+                    current: position,
+                    button,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::event::{KeyModifiers, MouseEvent};
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event<MockEvent> {
+        Event::Mouse(MouseEvent::new(kind, column, row, KeyModifiers::NONE))
+    }
+
+    fn drag(
+        start: (u16, u16),
+        current: (u16, u16),
+        button: MouseButton,
+    ) -> Option<Event<MockEvent>> {
+        Some(Event::MouseGesture(MouseGesture::Drag {
+            start,
+            current,
+            button,
+        }))
+    }
+
+    #[test]
+    fn should_aggregate_drag_sequence() {
+        let mut tracker = DragTracker::default();
+        let left = MouseButton::Left;
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Down(left), 10, 5)),
+            None
+        );
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Drag(left), 11, 5)),
+            drag((10, 5), (11, 5), left)
+        );
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Drag(left), 14, 7)),
+            drag((10, 5), (14, 7), left)
+        );
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Up(left), 14, 7)),
+            None
+        );
+        // Not mouse events
+        assert_eq!(tracker.process(&Event::<MockEvent>::Tick), None);
+        assert_eq!(tracker.process(&mouse(MouseEventKind::Moved, 1, 1)), None);
+    }
+
+    #[test]
+    fn should_reset_drag() {
+        let mut tracker = DragTracker::default();
+        let (left, right) = (MouseButton::Left, MouseButton::Right);
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Down(left), 10, 5)),
+            None
+        );
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Up(left), 10, 5)),
+            None
+        );
+        // Drag without press starts at the first drag event
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Drag(left), 20, 2)),
+            drag((20, 2), (20, 2), left)
+        );
+        // Different button pressed
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Down(right), 3, 3)),
+            None
+        );
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Drag(right), 4, 3)),
+            drag((3, 3), (4, 3), right)
+        );
+        // Drag with a different button restarts the drag
+        assert_eq!(
+            tracker.process(&mouse(MouseEventKind::Drag(left), 6, 6)),
+            drag((6, 6), (6, 6), left)
+        );
+        assert_eq!(tracker.fresh().start, None);
+    }
+}
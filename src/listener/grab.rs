@@ -0,0 +1,51 @@
+//! ## Grab
+//!
+//! Input grabbing: a handler which intercepts the events before they're sent by the event listener
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::Event;
+
+use std::sync::{Arc, Mutex};
+
+/// ## GrabHandler
+///
+/// The handler installed with `Application::grab_input()`
+pub(super) type GrabHandler<U> = Box<dyn FnMut(Event<U>) -> GrabResult + Send>;
+
+/// ## SharedGrab
+///
+/// The grab handler, shared by the event listener, which installs it, and the worker, which calls it
+pub(super) type SharedGrab<U> = Arc<Mutex<Option<GrabHandler<U>>>>;
+
+/// ## GrabResult
+///
+/// Tells what to do with an event received by a grab handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabResult {
+    /// The event has been handled by the grab handler and it's not sent to the application
+    Consume,
+    /// The event is sent to the application, as if there was no grab
+    Pass,
+}
@@ -0,0 +1,178 @@
+//! ## Group
+//!
+//! port group, which polls many sources with a shared interval
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{Event, ListenerResult, Poll};
+
+/// ## PortGroup
+///
+/// A port group holds many `Poll` sources which share the same interval.
+/// It implements `Poll` itself, so it is registered as a single port in the event listener,
+/// which saves the scheduling overhead of registering a port for each source:
+///
+/// ```rust,ignore
+/// EventListenerCfg::default().port(Box::new(PortGroup::default().member(a).member(b)), Duration::from_millis(100))
+/// ```
+///
+/// Each `poll()` call polls the members in turn, until one of them returns an event or an error.
+/// Members are polled in round-robin: the next call starts from the member after the one which returned,
+/// so that a busy member can't starve the others.
+/// An error returned by a member is returned by the group, as any other port error.
+pub struct PortGroup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    polls: Vec<Box<dyn Poll<U>>>,
+    /// Index of the member to poll first on the next call
+    cursor: usize,
+}
+
+impl<U> Default for PortGroup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn default() -> Self {
+        Self {
+            polls: Vec::default(),
+            cursor: 0,
+        }
+    }
+}
+
+impl<U> PortGroup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    /// ### member
+    ///
+    /// Add a new member to the group
+    pub fn member(mut self, poll: Box<dyn Poll<U>>) -> Self {
+        self.polls.push(poll);
+        self
+    }
+
+    /// ### len
+    ///
+    /// Returns the amount of members in the group
+    pub fn len(&self) -> usize {
+        self.polls.len()
+    }
+
+    /// ### is_empty
+    ///
+    /// Returns whether the group has no members
+    pub fn is_empty(&self) -> bool {
+        self.polls.is_empty()
+    }
+}
+
+impl<U> Poll<U> for PortGroup<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        let len = self.polls.len();
+        for i in (0..len).map(|x| (self.cursor + x) % len) {
+            match self.polls[i].poll() {
+                Ok(None) => continue,
+                res => {
+                    self.cursor = (i + 1) % len;
+                    return res;
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::listener::ListenerError;
+    use crate::mock::{MockEvent, MockPoll};
+
+    use pretty_assertions::assert_eq;
+
+    struct NamedPoll(&'static str);
+
+    impl Poll<MockEvent> for NamedPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            Ok(Some(Event::User(MockEvent::Hello(self.0.to_string()))))
+        }
+    }
+
+    struct EmptyPoll;
+
+    impl Poll<MockEvent> for EmptyPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            Ok(None)
+        }
+    }
+
+    struct FailingPoll;
+
+    impl Poll<MockEvent> for FailingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            Err(ListenerError::PollFailed(String::from("failed")))
+        }
+    }
+
+    fn hello(name: &str) -> Option<Event<MockEvent>> {
+        Some(Event::User(MockEvent::Hello(name.to_string())))
+    }
+
+    #[test]
+    fn should_poll_members_in_round_robin() {
+        let mut group = PortGroup::default()
+            .member(Box::new(NamedPoll("a")))
+            .member(Box::new(EmptyPoll))
+            .member(Box::new(NamedPoll("b")));
+        assert_eq!(group.len(), 3);
+        assert_eq!(group.poll().ok().unwrap(), hello("a"));
+        assert_eq!(group.poll().ok().unwrap(), hello("b"));
+        assert_eq!(group.poll().ok().unwrap(), hello("a"));
+    }
+
+    #[test]
+    fn should_return_member_errors() {
+        let mut group = PortGroup::default()
+            .member(Box::new(FailingPoll))
+            .member(Box::new(MockPoll::<MockEvent>::default()));
+        assert!(group.poll().is_err());
+        assert!(group.poll().ok().unwrap().is_some());
+        assert!(group.poll().is_err());
+    }
+
+    #[test]
+    fn should_poll_empty_group() {
+        let mut group = PortGroup::<MockEvent>::default();
+        assert!(group.is_empty());
+        assert!(group.poll().ok().unwrap().is_none());
+        let mut group = PortGroup::default().member(Box::new(EmptyPoll));
+        assert!(group.poll().ok().unwrap().is_none());
+    }
+}
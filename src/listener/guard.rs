@@ -0,0 +1,159 @@
+//! ## Guard
+//!
+//! pause guard for the event listener
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::ListenerCmd;
+
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+
+/// ## PauseGuard
+///
+/// A guard which keeps the event listener paused as long as it is alive.
+/// The listener is paused when the guard is created and, once it is dropped, restored to the state it had
+/// before (i.e. it stays paused if it was paused explicitly with `pause()`).
+/// Guards can be nested: the state is restored only once the last guard has been dropped.
+///
+/// > Calling `unlock_ports()` while a guard is alive unpauses the listener anyway
+#[must_use = "the listener is unpaused as soon as the guard is dropped"]
+pub struct PauseGuard {
+    paused: Arc<RwLock<bool>>,
+    guards: Arc<Mutex<PauseGuards>>,
+    /// Command sender to the worker, to wake it up when the pause state changes
+    cmd: mpsc::Sender<ListenerCmd>,
+}
+
+/// ## PauseGuards
+///
+/// The guards alive for a listener
+#[derive(Debug, Default)]
+pub(super) struct PauseGuards {
+    /// Amount of alive guards
+    count: usize,
+    /// Whether the listener was paused when the first guard was created
+    was_paused: bool,
+}
+
+impl PauseGuard {
+    /// ### new
+    ///
+    /// Pause the listener and instantiate a new `PauseGuard`
+    pub(super) fn new(
+        paused: Arc<RwLock<bool>>,
+        guards: Arc<Mutex<PauseGuards>>,
+        cmd: mpsc::Sender<ListenerCmd>,
+    ) -> Self {
+        let guard = Self {
+            paused,
+            guards,
+            cmd,
+        };
+        if let Ok(mut guards) = guard.guards.lock() {
+            if guards.count == 0 {
+                guards.was_paused = guard.paused.read().map(|x| *x).unwrap_or(false);
+            }
+            guards.count += 1;
+            guard.set_paused(true);
+        }
+        guard
+    }
+
+    /// ### set_paused
+    ///
+    /// Set the paused state of the listener and wake up the worker, so that it notices the new state right away
+    fn set_paused(&self, value: bool) {
+        if let Ok(mut paused) = self.paused.write() {
+            *paused = value;
+        }
+        // If the worker has terminated, there's nothing to wake up
+        let _ = self.cmd.send(ListenerCmd::Wake);
+    }
+}
+
+impl Drop for PauseGuard {
+    fn drop(&mut self) {
+        if let Ok(mut guards) = self.guards.lock() {
+            guards.count = guards.count.saturating_sub(1);
+            if guards.count == 0 {
+                self.set_paused(guards.was_paused);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_pause_while_guard_is_alive() {
+        let paused = Arc::new(RwLock::new(false));
+        let guards = Arc::new(Mutex::new(PauseGuards::default()));
+        let (cmd, _cmd_rx) = mpsc::channel();
+        {
+            let _guard = PauseGuard::new(Arc::clone(&paused), Arc::clone(&guards), cmd.clone());
+            assert_eq!(*paused.read().unwrap(), true);
+        }
+        assert_eq!(*paused.read().unwrap(), false);
+        assert_eq!(guards.lock().unwrap().count, 0);
+    }
+
+    #[test]
+    fn should_unpause_when_last_guard_is_dropped() {
+        let paused = Arc::new(RwLock::new(false));
+        let guards = Arc::new(Mutex::new(PauseGuards::default()));
+        let (cmd, _cmd_rx) = mpsc::channel();
+        let outer = PauseGuard::new(Arc::clone(&paused), Arc::clone(&guards), cmd.clone());
+        let inner = PauseGuard::new(Arc::clone(&paused), Arc::clone(&guards), cmd.clone());
+        assert_eq!(guards.lock().unwrap().count, 2);
+        drop(inner);
+        assert_eq!(*paused.read().unwrap(), true);
+        drop(outer);
+        assert_eq!(*paused.read().unwrap(), false);
+    }
+
+    #[test]
+    fn should_keep_explicit_pause_when_guard_is_dropped() {
+        let paused = Arc::new(RwLock::new(true));
+        let guards = Arc::new(Mutex::new(PauseGuards::default()));
+        let (cmd, _cmd_rx) = mpsc::channel();
+        {
+            let _guard = PauseGuard::new(Arc::clone(&paused), Arc::clone(&guards), cmd.clone());
+            assert_eq!(*paused.read().unwrap(), true);
+        }
+        assert_eq!(*paused.read().unwrap(), true);
+        // Guards created later restore the state at the time
+        *paused.write().unwrap() = false;
+        drop(PauseGuard::new(
+            Arc::clone(&paused),
+            Arc::clone(&guards),
+            cmd.clone(),
+        ));
+        assert_eq!(*paused.read().unwrap(), false);
+    }
+}
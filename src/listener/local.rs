@@ -0,0 +1,375 @@
+//! ## Local
+//!
+//! A single-thread event listener, for user events which are not `Send`
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{Event, InputEventListener, ListenerError, ListenerResult, Poll};
+
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// ## LocalPoll
+///
+/// Same as `Poll`, but without the `Send` bound, so it can be used to poll events which are not `Send`
+/// through the `LocalEventListener`. Every `Poll` is a `LocalPoll` too.
+pub trait LocalPoll<UserEvent>
+where
+    UserEvent: Eq + PartialEq + Clone + PartialOrd,
+{
+    /// ### poll
+    ///
+    /// Poll for an event. See `Poll::poll`
+    fn poll(&mut self) -> ListenerResult<Option<Event<UserEvent>>>;
+}
+
+impl<U, P> LocalPoll<U> for P
+where
+    U: Eq + PartialEq + Clone + PartialOrd + 'static,
+    P: Poll<U>,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        Poll::poll(self)
+    }
+}
+
+/// ## LocalSource
+///
+/// The source of the events of a `LocalPort`.
+/// The input listener of the backend is kept apart, since it can't implement `LocalPoll` without conflicting
+/// with the implementation for every `Poll`
+enum LocalSource<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    Poll(Box<dyn LocalPoll<U>>),
+    Input(InputEventListener<U>),
+}
+
+impl<U> LocalSource<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        match self {
+            Self::Poll(poll) => poll.poll(),
+            Self::Input(listener) => listener.read_event(),
+        }
+    }
+}
+
+/// ## LocalPort
+///
+/// A `LocalPoll` with its poll interval
+struct LocalPort<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    source: LocalSource<U>,
+    interval: Duration,
+    next_poll: Instant,
+}
+
+/// ## LocalEventListener
+///
+/// An event listener which doesn't spawn any thread: ports are polled and ticks are generated on the calling thread,
+/// each time `poll()` is called. For this reason, neither the user events nor the ports need to be `Send`
+/// (e.g. a user event may contain a `Rc`).
+///
+/// The tradeoff is that nothing is polled in background: ports are polled only while `poll()` runs, so they must
+/// return quickly, and events are produced only as often as you call `poll()`.
+/// Prefer the threaded `EventListenerCfg` whenever your user events are `Send`.
+///
+/// > `Application` still requires `UserEvent: Send`, since it runs the threaded event listener:
+/// > the `LocalEventListener` has to be driven manually, calling `poll()` and forwarding the events
+/// > to your components.
+pub struct LocalEventListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    ports: Vec<LocalPort<U>>,
+    poll_timeout: Duration,
+    tick_interval: Option<Duration>,
+    next_tick: Instant,
+    paused: bool,
+    stopped: bool,
+    /// Events polled but not returned yet
+    queue: VecDeque<ListenerResult<Event<U>>>,
+}
+
+impl<U> Default for LocalEventListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    fn default() -> Self {
+        Self {
+            ports: Vec::new(),
+            poll_timeout: Duration::from_millis(10),
+            tick_interval: None,
+            next_tick: Instant::now(),
+            paused: false,
+            stopped: false,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<U> LocalEventListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd,
+{
+    /// ### port
+    ///
+    /// Add a new port (poll, interval) to the event listener
+    pub fn port(self, poll: Box<dyn LocalPoll<U>>, interval: Duration) -> Self {
+        self.source(LocalSource::Poll(poll), interval)
+    }
+
+    /// ### source
+    ///
+    /// Add a new port polling `source` every `interval`
+    fn source(mut self, source: LocalSource<U>, interval: Duration) -> Self {
+        self.ports.push(LocalPort {
+            source,
+            interval,
+            next_poll: Instant::now(),
+        });
+        self
+    }
+
+    /// ### input_listener
+    ///
+    /// Add the input listener of the backend in use as a port, polled every `interval`
+    pub fn input_listener(self, listener: InputEventListener<U>, interval: Duration) -> Self {
+        self.source(LocalSource::Input(listener), interval)
+    }
+
+    /// ### default_input_listener
+    ///
+    /// Add the default input listener of the backend in use as a port, polled every `interval`
+    pub fn default_input_listener(self, interval: Duration) -> Self {
+        self.input_listener(InputEventListener::new(interval), interval)
+    }
+
+    /// ### poll_timeout
+    ///
+    /// Set poll timeout: the maximum time `poll()` waits for an event. Default: 10ms
+    pub fn poll_timeout(mut self, timeout: Duration) -> Self {
+        self.poll_timeout = timeout;
+        self
+    }
+
+    /// ### tick_interval
+    ///
+    /// Defines the tick interval for the event listener and enables the `Tick` event
+    pub fn tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = Some(interval);
+        self.next_tick = Instant::now();
+        self
+    }
+
+    /// ### pause
+    ///
+    /// Pause the event listener: ports won't be polled and ticks won't be generated until `unpause()` is called
+    pub fn pause(&mut self) -> ListenerResult<()> {
+        self.paused = true;
+        Ok(())
+    }
+
+    /// ### unpause
+    ///
+    /// Unpause the event listener
+    pub fn unpause(&mut self) -> ListenerResult<()> {
+        self.paused = false;
+        Ok(())
+    }
+
+    /// ### stop
+    ///
+    /// Stop the event listener. Once stopped, `poll()` returns `ListenerError::ListenerDied`
+    pub fn stop(&mut self) -> ListenerResult<()> {
+        self.stopped = true;
+        self.queue.clear();
+        Ok(())
+    }
+
+    /// ### poll
+    ///
+    /// Poll the ports which are due and generate the tick if due, then return the first available event.
+    /// If no event is available, waits for the next port or tick to be due, for at most the poll timeout.
+    pub fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        if self.stopped {
+            return Err(ListenerError::ListenerDied);
+        }
+        let deadline = Instant::now() + self.poll_timeout;
+        loop {
+            if let Some(ev) = self.queue.pop_front() {
+                return ev.map(Some);
+            }
+            if !self.paused {
+                self.iterate(Instant::now());
+                if !self.queue.is_empty() {
+                    continue;
+                }
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            let wake_at = match self.paused {
+                true => deadline,
+                false => self.next_event().unwrap_or(deadline).min(deadline),
+            };
+            thread::sleep(wake_at.saturating_duration_since(now));
+        }
+    }
+
+    /// ### iterate
+    ///
+    /// Poll due ports, then send tick if due
+    fn iterate(&mut self, now: Instant) {
+        for port in self.ports.iter_mut().filter(|x| x.next_poll <= now) {
+            match port.source.poll() {
+                Ok(Some(ev)) => self.queue.push_back(Ok(ev)),
+                Ok(None) => {}
+                Err(err) => self.queue.push_back(Err(err)),
+            }
+            port.next_poll = now + port.interval;
+        }
+        if let Some(interval) = self.tick_interval {
+            if self.next_tick <= now {
+                self.queue.push_back(Ok(Event::Tick));
+                self.next_tick = now + interval;
+            }
+        }
+    }
+
+    /// ### next_event
+    ///
+    /// Returns the time the next port or tick is due
+    fn next_event(&self) -> Option<Instant> {
+        let next_poll = self.ports.iter().map(|x| x.next_poll).min();
+        let next_tick = self.tick_interval.map(|_| self.next_tick);
+        match (next_poll, next_tick) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::{MockEvent, MockPoll};
+
+    use pretty_assertions::assert_eq;
+    use std::rc::Rc;
+
+    /// A user event which isn't `Send`
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+    struct RcEvent(Rc<String>);
+
+    /// A poll which isn't `Send`
+    struct RcPoll {
+        name: Rc<String>,
+    }
+
+    impl LocalPoll<RcEvent> for RcPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<RcEvent>>> {
+            Ok(Some(Event::User(RcEvent(Rc::clone(&self.name)))))
+        }
+    }
+
+    struct FailingPoll;
+
+    impl LocalPoll<RcEvent> for FailingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<RcEvent>>> {
+            Err(ListenerError::PollFailed(String::from("failed")))
+        }
+    }
+
+    #[test]
+    fn should_poll_local_ports() {
+        let name = Rc::new(String::from("omar"));
+        let mut listener = LocalEventListener::default().port(
+            Box::new(RcPoll {
+                name: Rc::clone(&name),
+            }),
+            Duration::from_secs(60),
+        );
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(RcEvent(Rc::clone(&name))))
+        );
+        // Not due yet
+        assert_eq!(listener.poll().ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_register_backend_input_listener() {
+        let listener = LocalEventListener::<RcEvent>::default()
+            .input_listener(
+                InputEventListener::new(Duration::from_millis(10))
+                    .multi_click_threshold(Duration::from_millis(500)),
+                Duration::from_millis(10),
+            )
+            .default_input_listener(Duration::from_millis(10));
+        assert_eq!(listener.ports.len(), 2);
+    }
+
+    #[test]
+    fn should_poll_send_ports_too() {
+        let mut listener = LocalEventListener::<MockEvent>::default()
+            .port(Box::new(MockPoll::default()), Duration::from_secs(60));
+        assert!(listener.poll().ok().unwrap().is_some());
+    }
+
+    #[test]
+    fn should_tick_and_pause() {
+        let mut listener = LocalEventListener::<RcEvent>::default()
+            .poll_timeout(Duration::from_millis(100))
+            .tick_interval(Duration::from_millis(50));
+        assert_eq!(listener.poll().ok().unwrap(), Some(Event::Tick));
+        // Waits for the next tick
+        let t = Instant::now();
+        assert_eq!(listener.poll().ok().unwrap(), Some(Event::Tick));
+        assert!(t.elapsed() >= Duration::from_millis(40));
+        // Pause
+        assert!(listener.pause().is_ok());
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        assert!(listener.unpause().is_ok());
+        assert_eq!(listener.poll().ok().unwrap(), Some(Event::Tick));
+    }
+
+    #[test]
+    fn should_report_errors_and_stop() {
+        let mut listener = LocalEventListener::<RcEvent>::default()
+            .port(Box::new(FailingPoll), Duration::from_secs(60));
+        assert!(matches!(listener.poll(), Err(ListenerError::PollFailed(_))));
+        assert!(listener.stop().is_ok());
+        assert!(matches!(listener.poll(), Err(ListenerError::ListenerDied)));
+    }
+}
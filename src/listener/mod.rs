@@ -27,20 +27,69 @@
  * SOFTWARE.
  */
 // -- modules
+mod adaptive;
 mod builder;
+mod clock;
+mod dedup;
+mod gesture;
+mod grab;
+mod group;
+mod guard;
+mod local;
+mod paste;
+mod polls;
 mod port;
+mod receiver;
+mod repeat;
+mod schedule;
+mod timing;
 mod worker;
 
 // -- export
 pub use crate::adapter::InputEventListener;
 pub use builder::EventListenerCfg;
+#[cfg(feature = "test-util")]
+pub use clock::{Clock, MockClock, SystemClock};
+pub use grab::GrabResult;
+pub use group::PortGroup;
+pub use guard::PauseGuard;
+pub use local::{LocalEventListener, LocalPoll};
+#[cfg(feature = "async-tokio")]
+pub use polls::BroadcastPoll;
+#[cfg(unix)]
+pub use polls::FdPort;
+pub use polls::{
+    DebouncePoll, IteratorPoll, MapPoll, NullPoll, RateLimitPort, ThrottlePoll, TimerPoll,
+};
+#[cfg(feature = "watch")]
+pub use polls::{FileChange, FileWatchPoll};
+pub use receiver::EventReceiver;
+pub use schedule::ScheduleHandle;
+pub use timing::IterationTiming;
 
 // -- internal
 use super::Event;
-pub use port::Port;
-use worker::EventListenerWorker;
+use adaptive::AdaptivePoll;
+use builder::ListenerSnapshot;
+#[cfg(not(feature = "test-util"))]
+use clock::{Clock, SystemClock};
+use dedup::Dedup;
+use gesture::DragTracker;
+use grab::SharedGrab;
+use guard::PauseGuards;
+use paste::PasteBurst;
+pub use port::{Port, PortBuilder, PortFactory, PortKind, PortStatus};
+use receiver::Subscribers;
+use repeat::KeyRepeat;
+use schedule::SharedSchedule;
+use timing::SharedTiming;
+use worker::{
+    DrainHook, EventFilter, EventListenerWorker, ReadySignal, SharedPorts, SizeQuery, TickHook,
+    WorkerOptions,
+};
 
-use std::sync::{mpsc, Arc, RwLock};
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Condvar, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 use thiserror::Error;
@@ -51,7 +100,7 @@ use thiserror::Error;
 /// Err value is always `ListenerError`.
 pub type ListenerResult<T> = Result<T, ListenerError>;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ListenerError {
     #[error("failed to start event listener")]
     CouldNotStart,
@@ -59,8 +108,35 @@ pub enum ListenerError {
     CouldNotStop,
     #[error("the event listener has died")]
     ListenerDied,
-    #[error("poll() call returned error")]
-    PollFailed,
+    /// A port failed to poll; the message describes the underlying cause
+    #[error("poll() call returned error: {0}")]
+    PollFailed(String),
+    /// The poll timeout is longer than the tick interval, so ticks can't be delivered on time
+    #[error("poll timeout ({poll_timeout:?}) must not be longer than the tick interval ({tick_interval:?})")]
+    PollTimeoutTooLong {
+        poll_timeout: Duration,
+        tick_interval: Duration,
+    },
+}
+
+/// ## PortId
+///
+/// Identifies a port registered in the event listener.
+/// The id of a port is its index in registration order (the first port added to `EventListenerCfg` is `0`).
+/// The default input listener, if used, is a port too.
+pub type PortId = usize;
+
+/// ## TickPriority
+///
+/// Defines the order in which the worker sends a tick, relative to the events polled from the ports
+/// in the same iteration. Default: `AfterInput`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickPriority {
+    /// A due tick is sent before the events polled in the same iteration
+    BeforeInput,
+    /// A due tick is sent after the events polled in the same iteration
+    #[default]
+    AfterInput,
 }
 
 /// ## Poll
@@ -93,12 +169,38 @@ where
     poll_timeout: Duration,
     /// Indicates whether the worker should paused polling ports
     paused: Arc<RwLock<bool>>,
+    /// Amount of alive pause guards
+    pause_guards: Arc<Mutex<PauseGuards>>,
     /// Indicates whether the worker should keep running
     running: Arc<RwLock<bool>>,
     /// Msg receiver from worker
     recv: mpsc::Receiver<ListenerMsg<U>>,
+    /// Events of the last batch received from the worker, not polled yet
+    unpacked: Mutex<VecDeque<Event<U>>>,
+    /// Msg sender to the listener queue, used to inject events
+    injector: mpsc::Sender<ListenerMsg<U>>,
+    /// Command sender to worker
+    cmd: mpsc::Sender<ListenerCmd>,
     /// Join handle for worker
     thread: Option<JoinHandle<()>>,
+    /// Configuration of the listener, updated when changed at runtime
+    snapshot: ListenerSnapshot<U>,
+    /// Status of the ports, reported by the worker
+    status: Arc<RwLock<Vec<PortStatus>>>,
+    /// Additional receivers of the events
+    subscribers: Subscribers<U>,
+    /// Raised by the worker once it is running
+    ready: ReadySignal,
+    /// One-shot events scheduled to be sent by the worker
+    schedule: SharedSchedule<U>,
+    /// Handler intercepting the events in the worker
+    grab: SharedGrab<U>,
+    /// Duration of the worker iterations, reported by the worker
+    timing: SharedTiming,
+    /// Ports replacing the ones of the worker on its next iteration
+    new_ports: SharedPorts<U>,
+    /// Callback given the events still queued on stop
+    on_drain: Option<DrainHook<U>>,
 }
 
 impl<U> EventListener<U>
@@ -113,47 +215,152 @@ where
     /// - `tick_interval` is the interval used to send the `Tick` event. If `None`, no tick will be sent.
     ///     Tick should be used only when you need to handle the tick in the interface through the Subscriptions.
     ///     The tick should have in this case, the same value (or less) of the refresh rate of the TUI.
+    /// - `options` defines the optional behaviours of the worker (key repeat, adaptive poll...)
     ///
     /// > Panics if `poll_timeout` is 0
     pub(self) fn start(
         ports: Vec<Port<U>>,
         poll_timeout: Duration,
         tick_interval: Option<Duration>,
+        mut options: WorkerOptions<U>,
     ) -> Self {
         if poll_timeout == Duration::ZERO {
             panic!(
                 "poll timeout cannot be 0 (see <https://github.com/rust-lang/rust/issues/39364>)"
             )
         }
+        let snapshot = ListenerSnapshot::new(&ports, poll_timeout, tick_interval, &options);
+        let on_drain = options.on_drain.take();
         // Prepare channel and running state
-        let config = Self::setup_thread(ports, tick_interval);
+        let config = Self::setup_thread(ports, tick_interval, options);
         Self {
             paused: config.paused,
+            pause_guards: Arc::new(Mutex::new(PauseGuards::default())),
             running: config.running,
             poll_timeout,
             recv: config.rx,
+            unpacked: Mutex::new(VecDeque::new()),
+            injector: config.tx,
+            cmd: config.cmd_tx,
             thread: Some(config.thread),
+            snapshot,
+            status: config.status,
+            subscribers: config.subscribers,
+            ready: config.ready,
+            schedule: config.schedule,
+            grab: config.grab,
+            timing: config.timing,
+            new_ports: config.new_ports,
+            on_drain,
         }
     }
 
+    /// ### start_with_clock
+    ///
+    /// Create a new `EventListener` and start it, as `start()`, but using `clock` as the time source of the worker,
+    /// instead of the wall clock. With a `MockClock`, polls and ticks are scheduled according to the time set
+    /// by the test, so that the scheduling can be tested deterministically.
+    ///
+    /// > Panics if `poll_timeout` is 0
+    #[cfg(test)]
+    pub(self) fn start_with_clock(
+        ports: Vec<Port<U>>,
+        poll_timeout: Duration,
+        tick_interval: Option<Duration>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self::start(
+            ports,
+            poll_timeout,
+            tick_interval,
+            WorkerOptions {
+                clock,
+                ..Default::default()
+            },
+        )
+    }
+
     /// ### stop
     ///
     /// Stop event listener
+    ///
+    /// Stopping is idempotent: stopping a listener which has already been stopped is a no-op.
+    /// If the worker has panicked, the panic is discarded and `Ok` is returned, since the listener is stopped anyway.
     pub fn stop(&mut self) -> ListenerResult<()> {
+        let thread = match self.thread.take() {
+            Some(thread) => thread,
+            None => return Ok(()),
+        };
+        self.signal_stop();
+        // Join thread; a panicked worker is stopped too
+        let _ = thread.join();
+        self.drain();
+        Ok(())
+    }
+
+    /// ### stop_timeout
+    ///
+    /// Stop event listener, waiting at most `timeout` for the worker to terminate.
+    /// If the worker doesn't exit in time (e.g. a port is stuck in a blocking call), the thread is detached
+    /// and `ListenerError::CouldNotStop` is returned.
+    /// As `stop()`, stopping is idempotent and a panicked worker is considered stopped.
+    pub fn stop_timeout(&mut self, timeout: Duration) -> ListenerResult<()> {
+        let thread = match self.thread.take() {
+            Some(thread) => thread,
+            None => return Ok(()),
+        };
+        self.signal_stop();
+        // Join thread from an helper thread, which reports once the worker has terminated
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = thread.join();
+            let _ = tx.send(());
+        });
+        let result = rx
+            .recv_timeout(timeout)
+            .map_err(|_| ListenerError::CouldNotStop);
+        self.drain();
+        result
+    }
+
+    /// ### drain
+    ///
+    /// Give the events still queued to the drain callback, if set, in the order they would have been polled.
+    /// Errors are discarded. The callback is called at most once per listener, on the thread stopping the listener
+    fn drain(&mut self) {
+        let on_drain = match self.on_drain.take() {
+            Some(on_drain) => on_drain,
+            None => return,
+        };
+        let mut on_drain = match on_drain.lock() {
+            Ok(on_drain) => on_drain,
+            Err(_) => return,
+        };
+        if let Ok(mut unpacked) = self.unpacked.lock() {
+            unpacked.drain(..).for_each(&mut *on_drain);
+        }
+        for msg in self.recv.try_iter() {
+            match msg {
+                ListenerMsg::Batch(events) => events.into_iter().for_each(&mut *on_drain),
+                ListenerMsg::Tick => on_drain(Event::Tick),
+                ListenerMsg::User(ev) => on_drain(ev),
+                ListenerMsg::Error(_) => {}
+            }
+        }
+    }
+
+    /// ### signal_stop
+    ///
+    /// Tell the worker to terminate
+    fn signal_stop(&mut self) {
+        // NOTE: keep these brackets to drop running after block
         {
-            // NOTE: keep these brackets to drop running after block
-            let mut running = match self.running.write() {
-                Ok(lock) => Ok(lock),
-                Err(_) => Err(ListenerError::CouldNotStop),
-            }?;
+            // If the lock is poisoned, the worker has panicked; set the flag anyway
+            let mut running = self.running.write().unwrap_or_else(|x| x.into_inner());
             *running = false;
         }
-        // Join thread
-        match self.thread.take().map(|x| x.join()) {
-            Some(Ok(_)) => Ok(()),
-            Some(Err(_)) => Err(ListenerError::CouldNotStop),
-            None => Ok(()), // Never happens, unless someone calls stop twice
-        }
+        // Wake up worker; if it has already terminated, there's nothing to wake up
+        let _ = self.cmd.send(ListenerCmd::Wake);
     }
 
     /// ### pause
@@ -166,6 +373,9 @@ where
             Err(_) => Err(ListenerError::CouldNotStop),
         }?;
         *paused = true;
+        drop(paused);
+        // Wake up worker, so that it notices the new state right away
+        let _ = self.cmd.send(ListenerCmd::Wake);
         Ok(())
     }
 
@@ -179,34 +389,403 @@ where
             Err(_) => Err(ListenerError::CouldNotStop),
         }?;
         *paused = false;
+        drop(paused);
+        // Wake up worker, so that it notices the new state right away
+        let _ = self.cmd.send(ListenerCmd::Wake);
+        Ok(())
+    }
+
+    /// ### pause_guard
+    ///
+    /// Pause event listener worker until the returned guard is dropped
+    pub fn pause_guard(&mut self) -> PauseGuard {
+        PauseGuard::new(
+            Arc::clone(&self.paused),
+            Arc::clone(&self.pause_guards),
+            self.cmd.clone(),
+        )
+    }
+
+    /// ### pause_all_except
+    ///
+    /// Pause all the ports except the one identified by `id`, which keeps being polled (e.g. to keep a cancel key
+    /// working while a modal is shown). If `keep_ticks` is false, ticks are paused too.
+    /// Call `unpause_all()` to resume the paused ports. This is independent of `pause()`, which pauses everything.
+    pub fn pause_all_except(&mut self, id: PortId, keep_ticks: bool) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::PauseAllExcept(id, keep_ticks))
+    }
+
+    /// ### unpause_all
+    ///
+    /// Resume the ports (and ticks) paused with `pause_all_except()`
+    pub fn unpause_all(&mut self) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::UnpauseAll)
+    }
+
+    /// ### pause_kind
+    ///
+    /// Pause all the ports of `kind` (e.g. `PortKind::Background` to stop background work while a modal is shown).
+    /// Ticks are not affected. Call `unpause_kind()` to resume them. This is independent of `pause_all_except()`:
+    /// a port is polled only if it's not paused by either
+    pub fn pause_kind(&mut self, kind: PortKind) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::PauseKind(kind))
+    }
+
+    /// ### unpause_kind
+    ///
+    /// Resume the ports of `kind` paused with `pause_kind()`
+    pub fn unpause_kind(&mut self, kind: PortKind) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::UnpauseKind(kind))
+    }
+
+    /// ### set_ports
+    ///
+    /// Replace all the ports of the listener with `ports`, at once: the worker swaps them at the top of its next
+    /// iteration, so it never polls a mix of the old and new ports. Ports are identified by their index in `ports`
+    /// from then on. Pause state (`pause()`, `pause_kind()`, `pause_all_except()`) and ticks are kept.
+    /// Events already sent by the old ports can still be polled.
+    pub fn set_ports(&mut self, ports: Vec<Port<U>>) -> ListenerResult<()> {
+        self.snapshot.ports = ports
+            .iter()
+            .map(|x| (x.factory().cloned(), *x.interval(), x.kind()))
+            .collect();
+        self.new_ports
+            .lock()
+            .map(|mut x| *x = Some(ports))
+            .map_err(|_| ListenerError::ListenerDied)?;
+        // Wake up worker, so that it swaps the ports
+        self.send_cmd(ListenerCmd::Wake)
+    }
+
+    /// ### set_port_interval
+    ///
+    /// Set a new poll interval for the port identified by `id`.
+    /// The worker applies the new interval on its next scheduling decision for that port.
+    /// Unknown ids are ignored by the worker.
+    pub fn set_port_interval(&mut self, id: PortId, interval: Duration) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::SetInterval(id, interval))?;
+        if let Some(port) = self.snapshot.ports.get_mut(id) {
+            port.1 = interval;
+        }
         Ok(())
     }
 
+    /// ### set_tick_interval
+    ///
+    /// Set a new tick interval for the worker. If `None`, ticks are disabled.
+    /// The worker updates its tick scheduling without being restarted, so no queued event is lost.
+    pub fn set_tick_interval(&mut self, interval: Option<Duration>) -> ListenerResult<()> {
+        self.send_cmd(ListenerCmd::SetTickInterval(interval))?;
+        self.snapshot.tick_interval = interval;
+        Ok(())
+    }
+
+    /// ### poll_timeout
+    ///
+    /// Returns the timeout `poll()` waits for an event
+    pub fn poll_timeout(&self) -> Duration {
+        self.poll_timeout
+    }
+
+    /// ### tick_interval
+    ///
+    /// Returns the current tick interval; `None` if ticks are disabled.
+    /// It reflects the changes made with `set_tick_interval()`
+    pub fn tick_interval(&self) -> Option<Duration> {
+        self.snapshot.tick_interval
+    }
+
+    /// ### port_interval
+    ///
+    /// Returns the current poll interval of the port identified by `id`; `None` if there's no such port.
+    /// It reflects the changes made with `set_port_interval()`
+    pub fn port_interval(&self, id: PortId) -> Option<Duration> {
+        self.snapshot
+            .ports
+            .get(id)
+            .map(|(_, interval, _)| *interval)
+    }
+
+    /// ### subscribe
+    ///
+    /// Returns a new receiver of the events sent by the worker (see `EventReceiver`)
+    pub fn subscribe(&self) -> EventReceiver<U> {
+        EventReceiver::subscribe(Arc::clone(&self.subscribers))
+    }
+
+    /// ### wait_ready
+    ///
+    /// Blocks until the worker is up and running (it has polled the ports for the first time, or it has started paused),
+    /// waiting at most for `timeout`. Returns whether the worker is ready.
+    pub fn wait_ready(&self, timeout: Duration) -> bool {
+        let (lock, cvar) = &*self.ready;
+        let guard = match lock.lock() {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+        cvar.wait_timeout_while(guard, timeout, |ready| !*ready)
+            .map(|(ready, _)| *ready)
+            .unwrap_or(false)
+    }
+
+    /// ### iteration_timing
+    ///
+    /// Returns how long the worker spends in each iteration (see `IterationTiming`)
+    pub fn iteration_timing(&self) -> IterationTiming {
+        self.timing.read().map(|x| *x).unwrap_or_default()
+    }
+
+    /// ### ports
+    ///
+    /// Returns the status of the ports registered in the listener.
+    /// The status is a point-in-time snapshot, reported by the worker each time it polls the ports,
+    /// so it may be slightly stale (e.g. while a port is being polled).
+    pub fn ports(&self) -> Vec<PortStatus> {
+        let paused = self.paused.read().map(|x| *x).unwrap_or(false);
+        self.status
+            .read()
+            .map(|x| x.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|x| PortStatus {
+                paused: paused || x.paused,
+                ..x
+            })
+            .collect()
+    }
+
+    /// ### config_snapshot
+    ///
+    /// Returns a configuration to start a new listener equivalent to this one: same ports intervals,
+    /// tick interval, options and pause state, including the changes made at runtime.
+    ///
+    /// > Only ports built by a factory (see `EventListenerCfg::port_factory`) can be rebuilt; other ports are skipped,
+    /// > so the `PortId` of the following ports change.
+    pub fn config_snapshot(&self) -> EventListenerCfg<U> {
+        let paused = self.paused.read().map(|x| *x).unwrap_or(false);
+        self.snapshot.config(paused)
+    }
+
+    /// ### send_cmd
+    ///
+    /// Send a command to the worker
+    fn send_cmd(&self, cmd: ListenerCmd) -> ListenerResult<()> {
+        self.cmd.send(cmd).map_err(|_| ListenerError::ListenerDied)
+    }
+
     /// ### poll
     ///
-    /// Checks whether there are new events available from event
+    /// Checks whether there are new events available from event.
+    /// Returns `ListenerError::PollFailed` if a port failed to poll, while `ListenerError::ListenerDied` is returned
+    /// once the worker has terminated (e.g. it has been stopped or it has panicked), and the listener must be restarted.
     pub fn poll(&self) -> ListenerResult<Option<Event<U>>> {
+        let mut unpacked = self
+            .unpacked
+            .lock()
+            .map_err(|_| ListenerError::ListenerDied)?;
+        if let Some(ev) = unpacked.pop_front() {
+            return Ok(Some(ev));
+        }
         match self.recv.recv_timeout(self.poll_timeout) {
+            // Unpack batch
+            Ok(ListenerMsg::Batch(events)) => {
+                unpacked.extend(events);
+                Ok(unpacked.pop_front())
+            }
             Ok(msg) => ListenerResult::from(msg),
+            // The listener holds a sender too, so the channel is never disconnected: check the worker instead
+            Err(mpsc::RecvTimeoutError::Timeout) if self.worker_terminated() => {
+                Err(ListenerError::ListenerDied)
+            }
             Err(mpsc::RecvTimeoutError::Timeout) => Ok(None),
-            Err(_) => Err(ListenerError::PollFailed),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(ListenerError::ListenerDied),
+        }
+    }
+
+    /// ### inject
+    ///
+    /// Push `event` to the listener queue, as if it had been produced by a port; it is also broadcast to subscribers.
+    /// The event is queued after the events already pending, so it is returned once they have been polled.
+    /// Since it doesn't go through the worker, the event filter and the other transformations applied by the worker
+    /// (key repeat, gestures...) don't apply to it.
+    /// Returns `ListenerError::ListenerDied` if the worker has terminated.
+    pub fn inject(&self, event: Event<U>) -> ListenerResult<()> {
+        if self.worker_terminated() {
+            return Err(ListenerError::ListenerDied);
+        }
+        receiver::broadcast(&self.subscribers, &event);
+        self.injector
+            .send(ListenerMsg::User(event))
+            .map_err(|_| ListenerError::ListenerDied)
+    }
+
+    /// ### schedule_once
+    ///
+    /// Schedule `event` to be sent once by the worker after `delay` (e.g. to dismiss a notification after a while).
+    /// Unlike ticks and ports, the event is sent only once; it can be cancelled with `cancel_scheduled()`
+    /// using the returned handle. As the events returned by ports, it is subject to the event filter,
+    /// and it is not sent while the listener is paused: in that case it is sent once the listener is resumed.
+    pub fn schedule_once(
+        &self,
+        delay: Duration,
+        event: Event<U>,
+    ) -> ListenerResult<ScheduleHandle> {
+        let handle = self
+            .schedule
+            .lock()
+            .map(|mut x| x.push(delay, event))
+            .map_err(|_| ListenerError::ListenerDied)?;
+        // Wake up worker, so that it arms the event
+        self.send_cmd(ListenerCmd::Wake)?;
+        Ok(handle)
+    }
+
+    /// ### cancel_scheduled
+    ///
+    /// Cancel the event scheduled with `schedule_once()` identified by `handle`.
+    /// Returns whether the event was still scheduled
+    pub fn cancel_scheduled(&self, handle: ScheduleHandle) -> bool {
+        self.schedule
+            .lock()
+            .map(|mut x| x.cancel(handle))
+            .unwrap_or(false)
+    }
+
+    /// ### grab
+    ///
+    /// Install `handler` on the worker, to intercept the events before they are sent (e.g. for a modal overlay
+    /// capturing all the input): the handler receives every event and returns `GrabResult::Consume`
+    /// to drop it, or `GrabResult::Pass` to send it as usual. It replaces the previous handler, if any,
+    /// and it is active until `release_grab()` is called. Ticks bypass the handler, unless
+    /// `EventListenerCfg::grab_ticks()` is enabled, while errors are never grabbed.
+    ///
+    /// > The handler runs on the worker thread while holding the grab lock, so it must not block,
+    /// > nor call `grab()` or `release_grab()` itself, which would deadlock: release the grab from the application.
+    pub fn grab<F>(&self, handler: F)
+    where
+        F: FnMut(Event<U>) -> GrabResult + Send + 'static,
+    {
+        if let Ok(mut grab) = self.grab.lock() {
+            *grab = Some(Box::new(handler));
+        }
+    }
+
+    /// ### release_grab
+    ///
+    /// Remove the handler installed with `grab()`; events are sent as usual again
+    pub fn release_grab(&self) {
+        if let Ok(mut grab) = self.grab.lock() {
+            *grab = None;
         }
     }
 
+    /// ### worker_terminated
+    ///
+    /// Returns whether the worker has terminated (stopped or panicked)
+    fn worker_terminated(&self) -> bool {
+        self.thread
+            .as_ref()
+            .map(|x| x.is_finished())
+            .unwrap_or(true)
+    }
+
+    /// ### flush
+    ///
+    /// Discard all the events pending in the listener queue, without waiting, and returns the amount of discarded events.
+    /// Ticks and port errors queued before the queue is empty are discarded too.
+    pub fn flush(&self) -> usize {
+        let unpacked = self
+            .unpacked
+            .lock()
+            .map(|mut x| x.drain(..).count())
+            .unwrap_or_default();
+        let queued: usize = std::iter::from_fn(|| self.recv.try_recv().ok())
+            .map(|msg| match msg {
+                ListenerMsg::Batch(events) => events.len(),
+                _ => 1,
+            })
+            .sum();
+        unpacked + queued
+    }
+
+    /// ### iter
+    ///
+    /// Returns an iterator over the events available from the listener.
+    /// See `Events` for the semantics of the iterator.
+    pub fn iter(&self) -> Events<'_, U> {
+        Events { listener: self }
+    }
+
     /// ### setup_thread
     ///
     /// Setup the thread and returns the structs necessary to interact with it
-    fn setup_thread(ports: Vec<Port<U>>, tick_interval: Option<Duration>) -> ThreadConfig<U> {
+    fn setup_thread(
+        ports: Vec<Port<U>>,
+        tick_interval: Option<Duration>,
+        options: WorkerOptions<U>,
+    ) -> ThreadConfig<U> {
         let (sender, recv) = mpsc::channel();
-        let paused = Arc::new(RwLock::new(false));
+        let injector = sender.clone();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(options.paused));
         let paused_t = Arc::clone(&paused);
         let running = Arc::new(RwLock::new(true));
         let running_t = Arc::clone(&running);
+        let status = Arc::new(RwLock::new(
+            ports
+                .iter()
+                .enumerate()
+                .map(|(id, port)| port.status(id))
+                .collect(),
+        ));
+        let status_t = Arc::clone(&status);
+        let subscribers = Arc::new(Mutex::new(Some(Vec::new())));
+        let subscribers_t = Arc::clone(&subscribers);
+        let ready = Arc::new((Mutex::new(false), Condvar::new()));
+        let ready_t = Arc::clone(&ready);
+        let schedule = SharedSchedule::default();
+        let schedule_t = Arc::clone(&schedule);
+        let grab = SharedGrab::default();
+        let grab_t = Arc::clone(&grab);
+        let timing = SharedTiming::default();
+        let timing_t = Arc::clone(&timing);
+        let new_ports = SharedPorts::default();
+        let new_ports_t = Arc::clone(&new_ports);
         // Start thread
         let thread = thread::spawn(move || {
-            EventListenerWorker::new(ports, sender, paused_t, running_t, tick_interval).run();
+            EventListenerWorker::new(
+                ports,
+                sender,
+                cmd_rx,
+                paused_t,
+                running_t,
+                tick_interval,
+                options,
+            )
+            .report_status(status_t)
+            .broadcast_to(subscribers_t)
+            .signal_ready(ready_t)
+            .schedule_from(schedule_t)
+            .grab_from(grab_t)
+            .report_timing(timing_t)
+            .ports_from(new_ports_t)
+            .run();
         });
-        ThreadConfig::new(recv, paused, running, thread)
+        ThreadConfig::new(
+            recv,
+            injector,
+            cmd_tx,
+            paused,
+            running,
+            status,
+            subscribers,
+            ready,
+            schedule,
+            grab,
+            timing,
+            new_ports,
+            thread,
+        )
     }
 }
 
@@ -219,6 +798,44 @@ where
     }
 }
 
+impl<'a, U> IntoIterator for &'a mut EventListener<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    type Item = ListenerResult<Event<U>>;
+    type IntoIter = Events<'a, U>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// -- iterator
+
+/// ## Events
+///
+/// Iterator over the events available from the event listener (see `Application::events()`).
+/// Each call to `next()` polls the listener, so it blocks up to the poll timeout.
+/// Since an iterator can't return a `Result`, errors are returned as items (`Some(Err(_))`) and iteration
+/// can go on after them; the iteration ends (`None`) as soon as no event is available within the poll timeout.
+pub struct Events<'a, U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    listener: &'a EventListener<U>,
+}
+
+impl<'a, U> Iterator for Events<'a, U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    type Item = ListenerResult<Event<U>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.listener.poll().transpose()
+    }
+}
+
 // -- thread config
 
 /// ## ThreadConfig
@@ -229,8 +846,17 @@ where
     U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
 {
     rx: mpsc::Receiver<ListenerMsg<U>>,
+    tx: mpsc::Sender<ListenerMsg<U>>,
+    cmd_tx: mpsc::Sender<ListenerCmd>,
     paused: Arc<RwLock<bool>>,
     running: Arc<RwLock<bool>>,
+    status: Arc<RwLock<Vec<PortStatus>>>,
+    subscribers: Subscribers<U>,
+    ready: ReadySignal,
+    schedule: SharedSchedule<U>,
+    grab: SharedGrab<U>,
+    timing: SharedTiming,
+    new_ports: SharedPorts<U>,
     thread: JoinHandle<()>,
 }
 
@@ -238,16 +864,35 @@ impl<U> ThreadConfig<U>
 where
     U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rx: mpsc::Receiver<ListenerMsg<U>>,
+        tx: mpsc::Sender<ListenerMsg<U>>,
+        cmd_tx: mpsc::Sender<ListenerCmd>,
         paused: Arc<RwLock<bool>>,
         running: Arc<RwLock<bool>>,
+        status: Arc<RwLock<Vec<PortStatus>>>,
+        subscribers: Subscribers<U>,
+        ready: ReadySignal,
+        schedule: SharedSchedule<U>,
+        grab: SharedGrab<U>,
+        timing: SharedTiming,
+        new_ports: SharedPorts<U>,
         thread: JoinHandle<()>,
     ) -> Self {
         Self {
             rx,
+            tx,
+            cmd_tx,
             paused,
             running,
+            status,
+            subscribers,
+            ready,
+            schedule,
+            grab,
+            timing,
+            new_ports,
             thread,
         }
     }
@@ -265,6 +910,29 @@ where
     Error(ListenerError),
     Tick,
     User(Event<U>),
+    /// Several events produced in the same worker iteration, sent at once
+    Batch(Vec<Event<U>>),
+}
+
+/// ## ListenerCmd
+///
+/// Command sent by the event listener to the worker, to change its configuration at runtime
+#[derive(Debug)]
+enum ListenerCmd {
+    /// Set the interval of the port with the provided id
+    SetInterval(PortId, Duration),
+    /// Set the tick interval; `None` disables ticks
+    SetTickInterval(Option<Duration>),
+    /// Pause all the ports except the one with the provided id; the flag tells whether ticks are kept
+    PauseAllExcept(PortId, bool),
+    /// Unpause the ports paused with `PauseAllExcept`
+    UnpauseAll,
+    /// Pause all the ports of the provided kind
+    PauseKind(PortKind),
+    /// Unpause the ports of the provided kind paused with `PauseKind`
+    UnpauseKind(PortKind),
+    /// Wake up the worker, so that it checks its running state immediately
+    Wake,
 }
 
 impl<U> From<ListenerMsg<U>> for ListenerResult<Option<Event<U>>>
@@ -276,6 +944,9 @@ where
             ListenerMsg::Error(err) => Err(err),
             ListenerMsg::Tick => Ok(Some(Event::Tick)),
             ListenerMsg::User(ev) => Ok(Some(ev)),
+            // A batch can't be converted into a single result without losing events:
+            // `EventListener::poll()` unpacks batches before converting the other messages
+            ListenerMsg::Batch(_) => unreachable!("batches must be unpacked by the listener"),
         }
     }
 }
@@ -286,21 +957,23 @@ mod test {
     use super::*;
     use crate::core::event::{Key, KeyEvent};
     use crate::mock::{MockEvent, MockPoll};
+    use clock::MockClock;
 
     use pretty_assertions::assert_eq;
 
     #[test]
     fn worker_should_run_thread() {
-        let mut listener = EventListener::<MockEvent>::start(
+        let clock = MockClock::new();
+        let mut listener = EventListener::<MockEvent>::start_with_clock(
             vec![Port::new(
                 Box::new(MockPoll::default()),
                 Duration::from_secs(10),
             )],
             Duration::from_millis(10),
             Some(Duration::from_secs(3)),
+            Arc::new(clock.clone()),
         );
-        // Wait 1 second
-        thread::sleep(Duration::from_secs(1));
+        assert!(listener.wait_ready(Duration::from_secs(5)));
         // Poll (event)
         assert_eq!(
             listener.poll().ok().unwrap().unwrap(),
@@ -310,8 +983,10 @@ mod test {
         assert_eq!(listener.poll().ok().unwrap().unwrap(), Event::Tick);
         // Poll (None)
         assert!(listener.poll().ok().unwrap().is_none());
-        // Wait 3 seconds
-        thread::sleep(Duration::from_secs(3));
+        // No tick until 3 seconds have passed
+        clock.advance(Duration::from_millis(2999));
+        assert!(listener.poll().ok().unwrap().is_none());
+        clock.advance(Duration::from_millis(1));
         // New tick
         assert_eq!(listener.poll().ok().unwrap().unwrap(), Event::Tick);
         // Stop
@@ -320,26 +995,868 @@ mod test {
 
     #[test]
     fn worker_should_be_paused() {
+        let clock = MockClock::new();
         let mut listener = EventListener::<MockEvent>::start(
             vec![],
-            Duration::from_millis(10),
+            Duration::from_millis(100),
             Some(Duration::from_millis(750)),
+            WorkerOptions {
+                clock: Arc::new(clock.clone()),
+                pause_events: true,
+                ..Default::default()
+            },
         );
-        thread::sleep(Duration::from_millis(100));
+        assert!(listener.wait_ready(Duration::from_secs(5)));
         assert!(listener.pause().is_ok());
         // Should be some
         assert_eq!(listener.poll().ok().unwrap().unwrap(), Event::Tick);
-        // Wait tick time
-        thread::sleep(Duration::from_secs(1));
+        // Wait for the worker to acknowledge the pause, then pass the tick time
+        assert_eq!(next_event(&mut listener), Event::ListenerPaused);
+        clock.advance(Duration::from_secs(1));
         assert_eq!(listener.poll().ok().unwrap(), None);
         // Unpause
         assert!(listener.unpause().is_ok());
-        thread::sleep(Duration::from_millis(300));
+        assert_eq!(next_event(&mut listener), Event::ListenerResumed);
         assert_eq!(listener.poll().ok().unwrap().unwrap(), Event::Tick);
         // Stop
         assert!(listener.stop().is_ok());
     }
 
+    #[test]
+    fn should_report_listener_died_once_worker_has_terminated() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.stop().is_ok());
+        // Worker's sender has been dropped
+        assert!(matches!(listener.poll(), Err(ListenerError::ListenerDied)));
+    }
+
+    #[test]
+    fn should_clone_and_compare_listener_errors() {
+        let err = ListenerError::PollFailed(String::from("connection reset"));
+        assert_eq!(err.clone(), err);
+        assert_ne!(err, ListenerError::PollFailed(String::from("broken pipe")));
+        assert_ne!(ListenerError::CouldNotStart, ListenerError::CouldNotStop);
+        assert_eq!(ListenerError::ListenerDied, ListenerError::ListenerDied);
+        // Messages are unchanged
+        assert_eq!(
+            ListenerError::CouldNotStart.to_string(),
+            "failed to start event listener"
+        );
+    }
+
+    #[test]
+    fn should_convert_listener_msg_into_result() {
+        // Cause is propagated
+        match ListenerResult::from(ListenerMsg::<MockEvent>::Error(ListenerError::PollFailed(
+            String::from("connection reset"),
+        ))) {
+            Err(err @ ListenerError::PollFailed(_)) => assert_eq!(
+                err.to_string(),
+                "poll() call returned error: connection reset"
+            ),
+            _ => panic!("expected PollFailed"),
+        }
+        assert!(matches!(
+            ListenerResult::from(ListenerMsg::<MockEvent>::Tick),
+            Ok(Some(Event::Tick))
+        ));
+        assert!(matches!(
+            ListenerResult::from(ListenerMsg::User(Event::<MockEvent>::WindowResize(80, 24))),
+            Ok(Some(Event::WindowResize(80, 24)))
+        ));
+    }
+
+    #[test]
+    fn should_drain_queued_events_on_drop() {
+        let drained = Arc::new(Mutex::new(Vec::new()));
+        let drained_t = Arc::clone(&drained);
+        let listener = EventListener::<MockEvent>::start(
+            vec![Port::from_iter(
+                vec![
+                    Event::User(MockEvent::Foo),
+                    Event::User(MockEvent::Bar),
+                    Event::Keyboard(KeyEvent::from(Key::Enter)),
+                ],
+                Duration::ZERO,
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions {
+                on_drain: Some(
+                    Arc::new(Mutex::new(move |ev| drained_t.lock().unwrap().push(ev)))
+                        as DrainHook<MockEvent>,
+                ),
+                ..Default::default()
+            },
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        // Let the worker send all the events
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        drop(listener);
+        assert_eq!(
+            *drained.lock().unwrap(),
+            vec![
+                Event::User(MockEvent::Bar),
+                Event::Keyboard(KeyEvent::from(Key::Enter)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_replace_ports() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::from_iter(
+                std::iter::repeat(Event::User(MockEvent::Foo)),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        assert!(listener.set_ports(Vec::new()).is_ok());
+        assert_eq!(listener.port_interval(0), None);
+        // Wait for the ports to be swapped and drain events sent so far
+        thread::sleep(Duration::from_millis(50));
+        while let Ok(Some(_)) = listener.poll() {}
+        assert!(listener.ports().is_empty());
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_report_ports_status() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![
+                Port::new(Box::new(MockPoll::default()), Duration::from_millis(10)),
+                Port::new(Box::new(MockPoll::default()), Duration::from_secs(60)),
+            ],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        thread::sleep(Duration::from_millis(100));
+        let status = listener.ports();
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].id, 0);
+        assert_eq!(status[0].interval, Duration::from_millis(10));
+        assert_eq!(status[0].paused, false);
+        assert!(status[0].last_poll.is_some());
+        assert!(status[0].events > 1);
+        assert_eq!(status[1].id, 1);
+        assert_eq!(status[1].events, 1);
+        // Change interval and pause
+        assert!(listener
+            .set_port_interval(1, Duration::from_secs(30))
+            .is_ok());
+        assert!(listener.pause().is_ok());
+        thread::sleep(Duration::from_millis(50));
+        let status = listener.ports();
+        assert_eq!(status[1].interval, Duration::from_secs(30));
+        assert!(status.iter().all(|x| x.paused));
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_broadcast_events_to_subscribers() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(50)),
+            WorkerOptions::default(),
+        );
+        let a = listener.subscribe();
+        let b = a.clone();
+        thread::sleep(Duration::from_millis(120));
+        // Each consumer gets every event
+        let ticks = |r: &EventReceiver<MockEvent>| {
+            std::iter::from_fn(|| r.try_recv().ok().flatten()).count()
+        };
+        let received = listener.iter().take_while(|x| x.is_ok()).count();
+        assert!(received >= 2);
+        assert!(ticks(&a) >= 2);
+        assert!(ticks(&b) >= 2);
+        assert!(listener.stop().is_ok());
+        assert!(matches!(a.try_recv(), Err(ListenerError::ListenerDied)));
+    }
+
+    #[test]
+    fn should_pause_all_ports_except_one() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![
+                Port::from_iter(
+                    std::iter::repeat(Event::User(MockEvent::Foo)),
+                    Duration::from_millis(10),
+                ),
+                Port::from_iter(
+                    std::iter::repeat(Event::User(MockEvent::Bar)),
+                    Duration::from_millis(10),
+                ),
+            ],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(10)),
+            WorkerOptions::default(),
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        assert!(listener.pause_all_except(1, false).is_ok());
+        // Wait for the command to be applied and drain events received so far
+        thread::sleep(Duration::from_millis(50));
+        while let Ok(Some(_)) = listener.poll() {}
+        thread::sleep(Duration::from_millis(100));
+        let mut events = Vec::new();
+        while let Ok(Some(ev)) = listener.poll() {
+            events.push(ev);
+        }
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|x| *x == Event::User(MockEvent::Bar)));
+        let ports = listener.ports();
+        assert_eq!(ports[0].paused, true);
+        assert_eq!(ports[1].paused, false);
+        // Keep ticks
+        assert!(listener.pause_all_except(1, true).is_ok());
+        thread::sleep(Duration::from_millis(100));
+        let mut events = Vec::new();
+        while let Ok(Some(ev)) = listener.poll() {
+            events.push(ev);
+        }
+        assert!(events.contains(&Event::Tick));
+        assert!(!events.contains(&Event::User(MockEvent::Foo)));
+        // Unpause all
+        assert!(listener.unpause_all().is_ok());
+        thread::sleep(Duration::from_millis(100));
+        let mut events = Vec::new();
+        while let Ok(Some(ev)) = listener.poll() {
+            events.push(ev);
+        }
+        assert!(events.contains(&Event::User(MockEvent::Foo)));
+        assert_eq!(listener.ports()[0].paused, false);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn should_not_spin_without_ports_and_ticks() {
+        use std::os::unix::thread::JoinHandleExt;
+
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        // Poll just times out
+        let t = std::time::Instant::now();
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        assert!(t.elapsed() >= Duration::from_millis(10));
+        thread::sleep(Duration::from_secs(1));
+        // Measure the CPU time used by the worker thread
+        let thread = listener.thread.as_ref().unwrap().as_pthread_t();
+        let mut clock: libc::clockid_t = 0;
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        unsafe {
+            assert_eq!(libc::pthread_getcpuclockid(thread, &mut clock), 0);
+            assert_eq!(libc::clock_gettime(clock, &mut time), 0);
+        }
+        let cpu_time = Duration::new(time.tv_sec as u64, time.tv_nsec as u32);
+        assert!(cpu_time < Duration::from_millis(50));
+        // Still stops promptly
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_wait_ready() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_secs(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        // Ports have been polled
+        assert_eq!(listener.ports()[0].events, 1);
+        assert!(listener.wait_ready(Duration::ZERO));
+        assert!(listener.stop().is_ok());
+        // Paused at start
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions {
+                paused: true,
+                ..Default::default()
+            },
+        );
+        assert!(listener.wait_ready(Duration::from_secs(5)));
+        assert!(listener.stop().is_ok());
+        // Worker panicked before being ready
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(PanickingPoll),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert_eq!(listener.wait_ready(Duration::from_millis(200)), false);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn stop_should_be_idempotent() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(50)),
+            WorkerOptions::default(),
+        );
+        assert!(listener.stop().is_ok());
+        assert!(listener.stop().is_ok());
+        assert!(listener.stop_timeout(Duration::from_millis(10)).is_ok());
+    }
+
+    #[test]
+    fn should_stop_after_worker_panic() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(PanickingPoll),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        // Wait for the worker to panic
+        thread::sleep(Duration::from_millis(100));
+        assert!(matches!(listener.poll(), Err(ListenerError::ListenerDied)));
+        assert!(listener.stop().is_ok());
+        assert!(listener.stop().is_ok());
+        // Drop doesn't panic
+        drop(listener);
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(PanickingPoll),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        thread::sleep(Duration::from_millis(100));
+        assert!(listener.stop_timeout(Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn should_pause_with_guard() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(100)),
+            WorkerOptions::default(),
+        );
+        {
+            let _guard = listener.pause_guard();
+            thread::sleep(Duration::from_millis(50));
+            // Drain ticks sent before pausing
+            while listener.poll().ok().unwrap().is_some() {}
+            thread::sleep(Duration::from_millis(300));
+            assert_eq!(listener.poll().ok().unwrap(), None);
+        }
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(listener.poll().ok().unwrap().unwrap(), Event::Tick);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_stay_paused_after_guard_if_paused_explicitly() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.pause().is_ok());
+        {
+            let _guard = listener.pause_guard();
+        }
+        assert_eq!(*listener.paused.read().unwrap(), true);
+        assert!(listener.unpause().is_ok());
+        {
+            let _guard = listener.pause_guard();
+            assert_eq!(*listener.paused.read().unwrap(), true);
+        }
+        assert_eq!(*listener.paused.read().unwrap(), false);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_set_port_interval_at_runtime() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_secs(60),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert_eq!(listener.port_interval(0), Some(Duration::from_secs(60)));
+        assert_eq!(listener.port_interval(1), None);
+        // First poll
+        assert!(listener.poll().ok().unwrap().is_some());
+        assert!(listener.poll().ok().unwrap().is_none());
+        // Poll faster
+        assert!(listener
+            .set_port_interval(0, Duration::from_millis(10))
+            .is_ok());
+        assert_eq!(listener.port_interval(0), Some(Duration::from_millis(10)));
+        thread::sleep(Duration::from_millis(200));
+        assert!(listener.poll().ok().unwrap().is_some());
+        assert!(listener.poll().ok().unwrap().is_some());
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_set_tick_interval_at_runtime() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            Some(Duration::from_secs(1)),
+            WorkerOptions::default(),
+        );
+        let count_ticks = |listener: &EventListener<MockEvent>| {
+            let mut ticks = 0;
+            while let Ok(Some(Event::Tick)) = listener.poll() {
+                ticks += 1;
+            }
+            ticks
+        };
+        assert_eq!(listener.poll_timeout(), Duration::from_millis(10));
+        assert_eq!(listener.tick_interval(), Some(Duration::from_secs(1)));
+        thread::sleep(Duration::from_millis(1100));
+        let slow = count_ticks(&listener);
+        assert!(listener
+            .set_tick_interval(Some(Duration::from_millis(100)))
+            .is_ok());
+        assert_eq!(listener.tick_interval(), Some(Duration::from_millis(100)));
+        thread::sleep(Duration::from_millis(1100));
+        let fast = count_ticks(&listener);
+        assert!(fast > slow);
+        // Disable ticks
+        assert!(listener.set_tick_interval(None).is_ok());
+        assert_eq!(listener.tick_interval(), None);
+        thread::sleep(Duration::from_millis(100));
+        count_ticks(&listener);
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_back_off_when_idle() {
+        let polls = Arc::new(Mutex::new(0));
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(CountingPoll {
+                    polls: Arc::clone(&polls),
+                }),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(100)),
+            WorkerOptions {
+                adaptive_poll: Some(AdaptivePoll::new(2, Duration::from_millis(250))),
+                ..Default::default()
+            },
+        );
+        thread::sleep(Duration::from_secs(1));
+        // Without backoff, port would have been polled ~100 times
+        assert!(*polls.lock().unwrap() < 30);
+        // Ticks are still sent on time
+        let mut ticks = 0;
+        while let Ok(Some(Event::Tick)) = listener.poll() {
+            ticks += 1;
+        }
+        assert!(ticks >= 9);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    #[ignore = "measures wall-clock jitter; run with `cargo test -- --ignored`"]
+    fn should_send_high_res_ticks() {
+        // Returns the mean deviation of the intervals between ticks from the tick interval
+        let jitter = |high_res_ticks: bool| {
+            let interval = Duration::from_millis(5);
+            let ticks = Arc::new(Mutex::new(Vec::new()));
+            let ticks_t = Arc::clone(&ticks);
+            let mut listener = EventListener::<MockEvent>::start(
+                vec![],
+                Duration::from_millis(1),
+                Some(interval),
+                WorkerOptions {
+                    high_res_ticks,
+                    on_tick: Some(Arc::new(Mutex::new(move || {
+                        ticks_t.lock().unwrap().push(std::time::Instant::now())
+                    }))),
+                    ..Default::default()
+                },
+            );
+            thread::sleep(Duration::from_millis(500));
+            assert!(listener.stop().is_ok());
+            let ticks = ticks.lock().unwrap();
+            assert!(ticks.len() > 50);
+            let deviation: Duration = ticks
+                .windows(2)
+                .map(|x| {
+                    let elapsed = x[1] - x[0];
+                    std::cmp::max(elapsed, interval) - std::cmp::min(elapsed, interval)
+                })
+                .sum();
+            deviation / (ticks.len() as u32 - 1)
+        };
+        let low_res = jitter(false);
+        let high_res = jitter(true);
+        assert!(high_res < Duration::from_millis(1));
+        assert!(high_res <= low_res);
+    }
+
+    #[test]
+    fn should_back_off_while_ticking() {
+        let run = |idle_ignores_ticks: bool| {
+            let polls = Arc::new(Mutex::new(0));
+            let mut listener = EventListener::<MockEvent>::start(
+                vec![Port::new(
+                    Box::new(CountingPoll {
+                        polls: Arc::clone(&polls),
+                    }),
+                    Duration::from_millis(10),
+                )],
+                Duration::from_millis(10),
+                Some(Duration::from_millis(10)),
+                WorkerOptions {
+                    adaptive_poll: Some(AdaptivePoll::new(2, Duration::from_millis(250))),
+                    idle_ignores_ticks,
+                    ..Default::default()
+                },
+            );
+            thread::sleep(Duration::from_secs(1));
+            assert!(listener.stop().is_ok());
+            let polls = *polls.lock().unwrap();
+            polls
+        };
+        // Ticks don't count as activity
+        assert!(run(true) < 30);
+        // Ticks restore the poll interval
+        assert!(run(false) > 50);
+    }
+
+    #[test]
+    fn should_filter_events() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_millis(100),
+            )],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(50)),
+            WorkerOptions {
+                filter: Some(Arc::new(|ev: &Event<MockEvent>| *ev != Event::Tick)),
+                ..Default::default()
+            },
+        );
+        thread::sleep(Duration::from_millis(300));
+        let mut events = 0;
+        while let Ok(Some(ev)) = listener.poll() {
+            assert_eq!(ev, Event::Keyboard(KeyEvent::from(Key::Enter)));
+            events += 1;
+        }
+        assert!(events > 0);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_iterate_over_events() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_secs(10),
+            )],
+            Duration::from_millis(10),
+            Some(Duration::from_secs(10)),
+            WorkerOptions::default(),
+        );
+        thread::sleep(Duration::from_millis(100));
+        let mut events = Vec::new();
+        for ev in &mut listener {
+            events.push(ev.ok().unwrap());
+        }
+        assert_eq!(events.len(), 2);
+        assert!(events.contains(&Event::Tick));
+        // No more events
+        assert!(listener.iter().next().is_none());
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_flush_pending_events() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_millis(5),
+            )],
+            Duration::from_millis(10),
+            Some(Duration::from_millis(5)),
+            WorkerOptions::default(),
+        );
+        thread::sleep(Duration::from_millis(100));
+        assert!(listener.pause().is_ok());
+        // Let the worker acknowledge the pause
+        thread::sleep(Duration::from_millis(50));
+        assert!(listener.flush() > 2);
+        assert_eq!(listener.flush(), 0);
+        assert!(listener.poll().ok().unwrap().is_none());
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_inject_events() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        let receiver = listener.subscribe();
+        assert!(listener.inject(Event::User(MockEvent::Foo)).is_ok());
+        assert!(listener.inject(Event::User(MockEvent::Bar)).is_ok());
+        // FIFO
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Bar))
+        );
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        // Broadcast to subscribers
+        assert_eq!(
+            receiver.try_recv().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert!(listener.stop().is_ok());
+        assert_eq!(
+            listener.inject(Event::User(MockEvent::Foo)).err().unwrap(),
+            ListenerError::ListenerDied
+        );
+        assert_eq!(listener.poll().err().unwrap(), ListenerError::ListenerDied);
+    }
+
+    #[test]
+    fn should_schedule_events_once() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        let t_start = std::time::Instant::now();
+        assert!(listener
+            .schedule_once(Duration::from_millis(200), Event::User(MockEvent::Foo))
+            .is_ok());
+        let cancelled = listener
+            .schedule_once(Duration::from_millis(100), Event::User(MockEvent::Bar))
+            .ok()
+            .unwrap();
+        assert_eq!(listener.cancel_scheduled(cancelled), true);
+        let mut events = Vec::new();
+        while t_start.elapsed() < Duration::from_millis(600) {
+            if let Some(ev) = listener.poll().ok().unwrap() {
+                events.push((t_start.elapsed(), ev));
+            }
+        }
+        // Sent once, after the delay
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1, Event::User(MockEvent::Foo));
+        assert!(events[0].0 >= Duration::from_millis(200));
+        assert!(events[0].0 < Duration::from_millis(400));
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_unpack_batches() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener
+            .injector
+            .send(ListenerMsg::Batch(vec![
+                Event::User(MockEvent::Foo),
+                Event::Tick,
+                Event::User(MockEvent::Bar),
+            ]))
+            .is_ok());
+        assert!(listener
+            .injector
+            .send(ListenerMsg::User(Event::User(MockEvent::None)))
+            .is_ok());
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert_eq!(listener.poll().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Bar))
+        );
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::None))
+        );
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        // Flush counts the events of batches
+        assert!(listener
+            .injector
+            .send(ListenerMsg::Batch(vec![
+                Event::Tick,
+                Event::Tick,
+                Event::Tick
+            ]))
+            .is_ok());
+        assert_eq!(listener.poll().ok().unwrap(), Some(Event::Tick));
+        assert!(listener.injector.send(ListenerMsg::Tick).is_ok());
+        assert_eq!(listener.flush(), 3);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_grab_events() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        let grabbed = Arc::new(Mutex::new(Vec::new()));
+        let grabbed_t = Arc::clone(&grabbed);
+        listener.grab(move |ev| {
+            grabbed_t.lock().unwrap().push(ev.clone());
+            match ev {
+                Event::User(MockEvent::Foo) => GrabResult::Consume,
+                _ => GrabResult::Pass,
+            }
+        });
+        let schedule = |ev| {
+            assert!(listener.schedule_once(Duration::ZERO, ev).is_ok());
+            thread::sleep(Duration::from_millis(50));
+        };
+        schedule(Event::User(MockEvent::Foo));
+        schedule(Event::User(MockEvent::Bar));
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Bar))
+        );
+        assert_eq!(listener.poll().ok().unwrap(), None);
+        assert_eq!(
+            grabbed.lock().unwrap().as_slice(),
+            &[Event::User(MockEvent::Foo), Event::User(MockEvent::Bar)]
+        );
+        // Release
+        listener.release_grab();
+        schedule(Event::User(MockEvent::Foo));
+        assert_eq!(
+            listener.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert_eq!(grabbed.lock().unwrap().len(), 2);
+        assert!(listener.stop().is_ok());
+    }
+
+    #[test]
+    fn should_grab_ticks_only_if_enabled() {
+        let run = |grab_ticks: bool| {
+            let mut listener = EventListener::<MockEvent>::start(
+                vec![],
+                Duration::from_millis(10),
+                Some(Duration::from_millis(10)),
+                WorkerOptions {
+                    grab_ticks,
+                    ..Default::default()
+                },
+            );
+            listener.grab(|_| GrabResult::Consume);
+            // Drop ticks sent before the grab
+            thread::sleep(Duration::from_millis(20));
+            listener.flush();
+            thread::sleep(Duration::from_millis(50));
+            let ticks = listener.flush();
+            assert!(listener.stop().is_ok());
+            ticks
+        };
+        assert!(run(false) > 0);
+        assert_eq!(run(true), 0);
+    }
+
+    #[test]
+    fn should_stop_with_timeout() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(listener.stop_timeout(Duration::from_secs(1)).is_ok());
+        assert!(listener.thread.is_none());
+        // Stopping again is ok
+        assert!(listener.stop_timeout(Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn should_fail_to_stop_with_timeout_if_port_is_stuck() {
+        let mut listener = EventListener::<MockEvent>::start(
+            vec![Port::new(
+                Box::new(BlockingPoll {
+                    block: Duration::from_secs(2),
+                }),
+                Duration::from_millis(10),
+            )],
+            Duration::from_millis(10),
+            None,
+            WorkerOptions::default(),
+        );
+        thread::sleep(Duration::from_millis(100));
+        assert!(matches!(
+            listener.stop_timeout(Duration::from_millis(100)),
+            Err(ListenerError::CouldNotStop)
+        ));
+        // Thread has been detached
+        assert!(listener.thread.is_none());
+        assert!(listener.stop().is_ok());
+    }
+
     #[test]
     #[should_panic]
     fn event_listener_with_poll_timeout_zero_should_panic() {
@@ -347,6 +1864,50 @@ mod test {
             vec![],
             Duration::from_millis(0),
             Some(Duration::from_secs(3)),
+            WorkerOptions::default(),
         );
     }
+
+    /// Poll the listener until an event is received, for at most 5 seconds
+    fn next_event(listener: &mut EventListener<MockEvent>) -> Event<MockEvent> {
+        for _ in 0..50 {
+            if let Some(ev) = listener.poll().ok().unwrap() {
+                return ev;
+            }
+        }
+        panic!("no event received");
+    }
+
+    /// Poll which blocks for `block` on each call
+    struct BlockingPoll {
+        block: Duration,
+    }
+
+    impl Poll<MockEvent> for BlockingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            thread::sleep(self.block);
+            Ok(None)
+        }
+    }
+
+    /// Poll which panics when polled
+    struct PanickingPoll;
+
+    impl Poll<MockEvent> for PanickingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            panic!("poll panicked");
+        }
+    }
+
+    /// Poll which never returns events and counts the times it has been polled
+    struct CountingPoll {
+        polls: Arc<Mutex<usize>>,
+    }
+
+    impl Poll<MockEvent> for CountingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            *self.polls.lock().unwrap() += 1;
+            Ok(None)
+        }
+    }
 }
@@ -0,0 +1,221 @@
+//! ## Paste
+//!
+//! a heuristic which groups bursts of printable keys into paste events
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::event::{Key, KeyEvent, KeyEventKind, KeyModifiers};
+use crate::Event;
+
+use std::time::{Duration, Instant};
+
+/// ## PasteBurst
+///
+/// Synthesizes `Event::Paste` for terminals which don't support bracketed paste.
+///
+/// When text is pasted, the terminal sends its characters as key events, much faster than a human can type.
+/// Printable keys (characters without modifiers other than shift, `Enter` and `Tab`) are held back while they keep
+/// coming within `max_gap` from each other; once the burst is over, it is reported as a single `Event::Paste`
+/// if it contains at least `min_len` keys, otherwise the keys are reported as they are.
+/// This is a best-effort heuristic: a very fast typist may trigger it, while a slow terminal may split a paste;
+/// moreover printable keys are delayed by up to `max_gap`.
+pub(crate) struct PasteBurst {
+    max_gap: Duration,
+    min_len: usize,
+    /// Keys of the current burst
+    keys: Vec<KeyEvent>,
+    /// Time the last key of the burst was received
+    last: Option<Instant>,
+}
+
+impl PasteBurst {
+    pub fn new(max_gap: Duration, min_len: usize) -> Self {
+        Self {
+            max_gap,
+            min_len,
+            keys: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// ### fresh
+    ///
+    /// Returns a new `PasteBurst` with the same configuration, but without any pending key
+    pub fn fresh(&self) -> Self {
+        Self::new(self.max_gap, self.min_len)
+    }
+
+    /// ### deadline
+    ///
+    /// Returns the time the current burst is over, if any
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.keys.is_empty() {
+            true => None,
+            false => self.last.map(|x| x + self.max_gap),
+        }
+    }
+
+    /// ### process_at
+    ///
+    /// Process the event received at `now`, returning the events to send
+    pub fn process_at<U>(&mut self, ev: Event<U>, now: Instant) -> Vec<Event<U>>
+    where
+        U: Eq + PartialEq + Clone + PartialOrd,
+    {
+        let mut events = self.expire_at(now);
+        match ev {
+            Event::Keyboard(key) if Self::char(&key).is_some() => {
+                self.keys.push(key);
+                self.last = Some(now);
+            }
+            ev => {
+                events.extend(self.flush());
+                events.push(ev);
+            }
+        }
+        events
+    }
+
+    /// ### expire_at
+    ///
+    /// If the current burst is over at `now`, returns its events
+    pub fn expire_at<U>(&mut self, now: Instant) -> Vec<Event<U>>
+    where
+        U: Eq + PartialEq + Clone + PartialOrd,
+    {
+        match self.deadline() {
+            Some(deadline) if deadline <= now => self.flush(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// ### flush
+    ///
+    /// Returns the events of the current burst
+    fn flush<U>(&mut self) -> Vec<Event<U>>
+    where
+        U: Eq + PartialEq + Clone + PartialOrd,
+    {
+        self.last = None;
+        let keys = std::mem::take(&mut self.keys);
+        if keys.is_empty() || keys.len() < self.min_len {
+            return keys.into_iter().map(Event::Keyboard).collect();
+        }
+        vec![Event::Paste(keys.iter().filter_map(Self::char).collect())]
+    }
+
+    /// ### char
+    ///
+    /// Returns the character which the key would insert if it's printable
+    fn char(key: &KeyEvent) -> Option<char> {
+        if key.kind != KeyEventKind::Press || !(KeyModifiers::SHIFT).contains(key.modifiers) {
+            return None;
+        }
+        match key.code {
+            Key::Char(ch) => Some(ch),
+            Key::Enter => Some('\n'),
+            Key::Tab => Some('\t'),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    fn ch(ch: char) -> Event<MockEvent> {
+        Event::Keyboard(KeyEvent::from(Key::Char(ch)))
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn should_group_burst_into_paste() {
+        let mut paste = PasteBurst::new(ms(5), 3);
+        let t = Instant::now();
+        assert!(paste.process_at(ch('h'), t).is_empty());
+        assert!(paste.process_at(ch('i'), t + ms(1)).is_empty());
+        assert!(paste
+            .process_at(
+                Event::<MockEvent>::Keyboard(KeyEvent::from(Key::Enter)),
+                t + ms(2)
+            )
+            .is_empty());
+        assert!(paste
+            .process_at(
+                Event::<MockEvent>::Keyboard(KeyEvent::new(Key::Char('Y'), KeyModifiers::SHIFT)),
+                t + ms(3)
+            )
+            .is_empty());
+        assert_eq!(paste.deadline(), Some(t + ms(8)));
+        assert!(paste.expire_at::<MockEvent>(t + ms(7)).is_empty());
+        assert_eq!(
+            paste.expire_at::<MockEvent>(t + ms(8)),
+            vec![Event::Paste(String::from("hi\nY"))]
+        );
+        assert_eq!(paste.deadline(), None);
+    }
+
+    #[test]
+    fn should_report_typed_keys_as_they_are() {
+        let mut paste = PasteBurst::new(ms(5), 3);
+        let t = Instant::now();
+        // Slow typing
+        assert!(paste.process_at(ch('a'), t).is_empty());
+        assert_eq!(paste.process_at(ch('b'), t + ms(100)), vec![ch('a')]);
+        assert_eq!(paste.expire_at::<MockEvent>(t + ms(200)), vec![ch('b')]);
+        // Short burst
+        assert!(paste.process_at(ch('a'), t + ms(300)).is_empty());
+        assert!(paste.process_at(ch('b'), t + ms(301)).is_empty());
+        assert_eq!(
+            paste.expire_at::<MockEvent>(t + ms(310)),
+            vec![ch('a'), ch('b')]
+        );
+    }
+
+    #[test]
+    fn should_flush_burst_on_other_events() {
+        let mut paste = PasteBurst::new(ms(5), 2);
+        let t = Instant::now();
+        let ctrl_c =
+            Event::<MockEvent>::Keyboard(KeyEvent::new(Key::Char('c'), KeyModifiers::CONTROL));
+        assert!(paste.process_at(ch('a'), t).is_empty());
+        assert!(paste.process_at(ch('b'), t).is_empty());
+        assert_eq!(
+            paste.process_at(ctrl_c.clone(), t + ms(1)),
+            vec![Event::Paste(String::from("ab")), ctrl_c]
+        );
+        assert_eq!(
+            paste.process_at(Event::<MockEvent>::Tick, t),
+            vec![Event::Tick]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! ## Broadcast
+//!
+//! a poll which receives events from a tokio broadcast channel
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::event::ListenerWarning;
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use tokio::sync::broadcast::{error::TryRecvError, Receiver};
+
+/// ## BroadcastPoll
+///
+/// A `Poll` which returns the events sent to a tokio broadcast channel, so that many async producers can feed
+/// the application. Each poll receives at most one event, without blocking.
+///
+/// Broadcast channels have a bounded capacity and never block the producers: once the channel is full, the oldest
+/// values are overwritten and a receiver which is behind loses them. When that happens, the next poll returns
+/// `Event::ListenerWarning(ListenerWarning::EventsDropped { skipped })`, with the amount of lost events, and then
+/// reception continues from the oldest event still in the channel. To avoid lagging, poll the port often enough
+/// or give the channel a larger capacity.
+///
+/// Once all the senders have been dropped and the remaining events have been received, the poll returns `None` forever.
+pub struct BroadcastPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    receiver: Receiver<Event<U>>,
+    closed: bool,
+}
+
+impl<U> BroadcastPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    pub fn new(receiver: Receiver<Event<U>>) -> Self {
+        Self {
+            receiver,
+            closed: false,
+        }
+    }
+
+    /// ### is_closed
+    ///
+    /// Returns whether the channel has been closed and all its events have been received
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl<U> Poll<U> for BroadcastPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        if self.closed {
+            return Ok(None);
+        }
+        match self.receiver.try_recv() {
+            Ok(ev) => Ok(Some(ev)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Lagged(skipped)) => Ok(Some(Event::ListenerWarning(
+                ListenerWarning::EventsDropped { skipped },
+            ))),
+            Err(TryRecvError::Closed) => {
+                self.closed = true;
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::broadcast;
+
+    #[test]
+    fn should_receive_broadcast_events() {
+        let (tx, rx) = broadcast::channel(8);
+        let mut poll = BroadcastPoll::new(rx);
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        tx.send(Event::User(MockEvent::Foo)).unwrap();
+        tx.send(Event::User(MockEvent::Bar)).unwrap();
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        // Close channel
+        tx.send(Event::Tick).unwrap();
+        drop(tx);
+        assert_eq!(poll.is_closed(), false);
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        assert_eq!(poll.is_closed(), true);
+        assert_eq!(poll.poll().ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_report_lagged_events() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut poll = BroadcastPoll::new(rx);
+        for ev in [
+            MockEvent::Foo,
+            MockEvent::Bar,
+            MockEvent::Foo,
+            MockEvent::Bar,
+        ] {
+            tx.send(Event::User(ev)).unwrap();
+        }
+        assert_eq!(
+            poll.poll().ok().unwrap(),
+            Some(Event::ListenerWarning(ListenerWarning::EventsDropped {
+                skipped: 2
+            }))
+        );
+        // Then the oldest events in the channel are received
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(poll.poll().ok().unwrap(), None);
+    }
+}
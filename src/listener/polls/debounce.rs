@@ -0,0 +1,131 @@
+//! ## Debounce
+//!
+//! a poll wrapper which debounces the events of a noisy source
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::time::{Duration, Instant};
+
+/// ## DebouncePoll
+///
+/// A `Poll` wrapper which debounces the events returned by the inner poll: an event is kept until no new event
+/// has arrived for `quiet`, then it is returned. Events received within the quiet period replace the pending one,
+/// so only the last event of a burst is returned.
+/// Errors returned by the inner poll are returned immediately.
+pub struct DebouncePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    poll: Box<dyn Poll<U>>,
+    quiet: Duration,
+    /// Latest event and the time it was received
+    pending: Option<(Event<U>, Instant)>,
+}
+
+impl<U> DebouncePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    pub fn new(poll: Box<dyn Poll<U>>, quiet: Duration) -> Self {
+        Self {
+            poll,
+            quiet,
+            pending: None,
+        }
+    }
+
+    /// ### poll_at
+    ///
+    /// Poll the inner poll at `now`
+    fn poll_at(&mut self, now: Instant) -> ListenerResult<Option<Event<U>>> {
+        if let Some(ev) = self.poll.poll()? {
+            self.pending = Some((ev, now));
+            return Ok(None);
+        }
+        match self.pending.take() {
+            Some((ev, t)) if now.duration_since(t) >= self.quiet => Ok(Some(ev)),
+            pending => {
+                self.pending = pending;
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<U> Poll<U> for DebouncePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.poll_at(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::listener::IteratorPoll;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    fn hello(s: &str) -> Event<MockEvent> {
+        Event::User(MockEvent::Hello(s.to_string()))
+    }
+
+    #[test]
+    fn should_return_only_last_event_of_burst() {
+        let inner = IteratorPoll::new(vec![hello("a"), hello("b"), hello("c")]);
+        let mut poll = DebouncePoll::new(Box::new(inner), Duration::from_millis(100));
+        let t = Instant::now();
+        // Burst
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        assert_eq!(
+            poll.poll_at(t + Duration::from_millis(10)).ok().unwrap(),
+            None
+        );
+        assert_eq!(
+            poll.poll_at(t + Duration::from_millis(20)).ok().unwrap(),
+            None
+        );
+        // Within quiet period
+        assert_eq!(
+            poll.poll_at(t + Duration::from_millis(50)).ok().unwrap(),
+            None
+        );
+        // Quiet period elapsed
+        assert_eq!(
+            poll.poll_at(t + Duration::from_millis(120)).ok().unwrap(),
+            Some(hello("c"))
+        );
+        assert_eq!(
+            poll.poll_at(t + Duration::from_millis(500)).ok().unwrap(),
+            None
+        );
+    }
+}
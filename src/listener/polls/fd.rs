@@ -0,0 +1,176 @@
+//! ## Fd
+//!
+//! a poll which reads from a raw file descriptor only when it is readable
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerError, ListenerResult, Poll};
+use crate::Event;
+
+use std::os::unix::io::RawFd;
+
+/// ## FdPort
+///
+/// A `Poll` which calls `read` only when the file descriptor `fd` is readable (e.g. a pipe or a socket).
+///
+/// Each time the worker polls the port, the readiness of the file descriptor is checked with `poll(2)`,
+/// without blocking. If the file descriptor is not readable, `read` is not called at all and no event is returned;
+/// otherwise `read` is called with the file descriptor and its result is returned.
+/// A hang up or an error condition on the file descriptor counts as readable, so that `read` can detect it,
+/// while an invalid file descriptor (e.g. closed) is reported as `ListenerError::PollFailed`.
+///
+/// The port is still scheduled by the worker according to its interval: readiness short-circuits the `read` call,
+/// not the interval, so the interval is the maximum latency between data becoming available and `read` being called.
+/// Since readiness is cheap to check, a short interval can be used without busy polling the reader.
+///
+/// The file descriptor is not owned by the port: it must stay open as long as the port is used, and it is not
+/// closed when the port is dropped.
+pub struct FdPort<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut(RawFd) -> ListenerResult<Option<Event<U>>> + Send,
+{
+    fd: RawFd,
+    read: F,
+}
+
+impl<U, F> FdPort<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut(RawFd) -> ListenerResult<Option<Event<U>>> + Send,
+{
+    /// ### new
+    ///
+    /// Instantiate a new `FdPort`, which calls `read` with `fd` each time `fd` is readable
+    pub fn new(fd: RawFd, read: F) -> Self {
+        Self { fd, read }
+    }
+
+    /// ### readable
+    ///
+    /// Returns whether the file descriptor is readable, without blocking.
+    /// Interrupted calls are retried
+    fn readable(&self) -> ListenerResult<bool> {
+        let mut pollfd = libc::pollfd {
+            fd: self.fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        loop {
+            // SAFETY: `pollfd` is a valid pointer to exactly one `pollfd` struct for the whole call
+            match unsafe { libc::poll(&mut pollfd, 1, 0) } {
+                -1 => {
+                    let err = std::io::Error::last_os_error();
+                    if err.kind() != std::io::ErrorKind::Interrupted {
+                        return Err(ListenerError::PollFailed(err.to_string()));
+                    }
+                }
+                0 => return Ok(false),
+                _ if pollfd.revents & libc::POLLNVAL != 0 => {
+                    return Err(ListenerError::PollFailed(format!(
+                        "invalid file descriptor {}",
+                        self.fd
+                    )))
+                }
+                _ => {
+                    return Ok(pollfd.revents & (libc::POLLIN | libc::POLLHUP | libc::POLLERR) != 0)
+                }
+            }
+        }
+    }
+}
+
+impl<U, F> Poll<U> for FdPort<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    F: FnMut(RawFd) -> ListenerResult<Option<Event<U>>> + Send,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        match self.readable()? {
+            true => (self.read)(self.fd),
+            false => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::os::unix::net::UnixStream;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn should_read_only_when_readable() {
+        let (mut tx, rx) = UnixStream::pair().unwrap();
+        let mut reader = rx.try_clone().unwrap();
+        let reads = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&reads);
+        let mut port = FdPort::new(rx.as_raw_fd(), move |_| {
+            *counter.lock().unwrap() += 1;
+            let mut buf = [0u8; 1];
+            reader
+                .read_exact(&mut buf)
+                .map_err(|err| ListenerError::PollFailed(err.to_string()))?;
+            Ok(Some(Event::User(match buf[0] {
+                b'f' => MockEvent::Foo,
+                _ => MockEvent::Bar,
+            })))
+        });
+        // Not readable
+        assert_eq!(port.poll().ok().unwrap(), None);
+        assert_eq!(port.poll().ok().unwrap(), None);
+        assert_eq!(*reads.lock().unwrap(), 0);
+        // Write data
+        tx.write_all(b"fb").unwrap();
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(port.poll().ok().unwrap(), None);
+        assert_eq!(*reads.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn should_read_on_hang_up() {
+        let (tx, rx) = UnixStream::pair().unwrap();
+        let mut port = FdPort::<MockEvent, _>::new(rx.as_raw_fd(), |_| Ok(Some(Event::Tick)));
+        assert_eq!(port.poll().ok().unwrap(), None);
+        drop(tx);
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::Tick));
+    }
+
+    #[test]
+    fn should_fail_on_invalid_fd() {
+        // Way above the file descriptors open by the tests
+        let mut port = FdPort::<MockEvent, _>::new(1 << 20, |_| Ok(Some(Event::Tick)));
+        assert_eq!(
+            port.poll().err().unwrap(),
+            ListenerError::PollFailed(String::from("invalid file descriptor 1048576"))
+        );
+    }
+}
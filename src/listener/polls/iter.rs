@@ -0,0 +1,95 @@
+//! ## Iter
+//!
+//! a poll which drains an iterator of events
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::marker::PhantomData;
+
+/// ## IteratorPoll
+///
+/// A `Poll` which returns the events yielded by an iterator.
+/// Each call to `poll()` advances the iterator by at most one item; once the iterator is exhausted,
+/// `poll()` always returns `Ok(None)`.
+/// This is useful to replay a precomputed sequence of events and to write deterministic tests.
+pub struct IteratorPoll<U, I>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    I: Iterator<Item = Event<U>> + Send,
+{
+    ghost: PhantomData<U>,
+    iter: I,
+}
+
+impl<U, I> IteratorPoll<U, I>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    I: Iterator<Item = Event<U>> + Send,
+{
+    pub fn new<T>(iter: T) -> Self
+    where
+        T: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            ghost: PhantomData,
+            iter: iter.into_iter(),
+        }
+    }
+}
+
+impl<U, I> Poll<U> for IteratorPoll<U, I>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    I: Iterator<Item = Event<U>> + Send,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        Ok(self.iter.next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_drain_iterator() {
+        let mut poll = IteratorPoll::new(vec![
+            Event::Tick,
+            Event::User(MockEvent::Foo),
+            Event::WindowResize(80, 24),
+        ]);
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::WindowResize(80, 24)));
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        assert_eq!(poll.poll().ok().unwrap(), None);
+    }
+}
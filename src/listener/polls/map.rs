@@ -0,0 +1,107 @@
+//! ## Map
+//!
+//! a poll wrapper which converts the events of a poll into another event type
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::marker::PhantomData;
+
+/// ## MapPoll
+///
+/// A `Poll` wrapper which converts each event returned by the inner poll with `f`, so that a poll
+/// of `Event<A>` (e.g. provided by a library) can be registered in a listener of `Event<B>`.
+/// `f` is given every event, not only user events, so it must convert the other variants too.
+/// Errors returned by the inner poll are returned unchanged.
+pub struct MapPoll<A, B, F>
+where
+    A: Eq + PartialEq + Clone + PartialOrd + Send,
+    B: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: Fn(Event<A>) -> Event<B> + Send,
+{
+    poll: Box<dyn Poll<A>>,
+    f: F,
+    ghost: PhantomData<fn() -> B>,
+}
+
+impl<A, B, F> MapPoll<A, B, F>
+where
+    A: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    B: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    F: Fn(Event<A>) -> Event<B> + Send,
+{
+    pub fn new(poll: Box<dyn Poll<A>>, f: F) -> Self {
+        Self {
+            poll,
+            f,
+            ghost: PhantomData,
+        }
+    }
+}
+
+impl<A, B, F> Poll<B> for MapPoll<A, B, F>
+where
+    A: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    B: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    F: Fn(Event<A>) -> Event<B> + Send,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<B>>> {
+        Ok(self.poll.poll()?.map(&self.f))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::listener::{IteratorPoll, Port};
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn should_convert_events() {
+        let inner = IteratorPoll::new(vec![Event::User(1u8), Event::Tick, Event::User(2u8)]);
+        let mut poll = MapPoll::new(Box::new(inner), |ev| match ev {
+            Event::User(n) => Event::User(MockEvent::Hello(n.to_string())),
+            Event::Tick => Event::Tick,
+            _ => Event::None,
+        });
+        assert_eq!(
+            poll.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Hello(String::from("1"))))
+        );
+        assert_eq!(poll.poll().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(
+            poll.poll().ok().unwrap(),
+            Some(Event::User(MockEvent::Hello(String::from("2"))))
+        );
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        // Can be registered in a port of the converted type
+        let _port: Port<MockEvent> = Port::new(Box::new(poll), Duration::from_millis(10));
+    }
+}
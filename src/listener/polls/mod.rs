@@ -0,0 +1,54 @@
+//! ## Polls
+//!
+//! general purpose implementations of the `Poll` trait
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+#[cfg(feature = "async-tokio")]
+mod broadcast;
+mod debounce;
+#[cfg(unix)]
+mod fd;
+mod iter;
+mod map;
+mod null;
+mod ratelimit;
+mod throttle;
+mod timer;
+#[cfg(feature = "watch")]
+mod watch;
+
+#[cfg(feature = "async-tokio")]
+pub use broadcast::BroadcastPoll;
+pub use debounce::DebouncePoll;
+#[cfg(unix)]
+pub use fd::FdPort;
+pub use iter::IteratorPoll;
+pub use map::MapPoll;
+pub use null::NullPoll;
+pub use ratelimit::RateLimitPort;
+pub use throttle::ThrottlePoll;
+pub use timer::TimerPoll;
+#[cfg(feature = "watch")]
+pub use watch::{FileChange, FileWatchPoll};
@@ -0,0 +1,86 @@
+//! ## Null
+//!
+//! a poll which never returns any event
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::marker::PhantomData;
+
+/// ## NullPoll
+///
+/// A `Poll` which never returns any event: `poll()` always returns `Ok(None)`.
+/// It's useful to register a port which is intentionally empty (e.g. a placeholder for an optional source)
+/// and in tests. It's also the simplest possible implementation of `Poll`.
+pub struct NullPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    ghost: PhantomData<fn() -> U>,
+}
+
+impl<U> NullPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    pub fn new() -> Self {
+        Self { ghost: PhantomData }
+    }
+}
+
+impl<U> Default for NullPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> Poll<U> for NullPoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_never_return_events() {
+        let mut poll = NullPoll::<MockEvent>::new();
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        assert_eq!(poll.poll().ok().unwrap(), None);
+    }
+}
@@ -0,0 +1,257 @@
+//! ## RateLimit
+//!
+//! a poll wrapper which caps the rate of an event source with a token bucket
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// ## RateLimitPort
+///
+/// A `Poll` wrapper which caps the rate of the events returned by the inner poll with a token bucket.
+///
+/// The bucket holds at most `capacity` tokens and is refilled with `refill` tokens per second; it starts full.
+/// Each event returned consumes a token, so bursts of up to `capacity` events are allowed,
+/// while the steady-state rate is at most `refill` events per second, regardless of the burst shape.
+///
+/// When no token is available, the events polled from the inner poll are dropped; if a queue has been set
+/// with `queue()`, they are queued instead, up to the queue limit, and returned once tokens become available.
+/// Events which don't fit in the queue are dropped.
+/// Errors returned by the inner poll are returned immediately.
+pub struct RateLimitPort<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    poll: Box<dyn Poll<U>>,
+    capacity: f64,
+    refill: f64,
+    tokens: f64,
+    last_refill: Option<Instant>,
+    queue_limit: usize,
+    queue: VecDeque<Event<U>>,
+}
+
+impl<U> RateLimitPort<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    /// ### new
+    ///
+    /// Instantiate a new `RateLimitPort` with a bucket of `capacity` tokens, refilled with `refill` tokens per second
+    pub fn new(poll: Box<dyn Poll<U>>, capacity: u32, refill: u32) -> Self {
+        Self {
+            poll,
+            capacity: capacity as f64,
+            refill: refill as f64,
+            tokens: capacity as f64,
+            last_refill: None,
+            queue_limit: 0,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// ### capacity
+    ///
+    /// Set the size of the bucket, which is the maximum burst of events allowed
+    pub fn capacity(mut self, capacity: u32) -> Self {
+        self.capacity = capacity as f64;
+        self.tokens = self.tokens.min(self.capacity);
+        self
+    }
+
+    /// ### refill
+    ///
+    /// Set the amount of tokens added to the bucket each second, which is the steady-state rate of events
+    pub fn refill(mut self, refill: u32) -> Self {
+        self.refill = refill as f64;
+        self
+    }
+
+    /// ### queue
+    ///
+    /// Queue up to `limit` events while no token is available, instead of dropping them.
+    /// A `limit` of `0` disables the queue
+    pub fn queue(mut self, limit: usize) -> Self {
+        self.queue_limit = limit;
+        self.queue.truncate(limit);
+        self
+    }
+
+    /// ### refill_at
+    ///
+    /// Refill the bucket with the tokens accumulated since the last refill
+    fn refill_at(&mut self, now: Instant) {
+        if let Some(last) = self.last_refill {
+            let elapsed = now.saturating_duration_since(last).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.refill).min(self.capacity);
+        }
+        self.last_refill = Some(now);
+    }
+
+    /// ### take_token
+    ///
+    /// Consume a token, if available
+    fn take_token(&mut self) -> bool {
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// ### poll_at
+    ///
+    /// Poll the inner poll at `now`
+    fn poll_at(&mut self, now: Instant) -> ListenerResult<Option<Event<U>>> {
+        self.refill_at(now);
+        let ev = self.poll.poll()?;
+        if self.queue_limit == 0 {
+            return Ok(ev.filter(|_| self.take_token()));
+        }
+        if let Some(ev) = ev {
+            if self.queue.len() < self.queue_limit {
+                self.queue.push_back(ev);
+            }
+        }
+        if !self.queue.is_empty() && self.take_token() {
+            Ok(self.queue.pop_front())
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<U> Poll<U> for RateLimitPort<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.poll_at(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::listener::IteratorPoll;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn hello(s: &str) -> Event<MockEvent> {
+        Event::User(MockEvent::Hello(s.to_string()))
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    /// Poll `poll` every `step` for `duration` starting from `t`, returning the amount of events returned
+    fn count_events(
+        poll: &mut RateLimitPort<MockEvent>,
+        t: Instant,
+        step: Duration,
+        duration: Duration,
+    ) -> usize {
+        let mut events = 0;
+        let mut elapsed = Duration::ZERO;
+        while elapsed < duration {
+            if poll.poll_at(t + elapsed).ok().unwrap().is_some() {
+                events += 1;
+            }
+            elapsed += step;
+        }
+        events
+    }
+
+    #[test]
+    fn should_allow_burst_up_to_capacity() {
+        let inner = IteratorPoll::new(std::iter::repeat(Event::<MockEvent>::Tick));
+        let mut poll = RateLimitPort::new(Box::new(inner), 5, 10);
+        let t = Instant::now();
+        // Burst at the same instant
+        for _ in 0..5 {
+            assert_eq!(poll.poll_at(t).ok().unwrap(), Some(Event::Tick));
+        }
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        // A token is refilled every 100ms
+        assert_eq!(poll.poll_at(t + ms(50)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), Some(Event::Tick));
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_cap_steady_state_rate() {
+        let inner = IteratorPoll::new(std::iter::repeat(Event::<MockEvent>::Tick));
+        let mut poll = RateLimitPort::new(Box::new(inner), 5, 10);
+        let t = Instant::now();
+        // Polling every 10ms for 2 seconds: burst (5) + 2s * 10/s
+        let events = count_events(&mut poll, t, ms(10), Duration::from_secs(2));
+        assert!((24..=25).contains(&events), "got {} events", events);
+        // Steady state: bucket is empty, so only the refill rate goes through
+        let events = count_events(
+            &mut poll,
+            t + Duration::from_secs(2),
+            ms(10),
+            Duration::from_secs(1),
+        );
+        assert!((9..=10).contains(&events), "got {} events", events);
+    }
+
+    #[test]
+    fn should_queue_events_up_to_limit() {
+        let inner = IteratorPoll::new(vec![hello("a"), hello("b"), hello("c"), hello("d")]);
+        let mut poll = RateLimitPort::new(Box::new(inner), 1, 10).queue(2);
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t).ok().unwrap(), Some(hello("a")));
+        // b and c are queued, d is dropped
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), Some(hello("b")));
+        assert_eq!(poll.poll_at(t + ms(150)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(200)).ok().unwrap(), Some(hello("c")));
+        assert_eq!(poll.poll_at(t + ms(300)).ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_configure_capacity_and_refill() {
+        let inner = IteratorPoll::new(std::iter::repeat(Event::<MockEvent>::Tick));
+        let mut poll = RateLimitPort::new(Box::new(inner), 10, 10)
+            .capacity(2)
+            .refill(100);
+        let t = Instant::now();
+        assert!(poll.poll_at(t).ok().unwrap().is_some());
+        assert!(poll.poll_at(t).ok().unwrap().is_some());
+        assert!(poll.poll_at(t).ok().unwrap().is_none());
+        assert!(poll.poll_at(t + ms(10)).ok().unwrap().is_some());
+    }
+}
@@ -0,0 +1,194 @@
+//! ## Throttle
+//!
+//! a poll wrapper which rate-limits an event source
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::time::{Duration, Instant};
+
+/// ## ThrottlePoll
+///
+/// A `Poll` wrapper which returns at most one event per `interval` from the inner poll.
+///
+/// - `leading` (default: `true`): the first event of a window is returned immediately
+/// - `trailing` (default: `false`): the latest event received while the window was closed is kept and returned
+///   as soon as the window opens again; otherwise these events are dropped
+///
+/// If both `leading` and `trailing` are disabled, the wrapper behaves as leading-only.
+/// Errors returned by the inner poll are returned immediately.
+pub struct ThrottlePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    poll: Box<dyn Poll<U>>,
+    interval: Duration,
+    leading: bool,
+    trailing: bool,
+    /// Start of the current window
+    window: Option<Instant>,
+    /// Latest event received while the window was closed
+    pending: Option<Event<U>>,
+}
+
+impl<U> ThrottlePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    pub fn new(poll: Box<dyn Poll<U>>, interval: Duration) -> Self {
+        Self {
+            poll,
+            interval,
+            leading: true,
+            trailing: false,
+            window: None,
+            pending: None,
+        }
+    }
+
+    /// ### leading
+    ///
+    /// Set whether the first event of a window should be returned immediately
+    pub fn leading(mut self, leading: bool) -> Self {
+        self.leading = leading;
+        self
+    }
+
+    /// ### trailing
+    ///
+    /// Set whether the latest event received while the window was closed should be returned once it opens
+    pub fn trailing(mut self, trailing: bool) -> Self {
+        self.trailing = trailing;
+        self
+    }
+
+    /// ### poll_at
+    ///
+    /// Poll the inner poll at `now`
+    fn poll_at(&mut self, now: Instant) -> ListenerResult<Option<Event<U>>> {
+        let ev = self.poll.poll()?;
+        let open = self
+            .window
+            .map(|x| now.duration_since(x) >= self.interval)
+            .unwrap_or(true);
+        let leading = self.leading || !self.trailing;
+        // Window has opened: return pending event and start a new window
+        if open && self.pending.is_some() {
+            self.window = Some(now);
+            let pending = self.pending.take();
+            if self.trailing {
+                self.pending = ev;
+            }
+            return Ok(pending);
+        }
+        match ev {
+            None => Ok(None),
+            Some(ev) if open => {
+                self.window = Some(now);
+                if leading {
+                    Ok(Some(ev))
+                } else {
+                    self.pending = Some(ev);
+                    Ok(None)
+                }
+            }
+            Some(ev) => {
+                if self.trailing {
+                    self.pending = Some(ev);
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<U> Poll<U> for ThrottlePoll<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.poll_at(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::listener::IteratorPoll;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    fn hello(s: &str) -> Event<MockEvent> {
+        Event::User(MockEvent::Hello(s.to_string()))
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn should_throttle_leading_only() {
+        let inner = IteratorPoll::new(vec![hello("a"), hello("b"), hello("c"), hello("d")]);
+        let mut poll = ThrottlePoll::new(Box::new(inner), ms(100));
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t).ok().unwrap(), Some(hello("a")));
+        assert_eq!(poll.poll_at(t + ms(10)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(20)).ok().unwrap(), None);
+        // b and c have been dropped
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), Some(hello("d")));
+        assert_eq!(poll.poll_at(t + ms(300)).ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_throttle_with_trailing() {
+        let inner = IteratorPoll::new(vec![hello("a"), hello("b"), hello("c")]);
+        let mut poll = ThrottlePoll::new(Box::new(inner), ms(100)).trailing(true);
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t).ok().unwrap(), Some(hello("a")));
+        assert_eq!(poll.poll_at(t + ms(10)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(20)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(50)).ok().unwrap(), None);
+        // Latest is returned once the window opens
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), Some(hello("c")));
+        assert_eq!(poll.poll_at(t + ms(300)).ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_throttle_trailing_only() {
+        let inner = IteratorPoll::new(vec![hello("a"), hello("b")]);
+        let mut poll = ThrottlePoll::new(Box::new(inner), ms(100))
+            .leading(false)
+            .trailing(true);
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(10)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(50)).ok().unwrap(), None);
+        assert_eq!(poll.poll_at(t + ms(100)).ok().unwrap(), Some(hello("b")));
+        assert_eq!(poll.poll_at(t + ms(300)).ok().unwrap(), None);
+    }
+}
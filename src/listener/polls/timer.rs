@@ -0,0 +1,173 @@
+//! ## Timer
+//!
+//! a poll which emits a user event periodically
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// ## TimerPoll
+///
+/// A `Poll` which returns the user event built by `event_fn` once every `interval`
+/// (e.g. to refresh data in the background, independently from the UI tick).
+///
+/// The timer starts on the first poll, so the first event is returned `interval` after it.
+/// Deadlines are scheduled at fixed intervals from the start, so that the cadence doesn't drift;
+/// if the poll is late for more than one interval, the missed events are skipped and only one event is returned.
+/// The port should be polled at least as frequently as `interval` (see `Port::timer()`).
+pub struct TimerPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut() -> U + Send,
+{
+    ghost: PhantomData<U>,
+    interval: Duration,
+    event_fn: F,
+    deadline: Option<Instant>,
+}
+
+impl<U, F> TimerPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut() -> U + Send,
+{
+    pub fn new(interval: Duration, event_fn: F) -> Self {
+        Self {
+            ghost: PhantomData,
+            interval,
+            event_fn,
+            deadline: None,
+        }
+    }
+
+    /// ### poll_at
+    ///
+    /// Poll the timer at `now`
+    fn poll_at(&mut self, now: Instant) -> Option<Event<U>> {
+        let deadline = match self.deadline {
+            Some(deadline) => deadline,
+            None => {
+                self.deadline = Some(now + self.interval);
+                return None;
+            }
+        };
+        if now < deadline {
+            return None;
+        }
+        // Schedule the next deadline, skipping the missed ones: it's the first one after `now`
+        let next = match self.interval.as_nanos() {
+            0 => deadline,
+            interval => {
+                let late = now.duration_since(deadline).as_nanos() % interval;
+                now + self.interval - Duration::from_nanos(late as u64)
+            }
+        };
+        self.deadline = Some(next);
+        Some(Event::User((self.event_fn)()))
+    }
+}
+
+impl<U, F> Poll<U> for TimerPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    F: FnMut() -> U + Send,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        Ok(self.poll_at(Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn should_emit_event_once_per_interval() {
+        let mut poll = TimerPoll::new(ms(100), || MockEvent::Foo);
+        let t = Instant::now();
+        // Timer starts on first poll
+        assert_eq!(poll.poll_at(t), None);
+        assert_eq!(poll.poll_at(t + ms(50)), None);
+        assert_eq!(poll.poll_at(t + ms(100)), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(poll.poll_at(t + ms(110)), None);
+        // Cadence doesn't drift, if polled late
+        assert_eq!(poll.poll_at(t + ms(205)), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(poll.poll_at(t + ms(299)), None);
+        assert_eq!(poll.poll_at(t + ms(300)), Some(Event::User(MockEvent::Foo)));
+        // Count events polling every 10ms for a second
+        let mut events = 0;
+        for step in 31..131 {
+            if poll.poll_at(t + ms(step * 10)).is_some() {
+                events += 1;
+            }
+        }
+        assert_eq!(events, 10);
+    }
+
+    #[test]
+    fn should_skip_missed_events() {
+        let mut counter = 0;
+        let mut poll = TimerPoll::new(ms(100), move || {
+            counter += 1;
+            MockEvent::Hello(counter.to_string())
+        });
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t), None);
+        // Late for more than 3 intervals
+        assert_eq!(
+            poll.poll_at(t + ms(450)),
+            Some(Event::User(MockEvent::Hello(String::from("1"))))
+        );
+        assert_eq!(poll.poll_at(t + ms(499)), None);
+        assert_eq!(
+            poll.poll_at(t + ms(500)),
+            Some(Event::User(MockEvent::Hello(String::from("2"))))
+        );
+    }
+
+    #[test]
+    fn should_skip_more_than_u32_max_missed_events() {
+        let mut poll = TimerPoll::new(ms(1), || MockEvent::Foo);
+        let t = Instant::now();
+        assert_eq!(poll.poll_at(t), None);
+        // Stalled for more than `u32::MAX` intervals
+        let stall = ms(u64::from(u32::MAX) + 2) + Duration::from_micros(500);
+        assert_eq!(poll.poll_at(t + stall), Some(Event::User(MockEvent::Foo)));
+        let next = t + ms(u64::from(u32::MAX) + 3);
+        assert_eq!(poll.poll_at(next - Duration::from_micros(1)), None);
+        assert_eq!(poll.poll_at(next), Some(Event::User(MockEvent::Foo)));
+    }
+}
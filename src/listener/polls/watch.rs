@@ -0,0 +1,375 @@
+//! ## Watch
+//!
+//! a poll which watches a filesystem path for changes
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::listener::{ListenerResult, Poll};
+use crate::Event;
+
+use std::collections::VecDeque;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+/// ## FileChange
+///
+/// Describes how a watched file has changed
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Hash)]
+pub enum FileChange {
+    /// The file has been created
+    Created,
+    /// The file has been modified (its modification time or size has changed)
+    Modified,
+    /// The file has been removed
+    Removed,
+}
+
+/// State of the watched file, as observed by the watcher: modification time and size; `None` if it doesn't exist
+type FileState = Option<(Option<SystemTime>, u64)>;
+
+/// ## FileWatchPoll
+///
+/// A `Poll` which watches a file and returns the user event built by `map` each time the file is created,
+/// modified or removed (e.g. to reload a config file or to tail a log file).
+///
+/// The file is watched by a dedicated thread, which checks it every `watch_interval` (100ms by default) and sends
+/// the changes to the poll over a channel, so the worker is never blocked by the filesystem; the thread is started
+/// on the first poll and stopped once the poll is dropped.
+/// Since editors often write files in chunks, successive modifications are coalesced: a `Modified` change is
+/// returned once no further modification has been observed for the coalesce window (twice the watch interval by
+/// default). The watcher reports at most one modification per interval, so a window shorter than the interval
+/// never merges anything.
+///
+/// The file is polled with `stat` rather than watched through the OS notification APIs (inotify, kqueue...),
+/// so changes are reported with a latency up to `watch_interval`, changes which don't affect the modification time
+/// or the size of the file within the same interval are missed, and a file replaced and restored between
+/// two checks is not reported at all.
+pub struct FileWatchPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut(FileChange) -> U + Send,
+{
+    ghost: PhantomData<U>,
+    path: PathBuf,
+    map: F,
+    watch_interval: Duration,
+    /// Coalesce window; `None` to use twice the watch interval
+    coalesce: Option<Duration>,
+    /// Changes reported by the watcher thread, once started
+    receiver: Option<mpsc::Receiver<(FileChange, Instant)>>,
+    /// Modification being coalesced, with the time of the last modification observed
+    pending: Option<Instant>,
+    /// Changes ready to be returned
+    ready: VecDeque<FileChange>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<U, F> FileWatchPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut(FileChange) -> U + Send,
+{
+    pub fn new<P: AsRef<Path>>(path: P, map: F) -> Self {
+        Self {
+            ghost: PhantomData,
+            path: path.as_ref().to_path_buf(),
+            map,
+            watch_interval: Duration::from_millis(100),
+            coalesce: None,
+            receiver: None,
+            pending: None,
+            ready: VecDeque::new(),
+            running: Arc::new(AtomicBool::new(true)),
+            thread: None,
+        }
+    }
+
+    /// ### watch_interval
+    ///
+    /// Set how often the watcher thread checks the file. Default: 100ms
+    pub fn watch_interval(mut self, interval: Duration) -> Self {
+        self.watch_interval = interval;
+        self
+    }
+
+    /// ### coalesce
+    ///
+    /// Set the time window within which successive modifications are reported as a single `Modified` change.
+    /// The window should be longer than the watch interval, since the watcher reports at most one modification
+    /// per interval. Default: twice the watch interval
+    pub fn coalesce(mut self, window: Duration) -> Self {
+        self.coalesce = Some(window);
+        self
+    }
+
+    /// ### coalesce_window
+    ///
+    /// Returns the coalesce window in use
+    fn coalesce_window(&self) -> Duration {
+        self.coalesce.unwrap_or(self.watch_interval * 2)
+    }
+
+    /// ### watch
+    ///
+    /// Start the watcher thread, if not started yet
+    fn watch(&mut self) {
+        if self.receiver.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let path = self.path.clone();
+        let interval = self.watch_interval;
+        let running = Arc::clone(&self.running);
+        // Take the initial state before returning, so that no change after the first poll is missed
+        let mut prev = Self::state(&path);
+        self.thread = Some(thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                thread::sleep(interval);
+                let curr = Self::state(&path);
+                if let Some(change) = Self::change(&prev, &curr) {
+                    // Poll has been dropped
+                    if tx.send((change, Instant::now())).is_err() {
+                        break;
+                    }
+                }
+                prev = curr;
+            }
+        }));
+        self.receiver = Some(rx);
+    }
+
+    /// ### state
+    ///
+    /// Returns the current state of the file at `path`
+    fn state(path: &Path) -> FileState {
+        fs::metadata(path)
+            .ok()
+            .map(|x| (x.modified().ok(), x.len()))
+    }
+
+    /// ### change
+    ///
+    /// Returns the change between two states of the file, if any
+    fn change(prev: &FileState, curr: &FileState) -> Option<FileChange> {
+        match (prev, curr) {
+            (None, Some(_)) => Some(FileChange::Created),
+            (Some(_), None) => Some(FileChange::Removed),
+            (Some(prev), Some(curr)) if prev != curr => Some(FileChange::Modified),
+            _ => None,
+        }
+    }
+
+    /// ### receive
+    ///
+    /// Handle a change observed at `at`, coalescing successive modifications
+    fn receive(&mut self, change: FileChange, at: Instant) {
+        match change {
+            FileChange::Modified => {
+                self.pending = Some(at);
+            }
+            change => {
+                if self.pending.take().is_some() {
+                    self.ready.push_back(FileChange::Modified);
+                }
+                self.ready.push_back(change);
+            }
+        }
+    }
+
+    /// ### poll_at
+    ///
+    /// Returns the next change at `now`, if any
+    fn poll_at(&mut self, now: Instant) -> Option<FileChange> {
+        let changes: Vec<(FileChange, Instant)> = self
+            .receiver
+            .as_ref()
+            .map(|x| x.try_iter().collect())
+            .unwrap_or_default();
+        for (change, at) in changes {
+            self.receive(change, at);
+        }
+        // Report the modification once the window has elapsed since the last one
+        if let Some(at) = self.pending {
+            if now.saturating_duration_since(at) >= self.coalesce_window() {
+                self.pending = None;
+                self.ready.push_back(FileChange::Modified);
+            }
+        }
+        self.ready.pop_front()
+    }
+}
+
+impl<U, F> Poll<U> for FileWatchPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+    F: FnMut(FileChange) -> U + Send,
+{
+    fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
+        self.watch();
+        Ok(self
+            .poll_at(Instant::now())
+            .map(|change| Event::User((self.map)(change))))
+    }
+}
+
+impl<U, F> Drop for FileWatchPoll<U, F>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+    F: FnMut(FileChange) -> U + Send,
+{
+    fn drop(&mut self) {
+        // The thread terminates on its next check; don't wait for it
+        self.running.store(false, Ordering::Relaxed);
+        self.thread.take();
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::NamedTempFile;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn poll() -> FileWatchPoll<MockEvent, impl FnMut(FileChange) -> MockEvent + Send> {
+        FileWatchPoll::new("/dev/null", |change| {
+            MockEvent::Hello(format!("{:?}", change))
+        })
+    }
+
+    #[test]
+    fn should_detect_changes() {
+        let t = SystemTime::now();
+        let state = Some((Some(t), 10));
+        assert_eq!(
+            FileWatchPoll::<MockEvent, fn(FileChange) -> MockEvent>::change(&None, &state),
+            Some(FileChange::Created)
+        );
+        assert_eq!(
+            FileWatchPoll::<MockEvent, fn(FileChange) -> MockEvent>::change(&state, &None),
+            Some(FileChange::Removed)
+        );
+        assert_eq!(
+            FileWatchPoll::<MockEvent, fn(FileChange) -> MockEvent>::change(
+                &state,
+                &Some((Some(t), 12))
+            ),
+            Some(FileChange::Modified)
+        );
+        assert_eq!(
+            FileWatchPoll::<MockEvent, fn(FileChange) -> MockEvent>::change(&state, &state),
+            None
+        );
+        assert_eq!(
+            FileWatchPoll::<MockEvent, fn(FileChange) -> MockEvent>::change(&None, &None),
+            None
+        );
+    }
+
+    #[test]
+    fn should_coalesce_modifications() {
+        let mut poll = poll().watch_interval(ms(10)).coalesce(ms(50));
+        let t = Instant::now();
+        poll.receive(FileChange::Created, t);
+        poll.receive(FileChange::Modified, t + ms(10));
+        poll.receive(FileChange::Modified, t + ms(20));
+        poll.receive(FileChange::Modified, t + ms(40));
+        assert_eq!(poll.poll_at(t + ms(40)), Some(FileChange::Created));
+        // Window elapses since the last modification
+        assert_eq!(poll.poll_at(t + ms(80)), None);
+        assert_eq!(poll.poll_at(t + ms(90)), Some(FileChange::Modified));
+        assert_eq!(poll.poll_at(t + ms(200)), None);
+        // Removal flushes the pending modification
+        poll.receive(FileChange::Modified, t + ms(300));
+        poll.receive(FileChange::Removed, t + ms(310));
+        assert_eq!(poll.poll_at(t + ms(310)), Some(FileChange::Modified));
+        assert_eq!(poll.poll_at(t + ms(310)), Some(FileChange::Removed));
+        assert_eq!(poll.poll_at(t + ms(310)), None);
+    }
+
+    #[test]
+    fn should_coalesce_modifications_with_default_settings() {
+        assert_eq!(poll().coalesce_window(), ms(200));
+        assert_eq!(poll().watch_interval(ms(30)).coalesce_window(), ms(60));
+        // The watcher reports a modification at each check, i.e. every 100ms
+        let mut poll = poll();
+        let t = Instant::now();
+        poll.receive(FileChange::Modified, t);
+        assert_eq!(poll.poll_at(t + ms(100)), None);
+        poll.receive(FileChange::Modified, t + ms(100));
+        assert_eq!(poll.poll_at(t + ms(200)), None);
+        poll.receive(FileChange::Modified, t + ms(200));
+        assert_eq!(poll.poll_at(t + ms(300)), None);
+        // Reported once no modification has been observed for two checks
+        assert_eq!(poll.poll_at(t + ms(400)), Some(FileChange::Modified));
+        assert_eq!(poll.poll_at(t + ms(500)), None);
+    }
+
+    #[test]
+    fn should_watch_file() {
+        let temp = NamedTempFile::new().expect("Failed to open tempfile");
+        let path = temp.path();
+        let mut poll = FileWatchPoll::new(path, |change| MockEvent::Hello(format!("{:?}", change)))
+            .watch_interval(ms(10))
+            .coalesce(ms(20));
+        assert_eq!(poll.poll().ok().unwrap(), None);
+        let mut wait_for = |expected: &str| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while Instant::now() < deadline {
+                if let Some(ev) = poll.poll().ok().unwrap() {
+                    return ev;
+                }
+                thread::sleep(ms(5));
+            }
+            panic!("{} not reported", expected);
+        };
+        fs::write(path, "hello").unwrap();
+        assert_eq!(
+            wait_for("Modified"),
+            Event::User(MockEvent::Hello(String::from("Modified")))
+        );
+        fs::remove_file(path).unwrap();
+        assert_eq!(
+            wait_for("Removed"),
+            Event::User(MockEvent::Hello(String::from("Removed")))
+        );
+        fs::write(path, "hello, world!").unwrap();
+        assert_eq!(
+            wait_for("Created"),
+            Event::User(MockEvent::Hello(String::from("Created")))
+        );
+    }
+}
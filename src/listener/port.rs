@@ -25,11 +25,50 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{Event, ListenerResult, Poll};
+use super::{Event, EventFilter, IteratorPoll, ListenerResult, NullPoll, Poll, PortId, TimerPoll};
 
+use std::cmp::min;
 use std::ops::Add;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// ## PortFactory
+///
+/// A function which builds the poll of a port; used to rebuild the port when the listener is recreated
+pub type PortFactory<U> = Arc<dyn Fn() -> Box<dyn Poll<U>> + Send + Sync>;
+
+/// ## PortKind
+///
+/// Describes what a port is polled for, so that ports can be paused by kind (see `EventListener::pause_kind()`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortKind {
+    /// A port reporting user input (e.g. the input listener of the backend)
+    Input,
+    /// A port reporting events from background work (e.g. I/O, notifications).
+    /// This is the kind of ports which don't declare one
+    #[default]
+    Background,
+    /// A port raising events at regular intervals (e.g. `Port::timer()`)
+    Timer,
+}
+
+/// ## PortStatus
+///
+/// A point-in-time snapshot of the status of a port registered in the event listener
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortStatus {
+    /// Id of the port
+    pub id: PortId,
+    /// Current poll interval
+    pub interval: Duration,
+    /// Whether the event listener is paused
+    pub paused: bool,
+    /// Last time the port was polled, if ever
+    pub last_poll: Option<Instant>,
+    /// Amount of events returned by the port so far
+    pub events: u64,
+}
+
 /// ## Port
 ///
 /// A port is a wrapper around the poll trait object, which also defines an interval, which defines
@@ -42,6 +81,11 @@ where
     poll: Box<dyn Poll<U>>,
     interval: Duration,
     next_poll: Instant,
+    last_poll: Option<Instant>,
+    factory: Option<PortFactory<U>>,
+    filter: Option<EventFilter<U>>,
+    events: u64,
+    kind: PortKind,
 }
 
 impl<U> Port<U>
@@ -56,6 +100,75 @@ where
             poll,
             interval,
             next_poll: Instant::now(),
+            last_poll: None,
+            factory: None,
+            filter: None,
+            events: 0,
+            kind: PortKind::default(),
+        }
+    }
+
+    /// ### builder
+    ///
+    /// Returns a `PortBuilder` to configure a new `Port` polling `poll`
+    pub fn builder(poll: Box<dyn Poll<U>>) -> PortBuilder<U> {
+        PortBuilder::new(poll)
+    }
+
+    /// ### from_factory
+    ///
+    /// Define a new `Port` whose poll is built by `factory`.
+    /// The factory is kept, so that the port can be rebuilt (see `EventListenerCfg::port_factory`)
+    pub fn from_factory(factory: PortFactory<U>, interval: Duration) -> Self {
+        let mut port = Self::new(factory(), interval);
+        port.factory = Some(factory);
+        port
+    }
+
+    /// ### from_iter
+    ///
+    /// Define a new `Port` which returns the events yielded by `iter`, one per poll (see `IteratorPoll`)
+    pub fn from_iter<I>(iter: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = Event<U>>,
+        I::IntoIter: Send + 'static,
+    {
+        Self::new(Box::new(IteratorPoll::new(iter)), interval)
+    }
+
+    /// ### null
+    ///
+    /// Define a new `Port` which never returns any event (see `NullPoll`)
+    pub fn null(interval: Duration) -> Self {
+        Self::new(Box::new(NullPoll::new()), interval)
+    }
+
+    /// ### timer
+    ///
+    /// Define a new `Port` which returns the user event built by `event_fn` once every `interval` (see `TimerPoll`).
+    /// The port is polled every `interval` too
+    pub fn timer<F>(interval: Duration, event_fn: F) -> Self
+    where
+        F: FnMut() -> U + Send + 'static,
+    {
+        let mut port = Self::new(Box::new(TimerPoll::new(interval, event_fn)), interval);
+        port.kind = PortKind::Timer;
+        port
+    }
+
+    /// ### factory
+    ///
+    /// Returns the factory of the port, if it was built from a factory
+    pub fn factory(&self) -> Option<&PortFactory<U>> {
+        self.factory.as_ref()
+    }
+
+    /// ### rebuild
+    ///
+    /// Replace the poll with a new one built by the factory, if the port was built from a factory
+    pub(crate) fn rebuild(&mut self) {
+        if let Some(factory) = self.factory.as_ref() {
+            self.poll = factory();
         }
     }
 
@@ -66,6 +179,37 @@ where
         &self.interval
     }
 
+    /// ### kind
+    ///
+    /// Returns the kind of the port; `PortKind::Background` unless set otherwise
+    pub fn kind(&self) -> PortKind {
+        self.kind
+    }
+
+    /// ### set_kind
+    ///
+    /// Set the kind of the port
+    pub fn set_kind(&mut self, kind: PortKind) {
+        self.kind = kind;
+    }
+
+    /// ### set_interval
+    ///
+    /// Set a new interval for the current `Port`.
+    /// If the next poll was scheduled later than `now + interval`, it is brought forward,
+    /// so that shortening the interval takes effect immediately.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.set_interval_at(interval, Instant::now());
+    }
+
+    /// ### set_interval_at
+    ///
+    /// Set a new interval for the current `Port`, at `now`
+    pub(crate) fn set_interval_at(&mut self, interval: Duration, now: Instant) {
+        self.interval = interval;
+        self.next_poll = min(self.next_poll, now.add(interval));
+    }
+
     /// ### next_poll
     ///
     /// Returns the time of the next poll for this listener
@@ -73,25 +217,152 @@ where
         self.next_poll
     }
 
+    /// ### last_poll
+    ///
+    /// Returns the time of the last poll for this listener, if it has ever been polled
+    pub fn last_poll(&self) -> Option<Instant> {
+        self.last_poll
+    }
+
     /// ### should_poll
     ///
     /// Returns whether next poll is now or in the past
     pub fn should_poll(&self) -> bool {
-        self.next_poll <= Instant::now()
+        self.should_poll_at(Instant::now())
+    }
+
+    /// ### should_poll_at
+    ///
+    /// Returns whether next poll is at `now` or before
+    pub(crate) fn should_poll_at(&self, now: Instant) -> bool {
+        self.next_poll <= now
     }
 
     /// ### poll
     ///
     /// Calls poll on the inner `Poll` trait object.
+    /// If the port has a filter, events rejected by the filter are discarded.
     pub fn poll(&mut self) -> ListenerResult<Option<Event<U>>> {
-        self.poll.poll()
+        let ev = match (self.poll.poll()?, self.filter.as_ref()) {
+            (Some(ev), Some(filter)) if !filter(&ev) => None,
+            (ev, _) => ev,
+        };
+        if ev.is_some() {
+            self.events += 1;
+        }
+        Ok(ev)
+    }
+
+    /// ### events
+    ///
+    /// Returns the amount of events returned by the port so far
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    /// ### status
+    ///
+    /// Returns the status of the port, which has been registered with `id`
+    pub fn status(&self, id: PortId) -> PortStatus {
+        PortStatus {
+            id,
+            interval: self.interval,
+            paused: false,
+            last_poll: self.last_poll,
+            events: self.events,
+        }
     }
 
     /// ### calc_next_poll
     ///
     /// Calculate the next poll (t_now + interval)
     pub fn calc_next_poll(&mut self) {
-        self.next_poll = Instant::now().add(self.interval);
+        self.calc_next_poll_at(Instant::now());
+    }
+
+    /// ### calc_next_poll_at
+    ///
+    /// Calculate the next poll (now + interval), having polled at `now`
+    pub(crate) fn calc_next_poll_at(&mut self, now: Instant) {
+        self.last_poll = Some(now);
+        self.next_poll = now.add(self.interval);
+    }
+
+    /// ### schedule_at
+    ///
+    /// Schedule the next poll at `next_poll`
+    pub(crate) fn schedule_at(&mut self, next_poll: Instant) {
+        self.next_poll = next_poll;
+    }
+}
+
+/// ## PortBuilder
+///
+/// A builder to configure a `Port` in a single chain. Get one with `Port::builder()`.
+///
+/// If no interval is set, the port is polled every `PortBuilder::DEFAULT_INTERVAL`.
+pub struct PortBuilder<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    poll: Box<dyn Poll<U>>,
+    interval: Duration,
+    filter: Option<EventFilter<U>>,
+    kind: PortKind,
+}
+
+impl<U> PortBuilder<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
+{
+    /// Interval of the port, if not set with `interval()`
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(10);
+
+    fn new(poll: Box<dyn Poll<U>>) -> Self {
+        Self {
+            poll,
+            interval: Self::DEFAULT_INTERVAL,
+            filter: None,
+            kind: PortKind::default(),
+        }
+    }
+
+    /// ### interval
+    ///
+    /// Set the amount of time between each poll of the port
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// ### filter
+    ///
+    /// Set a filter for the events polled by the port: only events for which `filter` returns `true` are reported.
+    /// Unlike `EventListenerCfg::filter`, the filter applies only to the events of this port.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Event<U>) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// ### kind
+    ///
+    /// Set the kind of the port. Default: `PortKind::Background`
+    pub fn kind(mut self, kind: PortKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// ### build
+    ///
+    /// Build the `Port`
+    pub fn build(self) -> Port<U> {
+        let mut port = Port::new(self.poll, self.interval);
+        port.filter = self.filter;
+        port.kind = self.kind;
+        port
     }
 }
 
@@ -110,9 +381,74 @@ mod test {
             Port::<MockEvent>::new(Box::new(MockPoll::default()), Duration::from_secs(5));
         assert!(listener.next_poll() <= Instant::now());
         assert_eq!(listener.should_poll(), true);
+        assert!(listener.last_poll().is_none());
         assert!(listener.poll().ok().unwrap().is_some());
         listener.calc_next_poll();
+        assert!(listener.last_poll().is_some());
         assert_eq!(listener.should_poll(), false);
         assert_eq!(*listener.interval(), Duration::from_secs(5));
     }
+
+    #[test]
+    fn should_create_port_from_iter() {
+        let mut port = Port::<MockEvent>::from_iter(
+            vec![Event::Tick, Event::User(MockEvent::Bar)],
+            Duration::from_millis(10),
+        );
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(port.poll().ok().unwrap(), None);
+    }
+
+    #[test]
+    fn should_create_null_port() {
+        let mut port = Port::<MockEvent>::null(Duration::from_secs(1));
+        assert_eq!(*port.interval(), Duration::from_secs(1));
+        assert_eq!(port.poll().ok().unwrap(), None);
+        assert_eq!(port.events(), 0);
+    }
+
+    #[test]
+    fn should_create_timer_port() {
+        let mut port = Port::<MockEvent>::timer(Duration::from_millis(10), || MockEvent::Bar);
+        assert_eq!(*port.interval(), Duration::from_millis(10));
+        assert_eq!(port.poll().ok().unwrap(), None);
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+    }
+
+    #[test]
+    fn should_build_port() {
+        let mut port = Port::<MockEvent>::builder(Box::new(IteratorPoll::new(vec![
+            Event::Tick,
+            Event::User(MockEvent::Bar),
+            Event::User(MockEvent::Foo),
+        ])))
+        .interval(Duration::from_secs(1))
+        .filter(|ev| *ev != Event::Tick)
+        .build();
+        assert_eq!(*port.interval(), Duration::from_secs(1));
+        // Tick is discarded
+        assert_eq!(port.poll().ok().unwrap(), None);
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(port.poll().ok().unwrap(), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(port.events(), 2);
+        // Default interval
+        let port = Port::<MockEvent>::builder(Box::new(MockPoll::default())).build();
+        assert_eq!(*port.interval(), PortBuilder::<MockEvent>::DEFAULT_INTERVAL);
+    }
+
+    #[test]
+    fn should_set_port_interval() {
+        let mut port =
+            Port::<MockEvent>::new(Box::new(MockPoll::default()), Duration::from_secs(60));
+        port.calc_next_poll();
+        port.set_interval(Duration::from_millis(10));
+        assert_eq!(*port.interval(), Duration::from_millis(10));
+        assert!(port.next_poll() <= Instant::now().add(Duration::from_millis(10)));
+        // Longer interval doesn't postpone the scheduled poll
+        let next_poll = port.next_poll();
+        port.set_interval(Duration::from_secs(60));
+        assert_eq!(port.next_poll(), next_poll);
+    }
 }
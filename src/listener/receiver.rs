@@ -0,0 +1,178 @@
+//! ## Receiver
+//!
+//! An additional consumer of the events produced by the event listener
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{Event, ListenerError, ListenerResult};
+
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// ## Subscribers
+///
+/// The senders of the event receivers subscribed to the listener; `None` once the worker has terminated
+pub(super) type Subscribers<U> = Arc<Mutex<Option<Vec<mpsc::Sender<Event<U>>>>>>;
+
+/// ## EventReceiver
+///
+/// An additional consumer of the events produced by the event listener (see `Application::event_receiver()`).
+/// Each receiver gets every event sent by the listener (ticks included) from the moment it has been created,
+/// independently from the other receivers and from the application. Errors are reported only by the listener `poll()`.
+///
+/// Cloning a receiver subscribes a new independent receiver to the listener.
+///
+/// > Events are buffered in an unbounded channel until they are received, so a slow (or forgotten) receiver
+/// > keeps growing its buffer: drop receivers you don't use anymore, to unsubscribe them.
+pub struct EventReceiver<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    recv: mpsc::Receiver<Event<U>>,
+    subscribers: Subscribers<U>,
+}
+
+impl<U> EventReceiver<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    /// ### subscribe
+    ///
+    /// Subscribe a new receiver to `subscribers`
+    pub(super) fn subscribe(subscribers: Subscribers<U>) -> Self {
+        let (sender, recv) = mpsc::channel();
+        if let Ok(mut lock) = subscribers.lock() {
+            // If the worker has terminated, sender is dropped and the receiver is disconnected
+            if let Some(senders) = lock.as_mut() {
+                senders.push(sender);
+            }
+        }
+        Self { recv, subscribers }
+    }
+
+    /// ### recv_timeout
+    ///
+    /// Wait at most `timeout` for the next event.
+    /// Returns `ListenerError::ListenerDied` once the listener has terminated and all the events have been received
+    pub fn recv_timeout(&self, timeout: Duration) -> ListenerResult<Option<Event<U>>> {
+        match self.recv.recv_timeout(timeout) {
+            Ok(ev) => Ok(Some(ev)),
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(None),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(ListenerError::ListenerDied),
+        }
+    }
+
+    /// ### try_recv
+    ///
+    /// Returns the next event, if any, without blocking.
+    /// Returns `ListenerError::ListenerDied` once the listener has terminated and all the events have been received
+    pub fn try_recv(&self) -> ListenerResult<Option<Event<U>>> {
+        match self.recv.try_recv() {
+            Ok(ev) => Ok(Some(ev)),
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => Err(ListenerError::ListenerDied),
+        }
+    }
+}
+
+impl<U> Clone for EventReceiver<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn clone(&self) -> Self {
+        Self::subscribe(Arc::clone(&self.subscribers))
+    }
+}
+
+/// ### broadcast
+///
+/// Send `ev` to all the subscribers, unsubscribing the receivers which have been dropped
+pub(super) fn broadcast<U>(subscribers: &Subscribers<U>, ev: &Event<U>)
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    if let Ok(mut lock) = subscribers.lock() {
+        if let Some(senders) = lock.as_mut() {
+            senders.retain(|x| x.send(ev.clone()).is_ok());
+        }
+    }
+}
+
+/// ### close
+///
+/// Disconnect all the subscribers; receivers subscribed afterwards are disconnected immediately
+pub(super) fn close<U>(subscribers: &Subscribers<U>)
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    if let Ok(mut lock) = subscribers.lock() {
+        *lock = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_broadcast_events_to_receivers() {
+        let subscribers: Subscribers<MockEvent> = Arc::new(Mutex::new(Some(Vec::new())));
+        let a = EventReceiver::subscribe(Arc::clone(&subscribers));
+        broadcast(&subscribers, &Event::Tick);
+        let b = a.clone();
+        broadcast(&subscribers, &Event::User(MockEvent::Foo));
+        assert_eq!(a.try_recv().ok().unwrap(), Some(Event::Tick));
+        assert_eq!(
+            a.try_recv().ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert_eq!(a.try_recv().ok().unwrap(), None);
+        // b has been subscribed after the tick
+        assert_eq!(
+            b.recv_timeout(Duration::from_millis(10)).ok().unwrap(),
+            Some(Event::User(MockEvent::Foo))
+        );
+        assert_eq!(
+            b.recv_timeout(Duration::from_millis(10)).ok().unwrap(),
+            None
+        );
+        // Dropped receivers are unsubscribed
+        drop(b);
+        broadcast(&subscribers, &Event::Tick);
+        assert_eq!(subscribers.lock().unwrap().as_ref().unwrap().len(), 1);
+        // Close
+        close(&subscribers);
+        assert_eq!(a.try_recv().ok().unwrap(), Some(Event::Tick));
+        assert!(matches!(a.try_recv(), Err(ListenerError::ListenerDied)));
+        let c = a.clone();
+        assert!(matches!(
+            c.recv_timeout(Duration::from_millis(10)),
+            Err(ListenerError::ListenerDied)
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+//! ## Repeat
+//!
+//! This module exposes the key repeat synthesizer used by the worker
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::event::{KeyEvent, KeyEventKind};
+
+use std::time::{Duration, Instant};
+
+/// ## KeyRepeat
+///
+/// Synthesizes `KeyEventKind::Repeat` for terminals which don't report key repeats.
+///
+/// When a key is held, the OS sends the same key again after `delay` and then every `interval`.
+/// Since the terminal doesn't tell us whether the key has been released in the meantime, an identical key event
+/// received within `delay` from the previous one (or within `interval` once repeating) is considered a repeat.
+/// This is an heuristic: pressing the same key again very quickly may be reported as a repeat too.
+pub(crate) struct KeyRepeat {
+    delay: Duration,
+    interval: Duration,
+    /// Last key received, when it was received and whether it's repeating
+    last: Option<(KeyEvent, Instant, bool)>,
+}
+
+impl KeyRepeat {
+    pub fn new(delay: Duration, interval: Duration) -> Self {
+        Self {
+            delay,
+            interval,
+            last: None,
+        }
+    }
+
+    /// ### fresh
+    ///
+    /// Returns a new `KeyRepeat` with the same configuration, but without any key history
+    pub fn fresh(&self) -> Self {
+        Self::new(self.delay, self.interval)
+    }
+
+    /// ### process_at
+    ///
+    /// Classify key event, received at `now`, as `Press` or `Repeat`
+    pub fn process_at(&mut self, mut ev: KeyEvent, now: Instant) -> KeyEvent {
+        let repeating = match self.last {
+            Some((last, at, repeating))
+                if last.code == ev.code && last.modifiers == ev.modifiers =>
+            {
+                // Allow some jitter on the repeat interval
+                let max_gap = if repeating {
+                    self.interval * 2
+                } else {
+                    self.delay + self.interval
+                };
+                now.duration_since(at) <= max_gap
+            }
+            _ => false,
+        };
+        ev.kind = match repeating {
+            true => KeyEventKind::Repeat,
+            false => KeyEventKind::Press,
+        };
+        self.last = Some((ev, now, repeating));
+        ev
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::event::Key;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_synthesize_key_repeat() {
+        let mut repeat = KeyRepeat::new(Duration::from_millis(300), Duration::from_millis(30));
+        let t0 = Instant::now();
+        let key = KeyEvent::from(Key::Down);
+        assert_eq!(repeat.process_at(key, t0).kind, KeyEventKind::Press);
+        // First repeat after delay
+        let t1 = t0 + Duration::from_millis(310);
+        assert_eq!(repeat.process_at(key, t1).kind, KeyEventKind::Repeat);
+        // Then after interval
+        let t2 = t1 + Duration::from_millis(30);
+        assert_eq!(repeat.process_at(key, t2).kind, KeyEventKind::Repeat);
+        let t3 = t2 + Duration::from_millis(35);
+        assert_eq!(repeat.process_at(key, t3).kind, KeyEventKind::Repeat);
+        // Released and pressed again later
+        let t4 = t3 + Duration::from_millis(200);
+        assert_eq!(repeat.process_at(key, t4).kind, KeyEventKind::Press);
+    }
+
+    #[test]
+    fn should_not_repeat_different_keys() {
+        let mut repeat = KeyRepeat::new(Duration::from_millis(300), Duration::from_millis(30));
+        let t0 = Instant::now();
+        assert_eq!(
+            repeat.process_at(KeyEvent::from(Key::Down), t0).kind,
+            KeyEventKind::Press
+        );
+        let t1 = t0 + Duration::from_millis(10);
+        assert_eq!(
+            repeat.process_at(KeyEvent::from(Key::Up), t1).kind,
+            KeyEventKind::Press
+        );
+        // Slow press of the same key
+        let t2 = t1 + Duration::from_secs(1);
+        assert_eq!(
+            repeat.process_at(KeyEvent::from(Key::Up), t2).kind,
+            KeyEventKind::Press
+        );
+    }
+}
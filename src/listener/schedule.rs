@@ -0,0 +1,246 @@
+//! ## Schedule
+//!
+//! One-shot events scheduled to be sent by the event listener after a delay
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::Event;
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// ## SharedSchedule
+///
+/// The schedule shared by the event listener, which adds events, and the worker, which sends them
+pub(super) type SharedSchedule<U> = Arc<Mutex<Schedule<U>>>;
+
+/// ## ScheduleHandle
+///
+/// Identifies a scheduled event, in order to cancel it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScheduleHandle(u64);
+
+/// ## Schedule
+///
+/// One-shot events scheduled after a delay.
+/// Events are scheduled without a deadline, which is set by the worker when it arms them,
+/// so that deadlines are computed with the worker clock.
+pub(super) struct Schedule<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    next_id: u64,
+    /// Events scheduled, but not armed yet by the worker
+    pending: Vec<(ScheduleHandle, Duration, Event<U>)>,
+    /// Armed events, ordered by deadline
+    armed: BinaryHeap<Reverse<Entry<U>>>,
+}
+
+impl<U> Default for Schedule<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            pending: Vec::new(),
+            armed: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<U> Schedule<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    /// ### push
+    ///
+    /// Schedule `event` to be sent after `delay` from the moment it is armed
+    pub fn push(&mut self, delay: Duration, event: Event<U>) -> ScheduleHandle {
+        let handle = ScheduleHandle(self.next_id);
+        self.next_id += 1;
+        self.pending.push((handle, delay, event));
+        handle
+    }
+
+    /// ### cancel
+    ///
+    /// Remove the event identified by `handle`; returns whether it was still scheduled
+    pub fn cancel(&mut self, handle: ScheduleHandle) -> bool {
+        let before = self.len();
+        self.pending.retain(|(x, _, _)| *x != handle);
+        self.armed.retain(|Reverse(x)| x.handle != handle);
+        self.len() != before
+    }
+
+    /// ### arm
+    ///
+    /// Set the deadline of the pending events, starting from `now`
+    pub fn arm(&mut self, now: Instant) {
+        for (handle, delay, event) in self.pending.drain(..) {
+            self.armed.push(Reverse(Entry {
+                deadline: now + delay,
+                handle,
+                event,
+            }));
+        }
+    }
+
+    /// ### next_deadline
+    ///
+    /// Returns the deadline of the first armed event, if any
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.armed.peek().map(|Reverse(x)| x.deadline)
+    }
+
+    /// ### pop_due
+    ///
+    /// Pop the first armed event, if its deadline has passed at `now`
+    pub fn pop_due(&mut self, now: Instant) -> Option<Event<U>> {
+        match self.next_deadline() {
+            Some(deadline) if deadline <= now => self.armed.pop().map(|Reverse(x)| x.event),
+            _ => None,
+        }
+    }
+
+    /// ### len
+    ///
+    /// Returns the amount of scheduled events
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.armed.len()
+    }
+
+    /// ### is_empty
+    ///
+    /// Returns whether there's no scheduled event
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// ## Entry
+///
+/// An armed event; entries are ordered by deadline, then by scheduling order
+struct Entry<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    deadline: Instant,
+    handle: ScheduleHandle,
+    event: Event<U>,
+}
+
+impl<U> Entry<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn key(&self) -> (Instant, u64) {
+        (self.deadline, self.handle.0)
+    }
+}
+
+impl<U> PartialEq for Entry<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<U> Eq for Entry<U> where U: Eq + PartialEq + Clone + PartialOrd + Send {}
+
+impl<U> PartialOrd for Entry<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U> Ord for Entry<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::MockEvent;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_pop_events_by_deadline() {
+        let mut schedule = Schedule::<MockEvent>::default();
+        schedule.push(Duration::from_secs(3), Event::User(MockEvent::Foo));
+        schedule.push(Duration::from_secs(1), Event::User(MockEvent::Bar));
+        schedule.push(Duration::from_secs(1), Event::Tick);
+        assert_eq!(schedule.len(), 3);
+        // Not armed yet
+        assert_eq!(schedule.next_deadline(), None);
+        let now = Instant::now();
+        schedule.arm(now);
+        assert_eq!(schedule.next_deadline(), Some(now + Duration::from_secs(1)));
+        assert_eq!(schedule.pop_due(now), None);
+        // Same deadline: scheduling order
+        let t = now + Duration::from_secs(1);
+        assert_eq!(schedule.pop_due(t), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(schedule.pop_due(t), Some(Event::Tick));
+        assert_eq!(schedule.pop_due(t), None);
+        let t = now + Duration::from_secs(5);
+        assert_eq!(schedule.pop_due(t), Some(Event::User(MockEvent::Foo)));
+        assert_eq!(schedule.pop_due(t), None);
+        assert_eq!(schedule.len(), 0);
+    }
+
+    #[test]
+    fn should_cancel_events() {
+        let mut schedule = Schedule::<MockEvent>::default();
+        let foo = schedule.push(Duration::from_secs(1), Event::User(MockEvent::Foo));
+        let bar = schedule.push(Duration::from_secs(2), Event::User(MockEvent::Bar));
+        assert_ne!(foo, bar);
+        // Armed
+        let now = Instant::now();
+        schedule.arm(now);
+        assert_eq!(schedule.cancel(foo), true);
+        assert_eq!(schedule.cancel(foo), false);
+        // Pending
+        let tick = schedule.push(Duration::from_secs(1), Event::Tick);
+        assert_eq!(schedule.cancel(tick), true);
+        assert_eq!(schedule.len(), 1);
+        let t = now + Duration::from_secs(3);
+        schedule.arm(t);
+        assert_eq!(schedule.pop_due(t), Some(Event::User(MockEvent::Bar)));
+        assert_eq!(schedule.cancel(bar), false);
+    }
+}
@@ -0,0 +1,125 @@
+//! ## Timing
+//!
+//! measures how long the worker spends in each iteration
+
+/**
+ * MIT License
+ *
+ * tui-realm - Copyright (C) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Amount of iterations the average iteration time is computed over
+const TIMING_WINDOW: usize = 64;
+
+/// ## IterationTiming
+///
+/// How long the event listener worker spends in each iteration, which is polling the due ports,
+/// sending ticks and scheduled events. The time spent waiting for the next event is not included.
+///
+/// Compare these numbers with the poll intervals of the ports: if the average is close to (or above)
+/// the shortest interval, the worker can't keep up and events are delayed; consider making the polls cheaper
+/// or increasing their interval. An occasional `max` above the interval is usually harmless (e.g. a slow poll
+/// on startup), while a high `average` is not.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IterationTiming {
+    /// Amount of iterations measured so far
+    pub iterations: u64,
+    /// Duration of the last iteration
+    pub last: Duration,
+    /// Average duration of the last iterations (up to 64)
+    pub average: Duration,
+    /// Longest iteration measured so far
+    pub max: Duration,
+}
+
+/// ## SharedTiming
+///
+/// Iteration timing, reported by the worker to the listener
+pub(crate) type SharedTiming = Arc<RwLock<IterationTiming>>;
+
+/// ## TimingRecorder
+///
+/// Records the duration of the iterations and keeps the rolling average up to date
+#[derive(Default)]
+pub(crate) struct TimingRecorder {
+    timing: IterationTiming,
+    /// Duration of the last iterations, oldest first
+    recent: VecDeque<Duration>,
+    /// Sum of `recent`
+    sum: Duration,
+}
+
+impl TimingRecorder {
+    /// ### record
+    ///
+    /// Record the duration of an iteration and returns the updated timing
+    pub fn record(&mut self, elapsed: Duration) -> IterationTiming {
+        if self.recent.len() == TIMING_WINDOW {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.sum -= oldest;
+            }
+        }
+        self.recent.push_back(elapsed);
+        self.sum += elapsed;
+        self.timing = IterationTiming {
+            iterations: self.timing.iterations + 1,
+            last: elapsed,
+            average: self.sum / self.recent.len() as u32,
+            max: self.timing.max.max(elapsed),
+        };
+        self.timing
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_record_iterations() {
+        let mut recorder = TimingRecorder::default();
+        recorder.record(Duration::from_millis(10));
+        let timing = recorder.record(Duration::from_millis(30));
+        assert_eq!(
+            timing,
+            IterationTiming {
+                iterations: 2,
+                last: Duration::from_millis(30),
+                average: Duration::from_millis(20),
+                max: Duration::from_millis(30),
+            }
+        );
+        // Average is computed over the last iterations only
+        for _ in 0..TIMING_WINDOW {
+            recorder.record(Duration::from_millis(1));
+        }
+        let timing = recorder.record(Duration::from_millis(1));
+        assert_eq!(timing.iterations, 67);
+        assert_eq!(timing.average, Duration::from_millis(1));
+        assert_eq!(timing.max, Duration::from_millis(30));
+    }
+}
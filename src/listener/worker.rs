@@ -25,12 +25,182 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{ListenerMsg, Port};
+use super::dedup::Dedup;
+use super::grab::{GrabResult, SharedGrab};
+use super::receiver::{self, Subscribers};
+use super::schedule::SharedSchedule;
+use super::timing::{SharedTiming, TimingRecorder};
+use super::{
+    AdaptivePoll, Clock, DragTracker, KeyRepeat, ListenerCmd, ListenerMsg, PasteBurst, Port,
+    PortId, PortKind, PortStatus, SystemClock, TickPriority,
+};
+use crate::event::ListenerWarning;
+use crate::Event;
+use std::collections::HashSet;
 use std::ops::{Add, Sub};
-use std::sync::{mpsc, Arc, RwLock};
+use std::sync::{mpsc, Arc, Condvar, Mutex, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// With high resolution ticks, the worker spin-waits for the events due within this time, instead of sleeping
+const HIGH_RES_SPIN: Duration = Duration::from_millis(2);
+
+// -- options
+
+/// ## EventFilter
+///
+/// A filter applied to events before sending them to the listener. Events for which it returns `false` are dropped
+pub(super) type EventFilter<U> = Arc<dyn Fn(&Event<U>) -> bool + Send + Sync>;
+
+/// ## ReadySignal
+///
+/// Flag set by the worker once it is up and running, with the condvar to wait for it
+pub(super) type ReadySignal = Arc<(Mutex<bool>, Condvar)>;
+
+/// ## TickHook
+///
+/// A callback invoked by the worker each time a tick fires
+pub(super) type TickHook = Arc<Mutex<dyn FnMut() + Send>>;
+
+/// ## DrainHook
+///
+/// A callback invoked for each event still queued when the listener is stopped
+pub(super) type DrainHook<U> = Arc<Mutex<dyn FnMut(Event<U>) + Send>>;
+
+/// ## SharedPorts
+///
+/// A new set of ports, stored by the listener and taken by the worker on its next iteration
+pub(super) type SharedPorts<U> = Arc<Mutex<Option<Vec<Port<U>>>>>;
+
+/// ## SizeQuery
+///
+/// A function returning the current size of the terminal as (columns, rows), if available
+pub(super) type SizeQuery = Arc<dyn Fn() -> Option<(u16, u16)> + Send + Sync>;
+
+/// ## WorkerOptions
+///
+/// Optional behaviours of the worker
+pub(super) struct WorkerOptions<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    /// If set, is used to synthesize key repeats
+    pub key_repeat: Option<KeyRepeat>,
+    /// If set, ports are polled less frequently when idle
+    pub adaptive_poll: Option<AdaptivePoll>,
+    /// If false, ticks reset the idle state of the adaptive poll, as port events do
+    pub idle_ignores_ticks: bool,
+    /// If set, events are sent only if accepted by the filter
+    pub filter: Option<EventFilter<U>>,
+    /// If set, a warning is sent when a port has been due for more than `threshold` times its interval
+    pub starvation_threshold: Option<u32>,
+    /// If true, the worker starts paused
+    pub paused: bool,
+    /// If true, `ListenerPaused` and `ListenerResumed` are sent when the worker is paused or resumed
+    pub pause_events: bool,
+    /// If true, ticks are paused between a `FocusLost` and the next `FocusGained`
+    pub pause_ticks_on_blur: bool,
+    /// Order of ticks relative to the events polled in the same iteration
+    pub tick_priority: TickPriority,
+    /// Delay before the first tick is sent
+    pub tick_initial_delay: Duration,
+    /// If set, is called each time a tick fires
+    pub on_tick: Option<TickHook>,
+    /// If set, `TooSmall` and `SizeOk` are sent when the terminal is resized below or back to the minimum size
+    pub min_size: Option<(u16, u16)>,
+    /// If set, is used to send the size of the terminal as a `WindowResize` when the worker starts
+    pub initial_size: Option<SizeQuery>,
+    /// If set, is given the events still queued when the listener is stopped.
+    /// Not used by the worker: the listener takes it on start
+    pub on_drain: Option<DrainHook<U>>,
+    /// If set, is used to synthesize paste events from bursts of keys
+    pub paste_burst: Option<PasteBurst>,
+    /// If true, key events are normalized (see `KeyEvent::normalized()`)
+    pub normalize_keys: bool,
+    /// If set, is used to aggregate mouse events into gestures
+    pub drag_tracker: Option<DragTracker>,
+    /// If true, the worker spin-waits the last moments before an event, to be more precise
+    pub high_res_ticks: bool,
+    /// If true, ticks are sent to the grab handler too
+    pub grab_ticks: bool,
+    /// If set, events identical to one sent shortly before are dropped
+    pub dedup: Option<Dedup<U>>,
+    /// Time source of the worker
+    pub clock: Arc<dyn Clock>,
+    /// If true, the events polled in the same iteration are sent one by one instead of as a `Batch`
+    #[cfg(test)]
+    pub unbatched: bool,
+}
+
+impl<U> Default for WorkerOptions<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn default() -> Self {
+        Self {
+            key_repeat: None,
+            adaptive_poll: None,
+            idle_ignores_ticks: true,
+            filter: None,
+            starvation_threshold: None,
+            paused: false,
+            pause_events: false,
+            pause_ticks_on_blur: false,
+            tick_priority: TickPriority::default(),
+            tick_initial_delay: Duration::ZERO,
+            on_tick: None,
+            min_size: None,
+            initial_size: None,
+            on_drain: None,
+            paste_burst: None,
+            normalize_keys: false,
+            drag_tracker: None,
+            high_res_ticks: false,
+            grab_ticks: false,
+            dedup: None,
+            clock: Arc::new(SystemClock),
+            #[cfg(test)]
+            unbatched: false,
+        }
+    }
+}
+
+impl<U> WorkerOptions<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    /// ### fresh
+    ///
+    /// Returns a copy of the options, without the state of the current worker
+    pub fn fresh(&self) -> Self {
+        Self {
+            key_repeat: self.key_repeat.as_ref().map(KeyRepeat::fresh),
+            adaptive_poll: self.adaptive_poll.as_ref().map(AdaptivePoll::fresh),
+            idle_ignores_ticks: self.idle_ignores_ticks,
+            filter: self.filter.clone(),
+            starvation_threshold: self.starvation_threshold,
+            paused: self.paused,
+            pause_events: self.pause_events,
+            pause_ticks_on_blur: self.pause_ticks_on_blur,
+            tick_priority: self.tick_priority,
+            tick_initial_delay: self.tick_initial_delay,
+            on_tick: self.on_tick.clone(),
+            min_size: self.min_size,
+            initial_size: self.initial_size.clone(),
+            on_drain: self.on_drain.clone(),
+            paste_burst: self.paste_burst.as_ref().map(PasteBurst::fresh),
+            normalize_keys: self.normalize_keys,
+            drag_tracker: self.drag_tracker.as_ref().map(DragTracker::fresh),
+            high_res_ticks: self.high_res_ticks,
+            grab_ticks: self.grab_ticks,
+            dedup: self.dedup.as_ref().map(Dedup::fresh),
+            clock: Arc::clone(&self.clock),
+            #[cfg(test)]
+            unbatched: self.unbatched,
+        }
+    }
+}
+
 // -- worker
 
 /// ## EventListenerWorker
@@ -42,10 +212,64 @@ where
 {
     ports: Vec<Port<U>>,
     sender: mpsc::Sender<ListenerMsg<U>>,
+    commands: mpsc::Receiver<ListenerCmd>,
     paused: Arc<RwLock<bool>>,
     running: Arc<RwLock<bool>>,
     next_tick: Instant,
     tick_interval: Option<Duration>,
+    key_repeat: Option<KeyRepeat>,
+    adaptive_poll: Option<AdaptivePoll>,
+    idle_ignores_ticks: bool,
+    filter: Option<EventFilter<U>>,
+    starvation_threshold: Option<u32>,
+    pause_events: bool,
+    pause_ticks_on_blur: bool,
+    tick_priority: TickPriority,
+    on_tick: Option<TickHook>,
+    min_size: Option<(u16, u16)>,
+    initial_size: Option<SizeQuery>,
+    paste_burst: Option<PasteBurst>,
+    normalize_keys: bool,
+    drag_tracker: Option<DragTracker>,
+    high_res_ticks: bool,
+    grab_ticks: bool,
+    dedup: Option<Dedup<U>>,
+    clock: Arc<dyn Clock>,
+    /// Whether the terminal was below the minimum size on the last resize
+    too_small: bool,
+    /// Ports for which a starvation warning has been sent and which haven't been polled since
+    starving: HashSet<PortId>,
+    /// Whether the worker has just been resumed from pause
+    resumed: bool,
+    /// Whether the worker was paused on the last check
+    was_paused: bool,
+    /// Whether the terminal has lost focus (`FocusLost` has been received and `FocusGained` hasn't yet)
+    blurred: bool,
+    /// Index of the port to poll first on the next iteration: the one after the first port polled last time
+    cursor: usize,
+    /// Status of the ports, shared with the listener
+    status: Arc<RwLock<Vec<PortStatus>>>,
+    /// Additional receivers events are broadcast to
+    subscribers: Subscribers<U>,
+    /// One-shot events scheduled by the listener
+    schedule: SharedSchedule<U>,
+    /// If set, sent messages are queued here, to be sent at once
+    batch: Option<Vec<ListenerMsg<U>>>,
+    /// Handler intercepting the events, installed by the listener
+    grab: SharedGrab<U>,
+    /// Duration of the iterations, shared with the listener
+    timing: SharedTiming,
+    /// Ports replacing the current ones, set by the listener
+    new_ports: SharedPorts<U>,
+    recorder: TimingRecorder,
+    /// Signal to raise once the worker is running
+    ready: Option<ReadySignal>,
+    /// If set, all the ports except this one are paused; the flag tells whether ticks are kept
+    exclusive: Option<(PortId, bool)>,
+    /// Kinds of the ports which are paused
+    paused_kinds: HashSet<PortKind>,
+    #[cfg(test)]
+    unbatched: bool,
 }
 
 impl<U> EventListenerWorker<U>
@@ -53,48 +277,359 @@ where
     U: Eq + PartialEq + Clone + PartialOrd + Send + 'static,
 {
     pub(super) fn new(
-        ports: Vec<Port<U>>,
+        mut ports: Vec<Port<U>>,
         sender: mpsc::Sender<ListenerMsg<U>>,
+        commands: mpsc::Receiver<ListenerCmd>,
         paused: Arc<RwLock<bool>>,
         running: Arc<RwLock<bool>>,
         tick_interval: Option<Duration>,
+        options: WorkerOptions<U>,
     ) -> Self {
+        let now = options.clock.now();
+        // Ports created before a mock clock must be due at the clock's time
+        for port in ports.iter_mut() {
+            port.schedule_at(std::cmp::min(port.next_poll(), now));
+        }
         Self {
             ports,
             sender,
+            commands,
             paused,
             running,
-            next_tick: Instant::now(),
+            next_tick: now.add(options.tick_initial_delay),
             tick_interval,
+            key_repeat: options.key_repeat,
+            adaptive_poll: options.adaptive_poll,
+            idle_ignores_ticks: options.idle_ignores_ticks,
+            filter: options.filter,
+            starvation_threshold: options.starvation_threshold,
+            pause_events: options.pause_events,
+            pause_ticks_on_blur: options.pause_ticks_on_blur,
+            tick_priority: options.tick_priority,
+            on_tick: options.on_tick,
+            min_size: options.min_size,
+            initial_size: options.initial_size,
+            paste_burst: options.paste_burst,
+            normalize_keys: options.normalize_keys,
+            drag_tracker: options.drag_tracker,
+            high_res_ticks: options.high_res_ticks,
+            grab_ticks: options.grab_ticks,
+            dedup: options.dedup,
+            clock: options.clock,
+            too_small: false,
+            starving: HashSet::new(),
+            resumed: false,
+            was_paused: options.paused,
+            blurred: false,
+            cursor: 0,
+            status: Arc::new(RwLock::new(Vec::new())),
+            subscribers: Arc::new(Mutex::new(Some(Vec::new()))),
+            schedule: SharedSchedule::default(),
+            batch: None,
+            grab: SharedGrab::default(),
+            timing: SharedTiming::default(),
+            new_ports: SharedPorts::default(),
+            recorder: TimingRecorder::default(),
+            ready: None,
+            exclusive: None,
+            paused_kinds: HashSet::new(),
+            #[cfg(test)]
+            unbatched: options.unbatched,
+        }
+    }
+
+    /// ### signal_ready
+    ///
+    /// Set the signal the worker raises once it is running
+    pub(super) fn signal_ready(mut self, ready: ReadySignal) -> Self {
+        self.ready = Some(ready);
+        self
+    }
+
+    /// ### set_ready
+    ///
+    /// Raise the ready signal, if not raised yet
+    fn set_ready(&mut self) {
+        if let Some(ready) = self.ready.take() {
+            let (lock, cvar) = &*ready;
+            if let Ok(mut ready) = lock.lock() {
+                *ready = true;
+            }
+            cvar.notify_all();
+        }
+    }
+
+    /// ### broadcast_to
+    ///
+    /// Set the subscribers the worker broadcasts the events to
+    pub(super) fn broadcast_to(mut self, subscribers: Subscribers<U>) -> Self {
+        self.subscribers = subscribers;
+        self
+    }
+
+    /// ### schedule_from
+    ///
+    /// Set the schedule the worker takes the one-shot events to send from
+    pub(super) fn schedule_from(mut self, schedule: SharedSchedule<U>) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// ### grab_from
+    ///
+    /// Set the shared slot of the grab handler
+    pub(super) fn grab_from(mut self, grab: SharedGrab<U>) -> Self {
+        self.grab = grab;
+        self
+    }
+
+    /// ### report_timing
+    ///
+    /// Set the shared slot the duration of the iterations is reported to
+    pub(super) fn report_timing(mut self, timing: SharedTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// ### ports_from
+    ///
+    /// Set the shared slot new ports are taken from
+    pub(super) fn ports_from(mut self, ports: SharedPorts<U>) -> Self {
+        self.new_ports = ports;
+        self
+    }
+
+    /// ### replace_ports
+    ///
+    /// If the listener has set a new set of ports, replace the current ports with them, at once.
+    /// The new ports are due immediately; pause state is kept
+    fn replace_ports(&mut self) {
+        let ports = match self.new_ports.lock().map(|mut x| x.take()) {
+            Ok(Some(ports)) => ports,
+            _ => return,
+        };
+        let now = self.clock.now();
+        self.ports = ports;
+        for port in self.ports.iter_mut() {
+            port.schedule_at(std::cmp::min(port.next_poll(), now));
+        }
+        self.cursor = 0;
+        self.starving.clear();
+        self.update_status();
+    }
+
+    /// ### grabbed
+    ///
+    /// Returns whether the message has been consumed by the grab handler, if any.
+    /// Errors are never grabbed, while ticks are grabbed only if `grab_ticks` is set
+    fn grabbed(&self, msg: &ListenerMsg<U>) -> bool {
+        let mut grab = match self.grab.lock() {
+            Ok(grab) => grab,
+            Err(_) => return false,
+        };
+        let handler = match grab.as_mut() {
+            Some(handler) => handler,
+            None => return false,
+        };
+        let ev = match msg {
+            ListenerMsg::Tick if self.grab_ticks => Event::Tick,
+            ListenerMsg::User(ev) => ev.clone(),
+            _ => return false,
+        };
+        handler(ev) == GrabResult::Consume
+    }
+
+    /// ### send_scheduled
+    ///
+    /// Send the scheduled events which are due, arming the events scheduled since the last iteration
+    fn send_scheduled(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        let now = self.clock.now();
+        let due: Vec<Event<U>> = match self.schedule.lock() {
+            Ok(mut schedule) => {
+                schedule.arm(now);
+                std::iter::from_fn(|| schedule.pop_due(now)).collect()
+            }
+            Err(_) => Vec::new(),
+        };
+        for ev in due {
+            let msg = ListenerMsg::User(ev);
+            if self.accepts(&msg) {
+                self.send(msg)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// ### send
+    ///
+    /// Send message to listener and broadcast events to subscribers, unless consumed by the grab handler.
+    /// While batching, the message is queued until `flush_batch()` is called
+    fn send(&mut self, msg: ListenerMsg<U>) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        if self.grabbed(&msg) {
+            return Ok(());
+        }
+        match &msg {
+            ListenerMsg::Tick => receiver::broadcast(&self.subscribers, &Event::Tick),
+            ListenerMsg::User(ev) => receiver::broadcast(&self.subscribers, ev),
+            ListenerMsg::Batch(events) => events
+                .iter()
+                .for_each(|ev| receiver::broadcast(&self.subscribers, ev)),
+            ListenerMsg::Error(_) => {}
+        }
+        match self.batch.as_mut() {
+            Some(batch) => {
+                batch.push(msg);
+                Ok(())
+            }
+            None => self.sender.send(msg),
+        }
+    }
+
+    /// ### flush_batch
+    ///
+    /// Stop batching and send the queued messages: consecutive events are sent at once as a `Batch`,
+    /// in order to reduce the overhead of the channel, while errors are sent on their own
+    fn flush_batch(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        let mut events = Vec::new();
+        for msg in self.batch.take().unwrap_or_default() {
+            match msg {
+                ListenerMsg::Tick => events.push(Event::Tick),
+                ListenerMsg::User(ev) => events.push(ev),
+                ListenerMsg::Batch(batch) => events.extend(batch),
+                ListenerMsg::Error(err) => {
+                    self.send_events(std::mem::take(&mut events))?;
+                    self.sender.send(ListenerMsg::Error(err))?;
+                }
+            }
+        }
+        self.send_events(events)
+    }
+
+    /// ### send_events
+    ///
+    /// Send `events` to the listener; as a `Batch` only if there's more than one
+    fn send_events(
+        &self,
+        mut events: Vec<Event<U>>,
+    ) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        match events.len() {
+            0 => Ok(()),
+            1 => self.sender.send(ListenerMsg::User(events.remove(0))),
+            _ => self.sender.send(ListenerMsg::Batch(events)),
+        }
+    }
+
+    /// ### report_status
+    ///
+    /// Set the shared state the worker reports the status of the ports to
+    pub(super) fn report_status(mut self, status: Arc<RwLock<Vec<PortStatus>>>) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// ### update_status
+    ///
+    /// Update the status of the ports shared with the listener
+    fn update_status(&self) {
+        if let Ok(mut status) = self.status.write() {
+            *status = self
+                .ports
+                .iter()
+                .enumerate()
+                .map(|(id, port)| PortStatus {
+                    paused: self.port_paused(id),
+                    ..port.status(id)
+                })
+                .collect();
         }
     }
 
+    /// ### port_paused
+    ///
+    /// Returns whether the port with `id` is paused, since another port has been made exclusive
+    /// or since ports of its kind have been paused
+    fn port_paused(&self, id: PortId) -> bool {
+        matches!(self.exclusive, Some((exclusive, _)) if exclusive != id)
+            || self
+                .ports
+                .get(id)
+                .map(|port| self.paused_kinds.contains(&port.kind()))
+                .unwrap_or(false)
+    }
+
+    /// ### min_active_interval
+    ///
+    /// Returns the smallest interval among the ports which aren't paused
+    fn min_active_interval(&self) -> Duration {
+        (0..self.ports.len())
+            .filter(|id| !self.port_paused(*id))
+            .map(|id| *self.ports[id].interval())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// ### ticks_paused
+    ///
+    /// Returns whether ticks are paused, since a port has been made exclusive without keeping ticks
+    /// or since the terminal has lost focus and ticks are paused on blur
+    fn ticks_paused(&self) -> bool {
+        matches!(self.exclusive, Some((_, false))) || (self.pause_ticks_on_blur && self.blurred)
+    }
+
     /// ### calc_next_tick
     ///
     /// Calculate next tick time.
     /// If tick is None, panics.
     fn calc_next_tick(&mut self) {
-        self.next_tick = Instant::now().add(self.tick_interval.unwrap());
+        self.next_tick = self.clock.now().add(self.tick_interval.unwrap());
+    }
+
+    /// ### set_tick_interval
+    ///
+    /// Set a new tick interval. The next tick is brought forward if it was scheduled
+    /// later than `now + interval`
+    fn set_tick_interval(&mut self, interval: Option<Duration>) {
+        self.tick_interval = interval;
+        if let Some(interval) = interval {
+            self.next_tick = std::cmp::min(self.next_tick, self.clock.now().add(interval));
+        }
     }
 
     /// ### next_event
     ///
     /// Calc the distance in time between now and the first upcoming event
     fn next_event(&self) -> Duration {
-        let now = Instant::now();
+        let now = self.clock.now();
         let fallback_time = now.add(Duration::from_secs(60));
         // Get first upcoming event from ports
         let min_listener_event = self
             .ports
             .iter()
-            .map(|x| x.next_poll())
+            .enumerate()
+            .filter(|(id, _)| !self.port_paused(*id))
+            .map(|(_, x)| x.next_poll())
             .min()
             .unwrap_or(fallback_time);
-        let next_tick = match self.tick_interval.is_some() {
+        // If backing off, ports won't be polled before the end of the backoff
+        let min_listener_event = match self.adaptive_poll.as_ref().and_then(|x| x.idle_until()) {
+            Some(idle_until) => std::cmp::max(min_listener_event, idle_until),
+            None => min_listener_event,
+        };
+        let next_tick = match self.tick_interval.is_some() && !self.ticks_paused() {
             false => fallback_time,
             true => self.next_tick,
         };
         let min_time = std::cmp::min(min_listener_event, next_tick);
+        // Keys held back by the paste heuristic must be sent once the burst is over
+        let min_time = match self.paste_burst.as_ref().and_then(|x| x.deadline()) {
+            Some(deadline) => std::cmp::min(min_time, deadline),
+            None => min_time,
+        };
+        // Scheduled events
+        let min_time = match self.schedule.lock().ok().and_then(|x| x.next_deadline()) {
+            Some(deadline) => std::cmp::min(min_time, deadline),
+            None => min_time,
+        };
         // If min time is > now, returns diff, otherwise return 0
         if min_time > now {
             min_time.sub(now)
@@ -103,6 +638,22 @@ where
         }
     }
 
+    /// ### idle
+    ///
+    /// Returns whether the worker has nothing to do until a command is received:
+    /// there's no port to poll (or they're all paused), ticks are disabled (or paused), no key is held back
+    /// and no event is scheduled
+    fn idle(&self) -> bool {
+        (0..self.ports.len()).all(|id| self.port_paused(id))
+            && (self.tick_interval.is_none() || self.ticks_paused())
+            && self
+                .paste_burst
+                .as_ref()
+                .and_then(|x| x.deadline())
+                .is_none()
+            && self.schedule.lock().map(|x| x.is_empty()).unwrap_or(true)
+    }
+
     /// ### running
     ///
     /// Returns whether should keep running
@@ -123,6 +674,24 @@ where
         false
     }
 
+    /// ### check_paused
+    ///
+    /// Returns whether worker is paused; if pause events are enabled, `ListenerPaused` or `ListenerResumed`
+    /// is sent when the paused state has changed since the last check
+    fn check_paused(&mut self) -> Result<bool, mpsc::SendError<ListenerMsg<U>>> {
+        let paused = self.paused();
+        if paused != self.was_paused {
+            self.was_paused = paused;
+            if self.pause_events {
+                self.send(ListenerMsg::User(match paused {
+                    true => Event::ListenerPaused,
+                    false => Event::ListenerResumed,
+                }))?;
+            }
+        }
+        Ok(paused)
+    }
+
     /// ### should_tick
     ///
     /// Returns whether it's time to tick.
@@ -130,7 +699,7 @@ where
     fn should_tick(&self) -> bool {
         match self.tick_interval {
             None => false,
-            Some(_) => self.next_tick <= Instant::now(),
+            Some(_) => !self.ticks_paused() && self.next_tick <= self.clock.now(),
         }
     }
 
@@ -138,90 +707,433 @@ where
     ///
     /// Send tick to listener and calc next tick
     fn send_tick(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
-        // Send tick
-        match self.sender.send(ListenerMsg::Tick) {
+        // Call tick hook before the tick is enqueued
+        if let Some(on_tick) = self.on_tick.as_ref() {
+            if let Ok(mut on_tick) = on_tick.lock() {
+                on_tick();
+            }
+        }
+        // Send tick, unless filtered out
+        if self.accepts(&ListenerMsg::Tick) {
             // Terminate thread on send failed
-            Err(err) => Err(err),
-            Ok(_) => {
-                // Calc next tick
-                self.calc_next_tick();
-                Ok(())
+            self.send(ListenerMsg::Tick)?;
+        }
+        // Ticks are internally generated, so they count as activity only if requested
+        if !self.idle_ignores_ticks {
+            if let Some(adaptive_poll) = self.adaptive_poll.as_mut() {
+                adaptive_poll.reset();
             }
         }
+        // Calc next tick
+        self.calc_next_tick();
+        Ok(())
+    }
+
+    /// ### accepts
+    ///
+    /// Returns whether the message is accepted by the event filter.
+    /// Errors are always accepted
+    fn accepts(&self, msg: &ListenerMsg<U>) -> bool {
+        match (msg, self.filter.as_ref()) {
+            (ListenerMsg::Tick, Some(filter)) => filter(&Event::Tick),
+            (ListenerMsg::User(ev), Some(filter)) => filter(ev),
+            _ => true,
+        }
     }
 
     /// ### poll
     ///
     /// Poll and send poll to listener. Calc next poll.
-    /// Returns only the messages, while the None returned by poll are discarded
-    #[allow(clippy::needless_collect)]
+    /// Returns only the messages, while the None returned by poll are discarded.
+    /// All the ports which are due are polled on each iteration, starting from the port after the one which was
+    /// polled first in the previous iteration, so that the events of ports which are ready at the same time
+    /// are not always sent in the same order.
+    /// Since there is no per-iteration event budget, this only rotates the order of the events: it doesn't
+    /// prevent starvation, which can't happen as no due port is ever skipped.
     fn poll(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
-        let msg: Vec<ListenerMsg<U>> = self
-            .ports
-            .iter_mut()
-            .map(|x| {
-                if x.should_poll() {
-                    let msg = match x.poll() {
-                        Ok(Some(ev)) => Some(ListenerMsg::User(ev)),
-                        Ok(None) => None,
-                        Err(err) => Some(ListenerMsg::Error(err)),
-                    };
-                    // Update next poll
-                    x.calc_next_poll();
-                    msg
-                } else {
-                    None
+        #[cfg(test)]
+        if self.unbatched {
+            return self.poll_ports();
+        }
+        // Events produced by the same poll are sent at once
+        self.batch = Some(Vec::new());
+        let result = self.poll_ports();
+        result.and(self.flush_batch())
+    }
+
+    /// ### poll_ports
+    ///
+    /// Poll the ports which are due and send their events
+    fn poll_ports(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        let now = self.clock.now();
+        // Send the keys held back by the paste heuristic, if the burst is over
+        let expired = self
+            .paste_burst
+            .as_mut()
+            .map(|x| x.expire_at(now))
+            .unwrap_or_default();
+        for ev in expired {
+            self.dispatch(ListenerMsg::User(ev))?;
+        }
+        if let Some(adaptive_poll) = self.adaptive_poll.as_ref() {
+            if adaptive_poll.is_backing_off(now) {
+                return Ok(());
+            }
+        }
+        let len = self.ports.len();
+        let mut msg: Vec<ListenerMsg<U>> = self.starved_ports(now);
+        let mut first_polled = None;
+        // Only events returned by ports count as activity for the adaptive poll
+        let mut events = false;
+        for i in (0..len).map(|x| (self.cursor + x) % len) {
+            if self.port_paused(i) {
+                continue;
+            }
+            let port = &mut self.ports[i];
+            if port.should_poll_at(self.clock.now()) {
+                first_polled.get_or_insert(i);
+                self.starving.remove(&i);
+                match port.poll() {
+                    Ok(Some(ev)) => {
+                        events = true;
+                        msg.push(ListenerMsg::User(ev));
+                    }
+                    Ok(None) => {}
+                    Err(err) => msg.push(ListenerMsg::Error(err)),
                 }
-            })
-            .flatten()
-            .collect();
+                // Update next poll
+                port.calc_next_poll_at(self.clock.now());
+            }
+        }
+        let polled = first_polled.is_some();
+        if let Some(first) = first_polled {
+            self.update_status();
+            self.cursor = (first + 1) % len;
+        }
+        if polled && self.adaptive_poll.is_some() {
+            let base = self.min_active_interval();
+            if let Some(adaptive_poll) = self.adaptive_poll.as_mut() {
+                adaptive_poll.update(events, base, now);
+            }
+        }
         // Send messages
-        match msg
-            .into_iter()
-            .map(|x| self.sender.send(x))
-            .filter(|x| x.is_err())
-            .map(|x| x.err().unwrap())
-            .next()
-        {
+        for msg in msg.into_iter() {
+            match (self.process(msg, now), self.paste_burst.as_mut()) {
+                (ListenerMsg::User(ev), Some(paste_burst)) => {
+                    for ev in paste_burst.process_at(ev, now) {
+                        self.dispatch(ListenerMsg::User(ev))?;
+                    }
+                }
+                (msg, _) => self.dispatch(msg)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// ### dispatch
+    ///
+    /// Send the processed message to the listener, if accepted by the filter,
+    /// followed by the size change and the mouse gesture it causes, if any
+    fn dispatch(&mut self, msg: ListenerMsg<U>) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        match &msg {
+            ListenerMsg::User(Event::FocusLost) => self.blurred = true,
+            ListenerMsg::User(Event::FocusGained) => self.blurred = false,
+            _ => {}
+        }
+        let size_change = self.size_change(&msg);
+        let gesture = self.gesture(&msg);
+        for msg in std::iter::once(msg).chain(size_change).chain(gesture) {
+            if self.accepts(&msg) && !self.duplicate(&msg) {
+                self.send(msg)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// ### duplicate
+    ///
+    /// Returns whether the message is an event identical to one sent within the dedup window, if set
+    fn duplicate(&mut self, msg: &ListenerMsg<U>) -> bool {
+        let now = self.clock.now();
+        match (msg, self.dedup.as_mut()) {
+            (ListenerMsg::User(ev), Some(dedup)) => !dedup.process_at(ev, now),
+            _ => false,
+        }
+    }
+
+    /// ### size_change
+    ///
+    /// If `msg` is a resize which crosses the minimum size, returns `TooSmall` or `SizeOk`
+    fn size_change(&mut self, msg: &ListenerMsg<U>) -> Option<ListenerMsg<U>> {
+        let (cols, rows, min) = match (msg, self.min_size) {
+            (ListenerMsg::User(Event::WindowResize(cols, rows)), Some(min)) => (*cols, *rows, min),
+            _ => return None,
+        };
+        let too_small = cols < min.0 || rows < min.1;
+        match (self.too_small, too_small) {
+            (false, true) => {
+                self.too_small = true;
+                Some(ListenerMsg::User(Event::TooSmall { cols, rows, min }))
+            }
+            (true, false) => {
+                self.too_small = false;
+                Some(ListenerMsg::User(Event::SizeOk))
+            }
+            _ => None,
+        }
+    }
+
+    /// ### gesture
+    ///
+    /// If `msg` is a mouse event which completes a gesture, returns the gesture
+    fn gesture(&mut self, msg: &ListenerMsg<U>) -> Option<ListenerMsg<U>> {
+        match (msg, self.drag_tracker.as_mut()) {
+            (ListenerMsg::User(ev), Some(drag_tracker)) => {
+                drag_tracker.process(ev).map(ListenerMsg::User)
+            }
+            _ => None,
+        }
+    }
+
+    /// ### starved_ports
+    ///
+    /// Returns a warning for each port which has been due for more than `starvation_threshold` times its interval.
+    /// A warning is sent only once, until the port is polled again.
+    /// The check is skipped right after resuming from pause, since all ports are overdue then.
+    fn starved_ports(&mut self, now: Instant) -> Vec<ListenerMsg<U>> {
+        let threshold = match self.starvation_threshold {
+            Some(threshold) => threshold,
+            None => return Vec::new(),
+        };
+        if self.resumed {
+            self.resumed = false;
+            return Vec::new();
+        }
+        // While backing off, ports are not due before the end of the backoff
+        let idle_until = self.adaptive_poll.as_ref().and_then(|x| x.idle_until());
+        let mut warnings = Vec::new();
+        for (id, port) in self.ports.iter().enumerate() {
+            let last_poll = match port.last_poll() {
+                Some(last_poll) if !self.port_paused(id) => last_poll,
+                _ => continue,
+            };
+            let due = match idle_until {
+                Some(idle_until) => std::cmp::max(port.next_poll(), idle_until),
+                None => port.next_poll(),
+            };
+            if now.saturating_duration_since(due) > *port.interval() * threshold
+                && self.starving.insert(id)
+            {
+                warnings.push(ListenerMsg::User(Event::ListenerWarning(
+                    ListenerWarning::PortStarved {
+                        port_id: id,
+                        elapsed: now.saturating_duration_since(last_poll),
+                    },
+                )));
+            }
+        }
+        warnings
+    }
+
+    /// ### process
+    ///
+    /// Process message polled from ports before sending it to the listener
+    fn process(&mut self, msg: ListenerMsg<U>, now: Instant) -> ListenerMsg<U> {
+        let msg = match msg {
+            ListenerMsg::User(Event::Keyboard(key)) if self.normalize_keys => {
+                ListenerMsg::User(Event::Keyboard(key.normalized()))
+            }
+            msg => msg,
+        };
+        match (msg, self.key_repeat.as_mut()) {
+            (ListenerMsg::User(Event::Keyboard(key)), Some(key_repeat)) => {
+                ListenerMsg::User(Event::Keyboard(key_repeat.process_at(key, now)))
+            }
+            (msg, _) => msg,
+        }
+    }
+
+    /// ### wait
+    ///
+    /// Wait up to `timeout` for commands from the listener; if `timeout` is `None`, wait until a command arrives.
+    /// Returns as soon as a command has been received, after applying all the pending commands,
+    /// so that the schedule can be computed again.
+    fn wait(&mut self, timeout: Option<Duration>) {
+        let timeout = timeout.map(|x| self.clock.wait_timeout(x));
+        let cmd = match timeout {
+            Some(timeout) => self.commands.recv_timeout(timeout),
+            None => self
+                .commands
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match cmd {
+            Ok(cmd) => {
+                self.apply(cmd);
+                while let Ok(cmd) = self.commands.try_recv() {
+                    self.apply(cmd);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            // Listener has gone; just wait
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                thread::sleep(timeout.unwrap_or(Duration::from_secs(60)))
+            }
+        }
+    }
+
+    /// ### wait_high_res
+    ///
+    /// Sleep till shortly before the next event, then spin-wait until it is due, applying commands as they arrive.
+    /// Since the OS may wake up a sleeping thread late, this is the only way to deliver ticks precisely
+    fn wait_high_res(&mut self) {
+        let timeout = self.next_event();
+        if timeout > HIGH_RES_SPIN {
+            self.wait(Some(timeout - HIGH_RES_SPIN));
+        }
+        // Commands may have changed the schedule
+        let deadline = Instant::now() + self.clock.wait_timeout(self.next_event());
+        while Instant::now() < deadline {
+            if let Ok(cmd) = self.commands.try_recv() {
+                self.apply(cmd);
+                return;
+            }
+            std::hint::spin_loop();
+        }
+    }
+
+    /// ### apply
+    ///
+    /// Apply command received from listener
+    fn apply(&mut self, cmd: ListenerCmd) {
+        match cmd {
+            ListenerCmd::SetInterval(id, interval) => {
+                if let Some(port) = self.ports.get_mut(id) {
+                    port.set_interval_at(interval, self.clock.now());
+                }
+                self.update_status();
+            }
+            ListenerCmd::SetTickInterval(interval) => self.set_tick_interval(interval),
+            ListenerCmd::PauseAllExcept(id, keep_ticks) => {
+                self.exclusive = Some((id, keep_ticks));
+                self.update_status();
+            }
+            ListenerCmd::UnpauseAll => {
+                self.exclusive = None;
+                self.update_status();
+            }
+            ListenerCmd::PauseKind(kind) => {
+                self.paused_kinds.insert(kind);
+                self.update_status();
+            }
+            ListenerCmd::UnpauseKind(kind) => {
+                self.paused_kinds.remove(&kind);
+                self.update_status();
+            }
+            ListenerCmd::Wake => {}
+        }
+    }
+
+    /// ### send_initial_size
+    ///
+    /// Send the current size of the terminal as a `WindowResize`, if enabled and if the size can be read.
+    /// It's sent only once, when the worker starts
+    fn send_initial_size(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        match self.initial_size.take().and_then(|query| query()) {
+            Some((cols, rows)) => self.dispatch(ListenerMsg::User(Event::WindowResize(cols, rows))),
             None => Ok(()),
-            Some(e) => Err(e),
         }
     }
 
+    /// ### iterate
+    ///
+    /// Poll ports and send tick if due, in the order defined by the tick priority
+    fn iterate(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        self.replace_ports();
+        if self.tick_priority == TickPriority::BeforeInput && self.should_tick() {
+            self.send_tick()?;
+        }
+        // Iter ports and Send messages
+        self.poll()?;
+        self.send_scheduled()?;
+        if self.tick_priority == TickPriority::AfterInput && self.should_tick() {
+            self.send_tick()?;
+        }
+        Ok(())
+    }
+
+    /// ### timed_iterate
+    ///
+    /// Iterate and report how long the iteration took. Iterations are measured with the wall clock,
+    /// since they measure the actual work of the worker
+    fn timed_iterate(&mut self) -> Result<(), mpsc::SendError<ListenerMsg<U>>> {
+        let start = Instant::now();
+        let result = self.iterate();
+        let timing = self.recorder.record(start.elapsed());
+        if let Ok(mut shared) = self.timing.write() {
+            *shared = timing;
+        }
+        result
+    }
+
     /// ### run
     ///
     /// thread run method
     pub(super) fn run(&mut self) {
+        if self.send_initial_size().is_err() {
+            return;
+        }
         loop {
             // Check if running or send_error has occurred
             if !self.running() {
                 break;
             }
             // If paused, wait and resume cycle
-            if self.paused() {
-                thread::sleep(Duration::from_millis(25));
+            let paused = match self.check_paused() {
+                Ok(paused) => paused,
+                Err(_) => break,
+            };
+            if paused {
+                self.resumed = true;
+                self.set_ready();
+                self.wait(Some(Duration::from_millis(25)));
                 continue;
             }
-            // Iter ports and Send messages
-            if self.poll().is_err() {
+            // Poll ports and tick
+            if self.timed_iterate().is_err() {
                 break;
             }
-            // Tick
-            if self.should_tick() && self.send_tick().is_err() {
-                break;
+            // Ready once the ports have been polled for the first time
+            self.set_ready();
+            // Sleep till next event or command; if there's nothing to do, park until a command arrives
+            match self.idle() {
+                true => self.wait(None),
+                false if self.high_res_ticks => self.wait_high_res(),
+                false => self.wait(Some(self.next_event())),
             }
-            // Sleep till next event
-            thread::sleep(self.next_event());
         }
     }
 }
 
+impl<U> Drop for EventListenerWorker<U>
+where
+    U: Eq + PartialEq + Clone + PartialOrd + Send,
+{
+    fn drop(&mut self) {
+        // Disconnect subscribers, even if the worker has panicked
+        receiver::close(&self.subscribers);
+    }
+}
+
 #[cfg(test)]
 mod test {
 
-    use super::super::{ListenerError, ListenerResult};
+    use super::super::clock::MockClock;
+    use super::super::{ListenerError, ListenerResult, Poll};
     use super::*;
-    use crate::core::event::{Key, KeyEvent};
+    use crate::core::event::{
+        Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, MouseGesture,
+    };
     use crate::mock::MockEvent;
     use crate::mock::MockPoll;
     use crate::Event;
@@ -229,27 +1141,295 @@ mod test {
     use pretty_assertions::assert_eq;
 
     #[test]
-    fn worker_should_send_poll() {
+    fn worker_should_send_pause_transitions() {
         let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
         let paused = Arc::new(RwLock::new(false));
-        let paused_t = Arc::clone(&paused);
-        let running = Arc::new(RwLock::new(true));
-        let running_t = Arc::clone(&running);
         let mut worker = EventListenerWorker::<MockEvent>::new(
-            vec![Port::new(
-                Box::new(MockPoll::default()),
-                Duration::from_secs(5),
-            )],
+            vec![],
             tx,
-            paused_t,
-            running_t,
+            cmd_rx,
+            Arc::clone(&paused),
+            Arc::new(RwLock::new(true)),
             None,
+            WorkerOptions {
+                pause_events: true,
+                ..Default::default()
+            },
         );
-        assert!(worker.poll().is_ok());
-        assert!(worker.next_event() <= Duration::from_secs(5));
-        // Receive
-        assert_eq!(
-            ListenerResult::from(rx.recv().ok().unwrap()).ok().unwrap(),
+        assert_eq!(worker.check_paused().ok().unwrap(), false);
+        *paused.write().unwrap() = true;
+        assert_eq!(worker.check_paused().ok().unwrap(), true);
+        // No event until the state changes again
+        assert_eq!(worker.check_paused().ok().unwrap(), true);
+        *paused.write().unwrap() = false;
+        assert_eq!(worker.check_paused().ok().unwrap(), false);
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(events, vec![Event::ListenerPaused, Event::ListenerResumed]);
+        // Disabled
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            Arc::clone(&paused),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        *paused.write().unwrap() = true;
+        assert_eq!(worker.check_paused().ok().unwrap(), true);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn worker_should_wait_high_res_till_next_tick() {
+        let (tx, rx) = mpsc::channel();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            Some(Duration::from_millis(10)),
+            WorkerOptions {
+                high_res_ticks: true,
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        worker.calc_next_tick();
+        // Tick is not due yet
+        worker.wait_high_res();
+        assert_eq!(worker.should_tick(), false);
+        // Returns immediately once the tick is due
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(worker.next_event(), Duration::ZERO);
+        worker.wait_high_res();
+        assert_eq!(worker.should_tick(), true);
+        assert!(worker.iterate().is_ok());
+        assert!(matches!(rx.try_recv().ok().unwrap(), ListenerMsg::Tick));
+        // Commands received while waiting are applied and reschedule the next tick
+        assert!(cmd_tx
+            .send(ListenerCmd::SetTickInterval(Some(Duration::from_millis(1))))
+            .is_ok());
+        worker.wait_high_res();
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(worker.should_tick(), true);
+    }
+
+    #[test]
+    fn worker_should_pause_ticks_on_blur() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let events = vec![
+            Event::FocusLost,
+            Event::Keyboard(KeyEvent::from(Key::Enter)),
+            Event::FocusGained,
+        ];
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(events, Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            Some(Duration::from_secs(1)),
+            WorkerOptions {
+                pause_ticks_on_blur: true,
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        assert!(worker.poll().is_ok());
+        // Blurred: no tick, but ports are still polled
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(worker.should_tick(), false);
+        assert_eq!(worker.idle(), false);
+        assert!(worker.iterate().is_ok());
+        assert_eq!(worker.should_tick(), false);
+        // Focus is back
+        assert!(worker.poll().is_ok());
+        assert_eq!(worker.should_tick(), true);
+        assert!(worker.iterate().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::FocusLost,
+                Event::Keyboard(KeyEvent::from(Key::Enter)),
+                Event::FocusGained,
+                Event::Tick,
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_should_send_initial_size() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(
+                vec![Event::Keyboard(KeyEvent::from(Key::Enter))],
+                Duration::ZERO,
+            )],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                initial_size: Some(Arc::new(|| Some((60, 20)))),
+                min_size: Some((80, 24)),
+                ..Default::default()
+            },
+        );
+        assert!(worker.send_initial_size().is_ok());
+        assert!(worker.poll().is_ok());
+        // Sent only once
+        assert!(worker.send_initial_size().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::WindowResize(60, 20),
+                Event::TooSmall {
+                    cols: 60,
+                    rows: 20,
+                    min: (80, 24)
+                },
+                Event::Keyboard(KeyEvent::from(Key::Enter)),
+            ]
+        );
+        // Size not available
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                initial_size: Some(Arc::new(|| None)),
+                ..Default::default()
+            },
+        );
+        assert!(worker.send_initial_size().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn worker_should_measure_iterations() {
+        /// Poll which takes 20ms to return
+        struct SlowPoll;
+
+        impl Poll<MockEvent> for SlowPoll {
+            fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+                std::thread::sleep(Duration::from_millis(20));
+                Ok(None)
+            }
+        }
+
+        let (tx, _rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let timing = SharedTiming::default();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::new(Box::new(SlowPoll), Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        )
+        .report_timing(Arc::clone(&timing));
+        for _ in 0..3 {
+            assert!(worker.timed_iterate().is_ok());
+        }
+        let timing = *timing.read().unwrap();
+        assert_eq!(timing.iterations, 3);
+        assert!(timing.last >= Duration::from_millis(20));
+        assert!(timing.average >= Duration::from_millis(20));
+        assert!(timing.max >= timing.average);
+    }
+
+    #[test]
+    fn worker_should_replace_ports() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let new_ports = SharedPorts::default();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(
+                std::iter::repeat(Event::User(MockEvent::Foo)),
+                Duration::ZERO,
+            )],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        )
+        .ports_from(Arc::clone(&new_ports));
+        assert!(worker.iterate().is_ok());
+        assert_eq!(unpack(&rx).len(), 1);
+        *new_ports.lock().unwrap() = Some(vec![Port::from_iter(
+            std::iter::repeat(Event::User(MockEvent::Bar)),
+            Duration::ZERO,
+        )]);
+        assert!(worker.iterate().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(events, vec![Event::User(MockEvent::Bar)]);
+        assert!(new_ports.lock().unwrap().is_none());
+        // No ports
+        *new_ports.lock().unwrap() = Some(Vec::new());
+        assert!(worker.iterate().is_ok());
+        assert!(rx.try_recv().is_err());
+        assert_eq!(worker.idle(), true);
+    }
+
+    #[test]
+    fn worker_should_send_poll() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let paused_t = Arc::clone(&paused);
+        let running = Arc::new(RwLock::new(true));
+        let running_t = Arc::clone(&running);
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_secs(5),
+            )],
+            tx,
+            cmd_rx,
+            paused_t,
+            running_t,
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(worker.poll().is_ok());
+        assert!(worker.next_event() <= Duration::from_secs(5));
+        // Receive
+        assert_eq!(
+            ListenerResult::from(rx.recv().ok().unwrap()).ok().unwrap(),
             Some(Event::Keyboard(KeyEvent::from(Key::Enter)))
         );
     }
@@ -257,6 +1437,7 @@ mod test {
     #[test]
     fn worker_should_send_tick() {
         let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
         let paused = Arc::new(RwLock::new(false));
         let paused_t = Arc::clone(&paused);
         let running = Arc::new(RwLock::new(true));
@@ -267,9 +1448,11 @@ mod test {
                 Duration::from_secs(5),
             )],
             tx,
+            cmd_rx,
             paused_t,
             running_t,
             Some(Duration::from_secs(1)),
+            WorkerOptions::default(),
         );
         assert!(worker.send_tick().is_ok());
         assert!(worker.next_tick > Instant::now());
@@ -280,36 +1463,273 @@ mod test {
         );
     }
 
+    #[test]
+    fn worker_should_order_tick_and_input_by_priority() {
+        for (priority, expected) in [
+            (
+                TickPriority::AfterInput,
+                vec![Event::Keyboard(KeyEvent::from(Key::Enter)), Event::Tick],
+            ),
+            (
+                TickPriority::BeforeInput,
+                vec![Event::Tick, Event::Keyboard(KeyEvent::from(Key::Enter))],
+            ),
+        ] {
+            let (tx, rx) = mpsc::channel();
+            let (_cmd_tx, cmd_rx) = mpsc::channel();
+            let mut worker = EventListenerWorker::<MockEvent>::new(
+                vec![Port::new(
+                    Box::new(MockPoll::default()),
+                    Duration::from_secs(5),
+                )],
+                tx,
+                cmd_rx,
+                Arc::new(RwLock::new(false)),
+                Arc::new(RwLock::new(true)),
+                Some(Duration::from_secs(1)),
+                WorkerOptions {
+                    tick_priority: priority,
+                    ..Default::default()
+                },
+            );
+            // Both port and tick are due
+            assert!(worker.iterate().is_ok());
+            let events: Vec<Event<MockEvent>> = rx
+                .try_iter()
+                .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+                .collect();
+            assert_eq!(events, expected);
+        }
+    }
+
+    #[test]
+    fn worker_should_send_too_small_and_size_ok() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let resizes = vec![
+            Event::WindowResize(100, 30),
+            Event::WindowResize(60, 30),
+            Event::WindowResize(70, 20),
+            Event::WindowResize(80, 24),
+            Event::WindowResize(120, 40),
+            Event::WindowResize(80, 23),
+        ];
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(resizes, Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                min_size: Some((80, 24)),
+                ..Default::default()
+            },
+        );
+        for _ in 0..6 {
+            assert!(worker.iterate().is_ok());
+        }
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::WindowResize(100, 30),
+                Event::WindowResize(60, 30),
+                Event::TooSmall {
+                    cols: 60,
+                    rows: 30,
+                    min: (80, 24)
+                },
+                Event::WindowResize(70, 20),
+                Event::WindowResize(80, 24),
+                Event::SizeOk,
+                Event::WindowResize(120, 40),
+                Event::WindowResize(80, 23),
+                Event::TooSmall {
+                    cols: 80,
+                    rows: 23,
+                    min: (80, 24)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_should_synthesize_paste() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let keys = "hello"
+            .chars()
+            .map(|x| Event::Keyboard(KeyEvent::from(Key::Char(x))))
+            .chain(std::iter::once(Event::Keyboard(KeyEvent::from(Key::Esc))));
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(keys, Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                paste_burst: Some(PasteBurst::new(Duration::from_secs(5), 3)),
+                ..Default::default()
+            },
+        );
+        for _ in 0..5 {
+            assert!(worker.iterate().is_ok());
+        }
+        // Keys are held back until the burst is over
+        assert!(rx.try_recv().is_err());
+        assert!(worker.next_event() <= Duration::from_secs(5));
+        assert!(worker.iterate().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::Paste(String::from("hello")),
+                Event::Keyboard(KeyEvent::from(Key::Esc))
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_should_aggregate_mouse_gestures() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mouse = |kind, column, row| {
+            Event::Mouse(MouseEvent::new(kind, column, row, KeyModifiers::NONE))
+        };
+        let left = MouseButton::Left;
+        let events = vec![
+            mouse(MouseEventKind::Down(left), 10, 5),
+            mouse(MouseEventKind::Drag(left), 12, 5),
+            mouse(MouseEventKind::Drag(left), 15, 8),
+            mouse(MouseEventKind::Up(left), 15, 8),
+        ];
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(events.clone(), Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                drag_tracker: Some(DragTracker::default()),
+                ..Default::default()
+            },
+        );
+        for _ in 0..4 {
+            assert!(worker.iterate().is_ok());
+        }
+        let received: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        let drag = |current| {
+            Event::MouseGesture(MouseGesture::Drag {
+                start: (10, 5),
+                current,
+                button: left,
+            })
+        };
+        assert_eq!(
+            received,
+            vec![
+                events[0].clone(),
+                events[1].clone(),
+                drag((12, 5)),
+                events[2].clone(),
+                drag((15, 8)),
+                events[3].clone(),
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_should_normalize_keys() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let keys = vec![
+            Event::Keyboard(KeyEvent::new(Key::Char('a'), KeyModifiers::SHIFT)),
+            Event::Keyboard(KeyEvent::from(Key::Char('A'))),
+        ];
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(keys, Duration::ZERO)],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                normalize_keys: true,
+                ..Default::default()
+            },
+        );
+        assert!(worker.iterate().is_ok());
+        assert!(worker.iterate().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::Keyboard(KeyEvent::from(Key::Char('A'))),
+                Event::Keyboard(KeyEvent::from(Key::Char('A')))
+            ]
+        );
+    }
+
     #[test]
     fn worker_should_calc_times_correctly_with_tick() {
         let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
         let paused = Arc::new(RwLock::new(false));
         let paused_t = Arc::clone(&paused);
         let running = Arc::new(RwLock::new(true));
         let running_t = Arc::clone(&running);
+        let clock = MockClock::new();
+        let t0 = clock.now();
         let mut worker = EventListenerWorker::<MockEvent>::new(
             vec![Port::new(
                 Box::new(MockPoll::default()),
                 Duration::from_secs(5),
             )],
             tx,
+            cmd_rx,
             paused_t,
             running_t,
             Some(Duration::from_secs(1)),
+            WorkerOptions {
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
         );
         assert_eq!(worker.running(), true);
         // Should set next events to now
-        assert!(worker.next_event() <= Duration::from_secs(1));
-        assert!(worker.next_tick <= Instant::now());
+        assert_eq!(worker.next_event(), Duration::ZERO);
+        assert_eq!(worker.next_tick, t0);
         assert!(worker.should_tick());
         // Calc next
-        let expected_next_tick = Instant::now().add(Duration::from_secs(1));
         worker.calc_next_tick();
-        assert!(worker.next_tick >= expected_next_tick);
+        assert_eq!(worker.next_tick, t0 + Duration::from_secs(1));
+        // Port is still due
+        assert_eq!(worker.next_event(), Duration::ZERO);
+        assert!(worker.poll().is_ok());
         // Next event should be in 1 second (tick)
-        assert!(worker.next_event() <= Duration::from_secs(1));
+        assert_eq!(worker.next_event(), Duration::from_secs(1));
         // Now should no more tick and poll
         assert_eq!(worker.should_tick(), false);
+        clock.advance(Duration::from_millis(999));
+        assert_eq!(worker.should_tick(), false);
+        assert_eq!(worker.next_event(), Duration::from_millis(1));
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(worker.should_tick(), true);
         // Stop
         {
             let mut running_flag = match running.write() {
@@ -324,9 +1744,99 @@ mod test {
         drop(rx);
     }
 
+    #[test]
+    fn worker_should_drop_duplicates_within_dedup_window() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(
+                vec![
+                    Event::User(MockEvent::Foo),
+                    Event::User(MockEvent::Foo),
+                    Event::User(MockEvent::Bar),
+                    Event::User(MockEvent::Foo),
+                ],
+                Duration::from_millis(50),
+            )],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                dedup: Some(Dedup::new(Duration::from_millis(100))),
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        // Events are returned 50ms apart
+        for _ in 0..4 {
+            assert!(worker.iterate().is_ok());
+            clock.advance(Duration::from_millis(50));
+        }
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        // The second `Foo` is sent 50ms after the first; the third 150ms after it
+        assert_eq!(
+            events,
+            vec![
+                Event::User(MockEvent::Foo),
+                Event::User(MockEvent::Bar),
+                Event::User(MockEvent::Foo),
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_should_keep_tick_and_poll_cadence() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::from_iter(
+                std::iter::repeat(Event::User(MockEvent::Foo)),
+                Duration::from_millis(500),
+            )],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            Some(Duration::from_millis(100)),
+            WorkerOptions {
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        // Run for 10 seconds, iterating whenever the worker would wake up
+        let mut elapsed = Duration::ZERO;
+        while elapsed < Duration::from_secs(10) {
+            assert!(worker.iterate().is_ok());
+            let next = worker.next_event();
+            clock.advance(next);
+            elapsed += next;
+        }
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        // Ticks and polls at time 0 included, nothing is lost nor doubled
+        assert_eq!(events.iter().filter(|x| **x == Event::Tick).count(), 100);
+        assert_eq!(
+            events
+                .iter()
+                .filter(|x| **x == Event::User(MockEvent::Foo))
+                .count(),
+            20
+        );
+    }
+
     #[test]
     fn worker_should_calc_times_correctly_without_tick() {
         let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
         let paused = Arc::new(RwLock::new(false));
         let paused_t = Arc::clone(&paused);
         let running = Arc::new(RwLock::new(true));
@@ -337,9 +1847,11 @@ mod test {
                 Duration::from_secs(3),
             )],
             tx,
+            cmd_rx,
             paused_t,
             running_t,
             None,
+            WorkerOptions::default(),
         );
         assert_eq!(worker.running(), true);
         assert_eq!(worker.paused(), false);
@@ -363,16 +1875,552 @@ mod test {
         drop(rx);
     }
 
+    #[test]
+    fn worker_should_poll_ports_in_round_robin() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let running = Arc::new(RwLock::new(true));
+        let ports = (0..3)
+            .map(|x| {
+                Port::new(
+                    Box::new(NamedPoll {
+                        name: x.to_string(),
+                    }) as Box<dyn Poll<MockEvent>>,
+                    Duration::ZERO,
+                )
+            })
+            .collect();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            ports,
+            tx,
+            cmd_rx,
+            paused,
+            running,
+            None,
+            WorkerOptions::default(),
+        );
+        let mut firsts = vec![0; 3];
+        let mut events = vec![0; 3];
+        for _ in 0..30 {
+            assert!(worker.poll().is_ok());
+            for (i, msg) in unpack(&rx).into_iter().enumerate() {
+                if let ListenerMsg::User(Event::User(MockEvent::Hello(name))) = msg {
+                    let port: usize = name.parse().unwrap();
+                    events[port] += 1;
+                    if i == 0 {
+                        firsts[port] += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(events, vec![30, 30, 30]);
+        assert_eq!(firsts, vec![10, 10, 10]);
+    }
+
+    #[test]
+    fn worker_should_rotate_from_the_first_port_polled() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let running = Arc::new(RwLock::new(true));
+        // The first port is due only on the first iteration
+        let ports = [Duration::from_secs(3600), Duration::ZERO, Duration::ZERO]
+            .into_iter()
+            .enumerate()
+            .map(|(x, interval)| {
+                Port::new(
+                    Box::new(NamedPoll {
+                        name: x.to_string(),
+                    }) as Box<dyn Poll<MockEvent>>,
+                    interval,
+                )
+            })
+            .collect();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            ports,
+            tx,
+            cmd_rx,
+            paused,
+            running,
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(worker.poll().is_ok());
+        assert_eq!(unpack(&rx).len(), 3);
+        let mut firsts = vec![0; 3];
+        for _ in 0..30 {
+            assert!(worker.poll().is_ok());
+            if let Some(ListenerMsg::User(Event::User(MockEvent::Hello(name)))) =
+                unpack(&rx).into_iter().next()
+            {
+                firsts[name.parse::<usize>().unwrap()] += 1;
+            }
+        }
+        // The ports which are due alternate, regardless of the port which is not
+        assert_eq!(firsts, vec![0, 15, 15]);
+    }
+
+    #[test]
+    fn worker_should_warn_about_starved_ports() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let running = Arc::new(RwLock::new(true));
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![
+                Port::new(Box::new(MockPoll::default()), Duration::from_millis(10)),
+                Port::new(
+                    Box::new(BlockingPoll {
+                        block: Duration::from_millis(100),
+                    }),
+                    Duration::from_millis(10),
+                ),
+            ],
+            tx,
+            cmd_rx,
+            paused,
+            running,
+            None,
+            WorkerOptions {
+                starvation_threshold: Some(2),
+                ..Default::default()
+            },
+        );
+        // First poll: port 1 blocks and starves port 0
+        assert!(worker.poll().is_ok());
+        assert_eq!(unpack(&rx).len(), 1);
+        assert!(worker.poll().is_ok());
+        let msg: Vec<ListenerMsg<MockEvent>> = unpack(&rx);
+        assert_eq!(msg.len(), 2);
+        assert!(matches!(
+            msg[0],
+            ListenerMsg::User(Event::ListenerWarning(ListenerWarning::PortStarved {
+                port_id: 0,
+                elapsed
+            })) if elapsed >= Duration::from_millis(100)
+        ));
+    }
+
+    #[test]
+    fn worker_should_apply_commands() {
+        let (tx, _rx) = mpsc::channel();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let running = Arc::new(RwLock::new(true));
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::new(
+                Box::new(MockPoll::default()),
+                Duration::from_secs(5),
+            )],
+            tx,
+            cmd_rx,
+            paused,
+            running,
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(cmd_tx
+            .send(ListenerCmd::SetInterval(0, Duration::from_secs(1)))
+            .is_ok());
+        // Unknown port is ignored
+        assert!(cmd_tx
+            .send(ListenerCmd::SetInterval(1, Duration::from_secs(1)))
+            .is_ok());
+        worker.wait(Some(Duration::from_millis(100)));
+        assert_eq!(*worker.ports[0].interval(), Duration::from_secs(1));
+        // Tick interval
+        assert!(cmd_tx
+            .send(ListenerCmd::SetTickInterval(Some(Duration::from_millis(
+                500
+            ))))
+            .is_ok());
+        worker.wait(Some(Duration::from_millis(100)));
+        assert_eq!(worker.tick_interval, Some(Duration::from_millis(500)));
+        assert!(worker.next_tick <= Instant::now().add(Duration::from_millis(500)));
+        assert!(cmd_tx.send(ListenerCmd::SetTickInterval(None)).is_ok());
+        worker.wait(Some(Duration::from_millis(100)));
+        assert_eq!(worker.tick_interval, None);
+        assert_eq!(worker.should_tick(), false);
+    }
+
+    #[test]
+    fn worker_should_be_idle_without_ports_and_ticks() {
+        let (tx, _rx) = mpsc::channel();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        assert_eq!(worker.idle(), true);
+        // Parks until a command arrives
+        assert!(cmd_tx
+            .send(ListenerCmd::SetTickInterval(Some(Duration::from_secs(1))))
+            .is_ok());
+        worker.wait(None);
+        assert_eq!(worker.idle(), false);
+        // All ports paused
+        let (tx, _rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::null(Duration::from_millis(10))],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        assert_eq!(worker.idle(), false);
+        worker.apply(ListenerCmd::PauseAllExcept(1, false));
+        assert_eq!(worker.idle(), true);
+    }
+
+    #[test]
+    fn worker_should_be_woken_up_by_pause_guard() {
+        use super::super::guard::{PauseGuard, PauseGuards};
+
+        let (tx, rx) = mpsc::channel();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let paused = Arc::new(RwLock::new(false));
+        let running = Arc::new(RwLock::new(true));
+        let worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            Arc::clone(&paused),
+            Arc::clone(&running),
+            None,
+            WorkerOptions {
+                pause_events: true,
+                ..Default::default()
+            },
+        );
+        // Without ports and ticks, the worker parks until a command arrives
+        assert_eq!(worker.idle(), true);
+        let ready: ReadySignal = Arc::new((Mutex::new(false), Condvar::new()));
+        let mut worker = worker.signal_ready(Arc::clone(&ready));
+        let thread = thread::spawn(move || worker.run());
+        // Wait for the worker to be parked
+        {
+            let (lock, cvar) = &*ready;
+            let guard = lock.lock().unwrap();
+            let (guard, _) = cvar
+                .wait_timeout_while(guard, Duration::from_secs(5), |ready| !*ready)
+                .unwrap();
+            assert_eq!(*guard, true);
+        }
+        let next_pause_event = || loop {
+            match rx.recv_timeout(Duration::from_secs(5)) {
+                Ok(ListenerMsg::User(ev @ (Event::ListenerPaused | Event::ListenerResumed))) => {
+                    return ev
+                }
+                Ok(_) => continue,
+                Err(_) => panic!("no pause event received"),
+            }
+        };
+        let guards = Arc::new(Mutex::new(PauseGuards::default()));
+        let guard = PauseGuard::new(Arc::clone(&paused), guards, cmd_tx.clone());
+        assert_eq!(next_pause_event(), Event::ListenerPaused);
+        drop(guard);
+        assert_eq!(next_pause_event(), Event::ListenerResumed);
+        // Stop
+        *running.write().unwrap() = false;
+        assert!(cmd_tx.send(ListenerCmd::Wake).is_ok());
+        assert!(thread.join().is_ok());
+    }
+
+    #[test]
+    fn worker_should_pause_ports_by_kind() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![
+                Port::builder(Box::new(NamedPoll {
+                    name: String::from("input"),
+                }))
+                .interval(Duration::ZERO)
+                .kind(PortKind::Input)
+                .build(),
+                Port::builder(Box::new(NamedPoll {
+                    name: String::from("background"),
+                }))
+                .interval(Duration::ZERO)
+                .build(),
+                Port::timer(Duration::ZERO, || MockEvent::Hello(String::from("timer"))),
+            ],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        worker.apply(ListenerCmd::PauseKind(PortKind::Background));
+        worker.apply(ListenerCmd::PauseKind(PortKind::Timer));
+        assert_eq!(worker.port_paused(0), false);
+        assert_eq!(worker.port_paused(1), true);
+        assert_eq!(worker.port_paused(2), true);
+        assert!(worker.poll().is_ok());
+        let events: Vec<Event<MockEvent>> = unpack(&rx)
+            .into_iter()
+            .map(|x| ListenerResult::from(x).ok().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![Event::User(MockEvent::Hello(String::from("input")))]
+        );
+        // Pause input too: nothing left to poll
+        worker.apply(ListenerCmd::PauseKind(PortKind::Input));
+        assert_eq!(worker.idle(), true);
+        worker.apply(ListenerCmd::UnpauseKind(PortKind::Background));
+        assert_eq!(worker.port_paused(1), false);
+        assert_eq!(worker.port_paused(2), true);
+        assert_eq!(worker.idle(), false);
+    }
+
+    #[test]
+    fn worker_should_back_off_when_ports_only_fail() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![Port::new(Box::new(FailingPoll), Duration::from_millis(10))],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                adaptive_poll: Some(AdaptivePoll::new(1, Duration::from_secs(1))),
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        assert!(worker.poll().is_ok());
+        // The error is still reported, but it isn't an event
+        assert!(matches!(
+            unpack(&rx).as_slice(),
+            [ListenerMsg::Error(ListenerError::PollFailed(_))]
+        ));
+        assert_eq!(
+            worker.adaptive_poll.as_ref().unwrap().idle_until(),
+            Some(clock.now() + Duration::from_millis(10))
+        );
+    }
+
+    #[test]
+    fn worker_should_ignore_paused_ports_in_backoff_base() {
+        let (tx, _rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![
+                Port::builder(Box::new(NamedPoll {
+                    name: String::from("input"),
+                }))
+                .interval(Duration::from_millis(1))
+                .kind(PortKind::Input)
+                .build(),
+                Port::new(
+                    Box::new(BlockingPoll {
+                        block: Duration::ZERO,
+                    }),
+                    Duration::from_millis(50),
+                ),
+            ],
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions {
+                adaptive_poll: Some(AdaptivePoll::new(1, Duration::from_secs(1))),
+                clock: Arc::new(clock.clone()),
+                ..Default::default()
+            },
+        );
+        worker.apply(ListenerCmd::PauseKind(PortKind::Input));
+        assert!(worker.poll().is_ok());
+        // Backoff starts from the interval of the background port
+        assert_eq!(
+            worker.adaptive_poll.as_ref().unwrap().idle_until(),
+            Some(clock.now() + Duration::from_millis(50))
+        );
+    }
+
     #[test]
     #[should_panic]
     fn worker_should_panic_when_trying_next_tick_without_it() {
         let (tx, _) = mpsc::channel();
+        let (_, cmd_rx) = mpsc::channel();
         let paused = Arc::new(RwLock::new(false));
         let paused_t = Arc::clone(&paused);
         let running = Arc::new(RwLock::new(true));
         let running_t = Arc::clone(&running);
-        let mut worker =
-            EventListenerWorker::<MockEvent>::new(vec![], tx, paused_t, running_t, None);
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            vec![],
+            tx,
+            cmd_rx,
+            paused_t,
+            running_t,
+            None,
+            WorkerOptions::default(),
+        );
         worker.calc_next_tick();
     }
+
+    #[test]
+    fn worker_should_batch_events_of_one_poll() {
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let ports = |n: usize| -> Vec<Port<MockEvent>> {
+            (0..n)
+                .map(|i| {
+                    Port::new(
+                        Box::new(NamedPoll {
+                            name: i.to_string(),
+                        }),
+                        Duration::from_secs(5),
+                    )
+                })
+                .collect()
+        };
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            ports(3),
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(worker.poll().is_ok());
+        let msg: Vec<ListenerMsg<MockEvent>> = rx.try_iter().collect();
+        assert_eq!(msg.len(), 1);
+        assert!(matches!(&msg[0], ListenerMsg::Batch(events) if events.len() == 3));
+        // Nothing batched outside of poll
+        assert!(worker.batch.is_none());
+        // Single events are sent as they are
+        let (tx, rx) = mpsc::channel();
+        let (_cmd_tx, cmd_rx) = mpsc::channel();
+        let mut worker = EventListenerWorker::<MockEvent>::new(
+            ports(1),
+            tx,
+            cmd_rx,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(true)),
+            None,
+            WorkerOptions::default(),
+        );
+        assert!(worker.poll().is_ok());
+        let msg: Vec<ListenerMsg<MockEvent>> = rx.try_iter().collect();
+        assert_eq!(msg.len(), 1);
+        assert!(matches!(&msg[0], ListenerMsg::User(_)));
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with `cargo test -- --ignored`"]
+    fn batched_sends_benchmark() {
+        // Poll the same high-rate ports sending their events one by one and at once,
+        // with a consumer receiving concurrently
+        const EVENTS: usize = 50_000;
+        const PORTS: usize = 10;
+        let bench = |unbatched: bool| {
+            let (tx, rx) = mpsc::channel::<ListenerMsg<MockEvent>>();
+            let (_cmd_tx, cmd_rx) = mpsc::channel();
+            let consumer = thread::spawn(move || {
+                let mut received = 0;
+                while let Ok(msg) = rx.recv() {
+                    received += match msg {
+                        ListenerMsg::Batch(events) => events.len(),
+                        _ => 1,
+                    };
+                }
+                received
+            });
+            let ports = (0..PORTS)
+                .map(|i| {
+                    Port::new(
+                        Box::new(NamedPoll {
+                            name: i.to_string(),
+                        }),
+                        Duration::ZERO,
+                    )
+                })
+                .collect();
+            let mut worker = EventListenerWorker::<MockEvent>::new(
+                ports,
+                tx,
+                cmd_rx,
+                Arc::new(RwLock::new(false)),
+                Arc::new(RwLock::new(true)),
+                None,
+                WorkerOptions {
+                    unbatched,
+                    ..Default::default()
+                },
+            );
+            let t_start = Instant::now();
+            for _ in 0..EVENTS / PORTS {
+                assert!(worker.poll().is_ok());
+            }
+            drop(worker);
+            assert_eq!(consumer.join().unwrap(), EVENTS);
+            t_start.elapsed()
+        };
+        let unbatched = bench(true);
+        let batched = bench(false);
+        println!(
+            "{} events from {} ports: unbatched {:?}, batched {:?}",
+            EVENTS, PORTS, unbatched, batched
+        );
+    }
+
+    /// Receive the messages sent by the worker, unpacking batches
+    fn unpack(rx: &mpsc::Receiver<ListenerMsg<MockEvent>>) -> Vec<ListenerMsg<MockEvent>> {
+        rx.try_iter()
+            .flat_map(|msg| match msg {
+                ListenerMsg::Batch(events) => events.into_iter().map(ListenerMsg::User).collect(),
+                msg => vec![msg],
+            })
+            .collect()
+    }
+
+    /// Poll which always returns an event with its name
+    struct NamedPoll {
+        name: String,
+    }
+
+    impl Poll<MockEvent> for NamedPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            Ok(Some(Event::User(MockEvent::Hello(self.name.clone()))))
+        }
+    }
+
+    /// Poll which always fails
+    struct FailingPoll;
+
+    impl Poll<MockEvent> for FailingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            Err(ListenerError::PollFailed(String::from("broken")))
+        }
+    }
+
+    /// Poll which blocks for `block` on each call
+    struct BlockingPoll {
+        block: Duration,
+    }
+
+    impl Poll<MockEvent> for BlockingPoll {
+        fn poll(&mut self) -> ListenerResult<Option<Event<MockEvent>>> {
+            thread::sleep(self.block);
+            Ok(None)
+        }
+    }
 }
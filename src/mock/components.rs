@@ -114,18 +114,22 @@ impl Component<MockMsg, MockEvent> for MockFooInput {
             Event::Keyboard(KeyEvent {
                 code: Key::Left,
                 modifiers: _,
+                ..
             }) => Cmd::Move(Direction::Left),
             Event::Keyboard(KeyEvent {
                 code: Key::Right,
                 modifiers: _,
+                ..
             }) => Cmd::Move(Direction::Right),
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => Cmd::Type(ch),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(MockMsg::FooSubmit(self.component.states.text.clone())),
             _ => Cmd::None,
         };
@@ -157,18 +161,22 @@ impl Component<MockMsg, MockEvent> for MockBarInput {
             Event::Keyboard(KeyEvent {
                 code: Key::Left,
                 modifiers: _,
+                ..
             }) => Cmd::Move(Direction::Left),
             Event::Keyboard(KeyEvent {
                 code: Key::Right,
                 modifiers: _,
+                ..
             }) => Cmd::Move(Direction::Right),
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => Cmd::Type(ch),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter,
                 modifiers: KeyModifiers::NONE,
+                ..
             }) => return Some(MockMsg::BarSubmit(self.component.states.text.clone())),
             Event::Tick => return Some(MockMsg::BarTick),
             _ => Cmd::None,
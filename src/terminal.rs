@@ -44,10 +44,23 @@ pub enum TerminalError {
     CannotToggleRawMode,
     #[error("cannot clear screen")]
     CannotClear,
+    #[error("cannot get cursor position")]
+    CannotGetCursorPosition,
     #[error("backend doesn't support this command")]
     Unsupported,
 }
 
+/// ## SelectedBackend
+///
+/// Describes which backend has been used to build a `TerminalBridge`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectedBackend {
+    /// The configured backend has been initialized successfully
+    Primary,
+    /// The configured backend failed to initialize, so the fallback terminal is in use
+    Fallback,
+}
+
 /// ## TerminalBridge
 ///
 /// An helper around `Terminal` to quickly setup and perform on terminal.
@@ -56,6 +69,7 @@ pub enum TerminalError {
 /// If you need more advance terminal command, you can get a reference to it using the `raw()` and `raw_mut()` methods.
 pub struct TerminalBridge {
     terminal: Terminal,
+    backend: SelectedBackend,
 }
 
 impl TerminalBridge {
@@ -65,9 +79,50 @@ impl TerminalBridge {
     pub fn new() -> TerminalResult<Self> {
         Ok(Self {
             terminal: Self::adapt_new_terminal()?,
+            backend: SelectedBackend::Primary,
         })
     }
 
+    /// ### new_or_fallback
+    ///
+    /// Instantiates a new Terminal bridge trying the configured backend first.
+    /// If the configured backend fails to initialize (e.g. on a headless CI), the terminal returned by `fallback`
+    /// is used instead; the error raised by the configured backend is passed to `fallback`.
+    /// Use `backend()` to know which backend has been selected.
+    ///
+    /// > With crossterm, a terminal with a fixed viewport doesn't need to query stdout and makes a good fallback:
+    /// > `Terminal::with_options(CrosstermBackend::new(stdout()), TerminalOptions { viewport: Viewport::fixed(area) })`
+    pub fn new_or_fallback<F>(fallback: F) -> TerminalResult<Self>
+    where
+        F: FnOnce(TerminalError) -> TerminalResult<Terminal>,
+    {
+        Self::new_with(Self::adapt_new_terminal, fallback)
+    }
+
+    fn new_with<P, F>(primary: P, fallback: F) -> TerminalResult<Self>
+    where
+        P: FnOnce() -> TerminalResult<Terminal>,
+        F: FnOnce(TerminalError) -> TerminalResult<Terminal>,
+    {
+        match primary() {
+            Ok(terminal) => Ok(Self {
+                terminal,
+                backend: SelectedBackend::Primary,
+            }),
+            Err(err) => Ok(Self {
+                terminal: fallback(err)?,
+                backend: SelectedBackend::Fallback,
+            }),
+        }
+    }
+
+    /// ### backend
+    ///
+    /// Returns which backend has been selected when the bridge was instantiated
+    pub fn backend(&self) -> SelectedBackend {
+        self.backend
+    }
+
     /// ### enter_alternate_screen
     ///
     /// Enter in alternate screen using the terminal adapter
@@ -103,6 +158,19 @@ impl TerminalBridge {
         self.adapt_disable_raw_mode()
     }
 
+    /// ### cursor_position
+    ///
+    /// Query the terminal for the current position of the cursor, returned as (column, row), 0-based.
+    /// A Device Status Report is sent to the terminal, which must answer within a short timeout;
+    /// if it doesn't, `TerminalError::CannotGetCursorPosition` is returned.
+    ///
+    /// > Raw mode must be enabled, otherwise the terminal response is echoed and line-buffered.
+    /// > With termion, the response is read straight from stdin, so it may be consumed by the input listener,
+    /// > if it's running.
+    pub fn cursor_position(&mut self) -> TerminalResult<(u16, u16)> {
+        self.adapt_cursor_position()
+    }
+
     /// ### raw
     ///
     /// Returna an immutable reference to the raw `Terminal` structure
@@ -117,3 +185,50 @@ impl TerminalBridge {
         &mut self.terminal
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn should_report_fallback_error() {
+        let result = TerminalBridge::new_with(
+            || Err(TerminalError::CannotConnectStdout),
+            |err| {
+                assert!(matches!(err, TerminalError::CannotConnectStdout));
+                Err(TerminalError::Unsupported)
+            },
+        );
+        assert!(matches!(result, Err(TerminalError::Unsupported)));
+    }
+
+    #[test]
+    #[cfg(feature = "with-crossterm")]
+    fn should_fallback_when_primary_fails() {
+        use crate::tui::{
+            backend::CrosstermBackend,
+            layout::Rect,
+            terminal::{TerminalOptions, Viewport},
+        };
+        use pretty_assertions::assert_eq;
+        use std::io::stdout;
+
+        let area = Rect::new(0, 0, 80, 24);
+        let bridge = TerminalBridge::new_with(
+            || Err(TerminalError::CannotConnectStdout),
+            |_| {
+                Terminal::with_options(
+                    CrosstermBackend::new(stdout()),
+                    TerminalOptions {
+                        viewport: Viewport::fixed(area),
+                    },
+                )
+                .map_err(|_| TerminalError::CannotConnectStdout)
+            },
+        )
+        .unwrap();
+        assert_eq!(bridge.backend(), SelectedBackend::Fallback);
+        assert_eq!(bridge.raw().size().unwrap(), area);
+    }
+}
@@ -29,4 +29,4 @@ pub mod parser;
 mod types;
 
 // export types
-pub use types::{Email, PhoneNumber};
+pub use types::{Email, EmailParseError, PhoneNumber, PhoneNumberParseError};
@@ -25,11 +25,49 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use super::parser::{parse_email, parse_phone_number};
+
+use std::str::FromStr;
+use thiserror::Error;
+
+/// ## PhoneNumberParseError
+///
+/// Describes why a string is not a valid phone number
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PhoneNumberParseError {
+    #[error("phone number is empty")]
+    Empty,
+    #[error("invalid character `{0}` in phone number")]
+    InvalidCharacter(char),
+    #[error("invalid country code `{0}`: expected 1 to 4 digits")]
+    InvalidPrefix(String),
+    #[error("phone number has no digits after the country code")]
+    MissingNumber,
+    #[error("`{0}` is not a valid phone number")]
+    Invalid(String),
+}
+
+/// ## EmailParseError
+///
+/// Describes why a string is not a valid email address
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EmailParseError {
+    #[error("email address is empty")]
+    Empty,
+    #[error("email address has no `@`")]
+    MissingAt,
+    #[error("email address has no name before `@`")]
+    MissingName,
+    #[error("email address has no agent after `@`")]
+    MissingAgent,
+    #[error("`{0}` is not a valid email address")]
+    Invalid(String),
+}
 
 /// ## PhoneNumber
 ///
 /// Represents a phone number
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
 pub struct PhoneNumber {
     /// Prefix number (without `00` or `+`)
     pub prefix: Option<String>,
@@ -47,6 +85,38 @@ impl PhoneNumber {
         }
     }
 
+    /// ### parse
+    ///
+    /// Parse a phone number typed by the user (e.g. `+39 345 777 6117`, `0039 345-777-6117`, `345 777 6117`).
+    /// If the number has no country code, `default_prefix` is used, if any (e.g. `Some("39")`)
+    pub fn parse(s: &str, default_prefix: Option<&str>) -> Result<Self, PhoneNumberParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(PhoneNumberParseError::Empty);
+        }
+        if let Some(ch) = s
+            .chars()
+            .find(|x| !(x.is_ascii_digit() || x.is_whitespace() || "+-./".contains(*x)))
+        {
+            return Err(PhoneNumberParseError::InvalidCharacter(ch));
+        }
+        if let Some(prefix) = default_prefix
+            .filter(|x| x.is_empty() || x.len() > 4 || !x.chars().all(|x| x.is_ascii_digit()))
+        {
+            return Err(PhoneNumberParseError::InvalidPrefix(prefix.to_string()));
+        }
+        let mut phone =
+            parse_phone_number(s).ok_or_else(|| PhoneNumberParseError::Invalid(s.to_string()))?;
+        phone.number.retain(|x| x.is_ascii_digit());
+        if phone.number.is_empty() {
+            return Err(PhoneNumberParseError::MissingNumber);
+        }
+        if phone.prefix.is_none() {
+            phone.prefix = default_prefix.map(str::to_string);
+        }
+        Ok(phone)
+    }
+
     /// ### phone_number
     ///
     /// Returns the full number with syntax `+{prefix}{number}`
@@ -61,7 +131,7 @@ impl PhoneNumber {
 /// ## Email
 ///
 /// Represents an email address
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
 pub struct Email {
     /// Address name (e.g. `foo.bar@preema.it` => `foo.bar`)
     pub name: String,
@@ -77,6 +147,24 @@ impl Email {
         }
     }
 
+    /// ### parse
+    ///
+    /// Parse an email address typed by the user (e.g. `foo.bar@preema.it`)
+    pub fn parse(s: &str) -> Result<Self, EmailParseError> {
+        let s = s.trim();
+        let (name, agent) = match s.rsplit_once('@') {
+            _ if s.is_empty() => return Err(EmailParseError::Empty),
+            None => return Err(EmailParseError::MissingAt),
+            Some(("", _)) => return Err(EmailParseError::MissingName),
+            Some((_, "")) => return Err(EmailParseError::MissingAgent),
+            Some(parts) => parts,
+        };
+        match parse_email(s) {
+            Some(email) if email.name == name && email.agent == agent => Ok(email),
+            _ => Err(EmailParseError::Invalid(s.to_string())),
+        }
+    }
+
     /// ### address
     ///
     /// Returns the email address
@@ -85,6 +173,22 @@ impl Email {
     }
 }
 
+impl FromStr for PhoneNumber {
+    type Err = PhoneNumberParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, None)
+    }
+}
+
+impl FromStr for Email {
+    type Err = EmailParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -104,6 +208,83 @@ mod test {
         assert_eq!(phone.phone_number().as_str(), "3457776117");
     }
 
+    #[test]
+    fn phone_number_parse() {
+        assert_eq!(
+            PhoneNumber::parse("+39 345 777 6117", None).unwrap(),
+            PhoneNumber::new(Some("39"), "3457776117")
+        );
+        assert_eq!(
+            PhoneNumber::parse(" 0039 345.777/6117 ", Some("1")).unwrap(),
+            PhoneNumber::new(Some("39"), "3457776117")
+        );
+        assert_eq!(
+            PhoneNumber::parse("345-777-6117", Some("39")).unwrap(),
+            PhoneNumber::new(Some("39"), "3457776117")
+        );
+        assert_eq!(
+            "345 777 6117".parse::<PhoneNumber>().unwrap(),
+            PhoneNumber::new(None, "3457776117")
+        );
+        assert_eq!(
+            PhoneNumber::parse("  ", None).unwrap_err(),
+            PhoneNumberParseError::Empty
+        );
+        assert_eq!(
+            PhoneNumber::parse("345 CALL ME", None).unwrap_err(),
+            PhoneNumberParseError::InvalidCharacter('C')
+        );
+        assert_eq!(
+            PhoneNumber::parse("345 777 6117", Some("+39")).unwrap_err(),
+            PhoneNumberParseError::InvalidPrefix(String::from("+39"))
+        );
+        assert_eq!(
+            PhoneNumber::parse("+39", None).unwrap_err(),
+            PhoneNumberParseError::MissingNumber
+        );
+        assert_eq!(
+            "345 +39".parse::<PhoneNumber>().unwrap_err(),
+            PhoneNumberParseError::Invalid(String::from("345 +39"))
+        );
+    }
+
+    #[test]
+    fn email_parse() {
+        assert_eq!(
+            Email::parse("foo.bar@preema.it").unwrap(),
+            Email::new("foo.bar", "preema.it")
+        );
+        assert_eq!(
+            " cvisintin@youtube.com ".parse::<Email>().unwrap(),
+            Email::new("cvisintin", "youtube.com")
+        );
+        assert_eq!(Email::parse("").unwrap_err(), EmailParseError::Empty);
+        assert_eq!(
+            Email::parse("foo.bar").unwrap_err(),
+            EmailParseError::MissingAt
+        );
+        assert_eq!(
+            Email::parse("@preema.it").unwrap_err(),
+            EmailParseError::MissingName
+        );
+        assert_eq!(
+            Email::parse("foo.bar@").unwrap_err(),
+            EmailParseError::MissingAgent
+        );
+        assert_eq!(
+            Email::parse("foo bar@preema.it").unwrap_err(),
+            EmailParseError::Invalid(String::from("foo bar@preema.it"))
+        );
+        assert_eq!(
+            Email::parse("foo@bar@preema.it").unwrap_err(),
+            EmailParseError::Invalid(String::from("foo@bar@preema.it"))
+        );
+        assert_eq!(
+            EmailParseError::MissingAt.to_string(),
+            "email address has no `@`"
+        );
+    }
+
     #[test]
     fn email() {
         let email = Email::new("cvisintin", "youtube.com");